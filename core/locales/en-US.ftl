@@ -0,0 +1,219 @@
+# English, also the fallback for messages missing from another catalogue
+
+app-title = YT Monitoring App (by KRS)
+header-welcome = Welcome! Here is the status of your favorite YouTubers:
+footer-thanks = Thank you for being here, this was an app by Kushashwa Ravi Shrimali
+header-summary = { $live } of { $total } live, { $refreshed }
+summary-refreshed = last refreshed { $ago }
+summary-not-refreshed = not refreshed yet
+status-ok = Up to date
+status-error = Refresh failed: { $error }
+status-quota = API quota: { $used } / { $limit } units today
+daemon-connecting = Connecting to the daemon at { $url }…
+daemon-connected = Connected to the daemon at { $url }
+daemon-disconnected = Lost the daemon at { $url } ({ $error }), retrying in { $seconds }s
+
+## Header
+
+watchlist-label = Watchlist:
+theme-label = Choose a theme:
+theme-auto = Auto
+theme-dark = Dark
+theme-light = Light
+theme-high-contrast = High contrast
+edit-theme = Edit theme
+shortcuts-button = Shortcuts (?)
+language-label = Language:
+header-text-label = Header text:
+footer-text-label = Footer text:
+show-text = Show
+start-hidden = Start hidden in the tray
+
+## Layout and sorting
+
+cards-per-row-label = Cards per row:
+refresh-every-label = Refresh every (seconds):
+daemon-label = Daemon:
+daemon-placeholder = http://host:8080, empty to refresh here
+animations-toggle = Animations
+notify-upcoming-label = Notify before streams:
+notice-off = Off
+notice-minutes-before = { $minutes } min before
+view-label = View:
+view-grid = Grid
+view-compact-grid = Compact grid
+view-list = List
+format-label = Numbers and dates:
+format-abbreviated = Abbreviated
+format-full = Full
+sort-by-label = Sort by:
+sort-subscribers = Subscribers
+sort-is-live-status = Live status
+sort-name = Name
+sort-recent-growth = Recent growth
+sort-last-live = Last live
+sort-custom-order = Custom order
+order-label = Order:
+order-descending = Descending
+order-ascending = Ascending
+
+## Filters
+
+search-placeholder = Search by name or description...
+live-only = Live only
+group-by-tag = Group by tag
+clear-filters = Clear filters
+subscribers-any = Any subscriber count
+subscribers-under-1k = Under 1K
+subscribers-1k-10k = 1K - 10K
+subscribers-10k-100k = 10K - 100K
+subscribers-100k-1m = 100K - 1M
+subscribers-over-1m = Over 1M
+untagged = Untagged
+tag-section = { $tag } ({ $count })
+
+## Cards and list view
+
+card-name = Name: { $name }
+card-description = Description: { $description }
+card-subscribers = Subscriber Count: { $subscribers }
+card-is-live = Is Live?: { $is_live ->
+        [true] Yes
+       *[false] No
+    }
+live-badge = LIVE
+column-name = Name
+column-subscribers = Subscribers
+column-live = Live
+column-last-live = Last live
+column-channel = Channel
+column-change = Change
+column-viewers = Viewers
+column-tags = Tags
+never-live = never
+card-live-for = Live for { $duration }
+card-live-since = Live since { $date }
+card-last-live-ago = Last live { $ago }
+card-last-live-on = Last live { $date }
+card-starting-soon = Starting soon
+card-starts-in = Starts in { $duration }
+card-starts-in-days = Starts in { $count ->
+        [one] 1 day
+       *[other] { $count } days
+    }
+card-starts-on = Starts { $date }
+upcoming-section = Upcoming ({ $count })
+
+## Numbers and times
+
+count-thousands = { $count }K
+count-millions = { $count }M
+count-billions = { $count }B
+duration-minutes = { $minutes }m
+duration-hours-minutes = { $hours }h { $minutes }m
+time-just-now = just now
+time-seconds-ago = { $count } seconds ago
+time-minutes-ago = { $count ->
+        [one] 1 minute ago
+       *[other] { $count } minutes ago
+    }
+time-hours-ago = { $count ->
+        [one] 1 hour ago
+       *[other] { $count } hours ago
+    }
+time-days-ago = { $count ->
+        [one] 1 day ago
+       *[other] { $count } days ago
+    }
+time-months-ago = { $count ->
+        [one] 1 month ago
+       *[other] { $count } months ago
+    }
+time-years-ago = { $count ->
+        [one] 1 year ago
+       *[other] { $count } years ago
+    }
+
+## Theme editor
+
+theme-editor-name = Theme name:
+theme-editor-name-placeholder = Theme name
+theme-editor-font-size = Font size: { $size }
+theme-editor-border-width = Border width: { $width }
+theme-editor-save = Save
+theme-editor-cancel = Cancel
+theme-editor-new-theme = My theme
+color-background = Background
+color-text = Text
+color-primary = Primary
+color-success = Success
+color-danger = Danger
+color-card-background = Card background
+color-card-text = Card text
+color-card-border = Card border
+color-live-background = Live background
+color-live-text = Live text
+color-live-border = Live border
+
+## Import
+
+import-button = Import…
+import-path-label = Subscriptions file:
+import-path-placeholder = subscriptions.csv from Google Takeout, or an OPML file
+import-load = Load
+import-account = Import my subscriptions
+import-account-signing-in = Sign in to your Google account in the browser…
+import-account-fetching = Fetching your subscriptions…
+import-account-progress = Fetched { $fetched } of { $total } subscriptions…
+import-close = Close
+import-summary = { $new } new channels, { $known } already in the watchlist, room for { $free } more
+import-select-all = Select all
+import-add = Add to { $watchlist }
+import-added = Added { $count } creators to { $watchlist }
+import-failed = Couldn't import: { $error }
+
+## Export
+
+export-button = Export…
+export-format-label = Format:
+export-csv = CSV
+export-json = JSON
+export-markdown = Markdown table
+export-columns-label = Columns:
+export-path-label = File:
+export-save = Save
+export-copy = Copy to clipboard
+export-close = Close
+export-saved = Saved { $count } creators to { $path }
+export-copied = Copied { $count } creators
+export-failed = Couldn't save the export: { $error }
+
+## Keyboard shortcuts
+
+shortcuts-title = Keyboard shortcuts
+shortcut-move-focus = Move between creators
+shortcut-open = Open the channel of the selected creator
+shortcut-search = Search
+shortcut-next-theme = Next theme
+shortcut-next-sort = Next sort option
+shortcut-refresh = Refresh now
+shortcut-toggle-help = Show / hide the shortcuts
+shortcut-close-help = Close the shortcuts
+tui-next-view = Next view
+tui-flip-order = Flip the order
+tui-quit = Quit
+
+## Tray
+
+tray-title = { $live ->
+        [0] Nobody live
+       *[other] { $live } live
+    }
+tray-tooltip = { $live } of { $total } creators live
+tray-nobody-live = Nobody is live
+tray-show-window = Show window
+tray-quit = Quit
+
+## Notifications
+
+notification-upcoming = { $name } goes live soon
@@ -0,0 +1,216 @@
+# French
+
+app-title = YT Monitoring App (par KRS)
+header-welcome = Bienvenue ! Voici le statut de vos YouTubeurs préférés :
+footer-thanks = Merci d'être passé, cette application a été créée par Kushashwa Ravi Shrimali
+header-summary = { $live } sur { $total } en direct, { $refreshed }
+summary-refreshed = actualisé { $ago }
+summary-not-refreshed = pas encore actualisé
+status-ok = À jour
+status-error = Échec de l'actualisation : { $error }
+status-quota = Quota de l'API : { $used } / { $limit } unités aujourd'hui
+daemon-connecting = Connexion au démon { $url }…
+daemon-connected = Connecté au démon { $url }
+daemon-disconnected = Démon { $url } injoignable ({ $error }), nouvel essai dans { $seconds } s
+
+## Header
+
+watchlist-label = Liste :
+theme-label = Thème :
+theme-auto = Automatique
+theme-dark = Sombre
+theme-light = Clair
+theme-high-contrast = Contraste élevé
+edit-theme = Modifier le thème
+shortcuts-button = Raccourcis (?)
+language-label = Langue :
+header-text-label = Texte d'en-tête :
+footer-text-label = Texte de pied de page :
+show-text = Afficher
+start-hidden = Démarrer caché dans la zone de notification
+
+## Layout and sorting
+
+cards-per-row-label = Cartes par ligne :
+refresh-every-label = Actualiser toutes les (secondes) :
+daemon-label = Démon :
+daemon-placeholder = http://hôte:8080, vide pour actualiser ici
+animations-toggle = Animations
+notify-upcoming-label = Prévenir avant les directs :
+notice-off = Non
+notice-minutes-before = { $minutes } min avant
+view-label = Affichage :
+view-grid = Grille
+view-compact-grid = Grille compacte
+view-list = Liste
+format-label = Nombres et dates :
+format-abbreviated = Abrégés
+format-full = Complets
+sort-by-label = Trier par :
+sort-subscribers = Abonnés
+sort-is-live-status = En direct
+sort-name = Nom
+sort-recent-growth = Croissance récente
+sort-last-live = Dernier direct
+sort-custom-order = Ordre personnalisé
+order-label = Ordre :
+order-descending = Décroissant
+order-ascending = Croissant
+
+## Filters
+
+search-placeholder = Rechercher par nom ou description...
+live-only = En direct uniquement
+group-by-tag = Grouper par étiquette
+clear-filters = Effacer les filtres
+subscribers-any = Tous les nombres d'abonnés
+subscribers-under-1k = Moins de 1 k
+subscribers-1k-10k = 1 k - 10 k
+subscribers-10k-100k = 10 k - 100 k
+subscribers-100k-1m = 100 k - 1 M
+subscribers-over-1m = Plus de 1 M
+untagged = Sans étiquette
+tag-section = { $tag } ({ $count })
+
+## Cards and list view
+
+card-name = Nom : { $name }
+card-description = Description : { $description }
+card-subscribers = Abonnés : { $subscribers }
+card-is-live = En direct ? { $is_live ->
+        [true] Oui
+       *[false] Non
+    }
+live-badge = EN DIRECT
+column-name = Nom
+column-subscribers = Abonnés
+column-live = Direct
+column-last-live = Dernier direct
+column-channel = Chaîne
+column-change = Évolution
+column-viewers = Spectateurs
+column-tags = Tags
+never-live = jamais
+card-live-for = En direct depuis { $duration }
+card-live-since = En direct depuis le { $date }
+card-last-live-ago = Dernier direct { $ago }
+card-last-live-on = Dernier direct le { $date }
+card-starting-soon = Commence bientôt
+card-starts-in = Commence dans { $duration }
+card-starts-in-days = Commence dans { $count ->
+        [one] 1 jour
+       *[other] { $count } jours
+    }
+card-starts-on = Commence le { $date }
+upcoming-section = À venir ({ $count })
+
+## Numbers and times
+
+count-thousands = { $count } k
+count-millions = { $count } M
+count-billions = { $count } Md
+duration-minutes = { $minutes } min
+duration-hours-minutes = { $hours } h { $minutes } min
+time-just-now = à l'instant
+time-seconds-ago = il y a { $count } secondes
+time-minutes-ago = il y a { $count ->
+        [one] 1 minute
+       *[other] { $count } minutes
+    }
+time-hours-ago = il y a { $count ->
+        [one] 1 heure
+       *[other] { $count } heures
+    }
+time-days-ago = il y a { $count ->
+        [one] 1 jour
+       *[other] { $count } jours
+    }
+time-months-ago = il y a { $count } mois
+time-years-ago = il y a { $count ->
+        [one] 1 an
+       *[other] { $count } ans
+    }
+
+## Theme editor
+
+theme-editor-name = Nom du thème :
+theme-editor-name-placeholder = Nom du thème
+theme-editor-font-size = Taille du texte : { $size }
+theme-editor-border-width = Épaisseur de la bordure : { $width }
+theme-editor-save = Enregistrer
+theme-editor-cancel = Annuler
+theme-editor-new-theme = Mon thème
+color-background = Arrière-plan
+color-text = Texte
+color-primary = Principale
+color-success = Succès
+color-danger = Danger
+color-card-background = Fond des cartes
+color-card-text = Texte des cartes
+color-card-border = Bordure des cartes
+color-live-background = Fond en direct
+color-live-text = Texte en direct
+color-live-border = Bordure en direct
+
+## Import
+
+import-button = Importer…
+import-path-label = Fichier d'abonnements :
+import-path-placeholder = subscriptions.csv de Google Takeout, ou un fichier OPML
+import-load = Charger
+import-account = Importer mes abonnements
+import-account-signing-in = Connectez-vous à votre compte Google dans le navigateur…
+import-account-fetching = Récupération de vos abonnements…
+import-account-progress = { $fetched } abonnements récupérés sur { $total }…
+import-close = Fermer
+import-summary = { $new } nouvelles chaînes, { $known } déjà dans la liste, encore { $free } places
+import-select-all = Tout sélectionner
+import-add = Ajouter à { $watchlist }
+import-added = { $count } créateurs ajoutés à { $watchlist }
+import-failed = Impossible d'importer : { $error }
+
+## Export
+
+export-button = Exporter…
+export-format-label = Format :
+export-csv = CSV
+export-json = JSON
+export-markdown = Tableau Markdown
+export-columns-label = Colonnes :
+export-path-label = Fichier :
+export-save = Enregistrer
+export-copy = Copier dans le presse-papiers
+export-close = Fermer
+export-saved = { $count } créateurs enregistrés dans { $path }
+export-copied = { $count } créateurs copiés
+export-failed = Impossible d'enregistrer l'export : { $error }
+
+## Keyboard shortcuts
+
+shortcuts-title = Raccourcis clavier
+shortcut-move-focus = Se déplacer entre les créateurs
+shortcut-open = Ouvrir la chaîne du créateur sélectionné
+shortcut-search = Rechercher
+shortcut-next-theme = Thème suivant
+shortcut-next-sort = Tri suivant
+shortcut-refresh = Actualiser maintenant
+shortcut-toggle-help = Afficher / masquer les raccourcis
+shortcut-close-help = Fermer les raccourcis
+tui-next-view = Affichage suivant
+tui-flip-order = Inverser l'ordre
+tui-quit = Quitter
+
+## Tray
+
+tray-title = { $live ->
+        [0] Personne en direct
+       *[other] { $live } en direct
+    }
+tray-tooltip = { $live } créateurs sur { $total } en direct
+tray-nobody-live = Personne n'est en direct
+tray-show-window = Afficher la fenêtre
+tray-quit = Quitter
+
+## Notifications
+
+notification-upcoming = { $name } sera bientôt en direct
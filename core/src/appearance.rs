@@ -0,0 +1,312 @@
+use std::error::Error;
+use std::sync::OnceLock;
+
+use zbus::blocking::proxy::SignalIterator;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{OwnedValue, Value};
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const SETTINGS_INTERFACE: &str = "org.freedesktop.portal.Settings";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    #[default]
+    NoPreference,
+    PreferDark,
+    PreferLight,
+}
+
+impl ColorScheme {
+    // Values defined by the portal, anything unknown counts as no preference
+    pub fn from_portal_value(value: u32) -> ColorScheme {
+        match value {
+            1 => ColorScheme::PreferDark,
+            2 => ColorScheme::PreferLight,
+            _ => ColorScheme::NoPreference,
+        }
+    }
+
+    // Without a preference the app keeps its own default, which is dark
+    pub fn is_dark(&self) -> bool {
+        *self != ColorScheme::PreferLight
+    }
+}
+
+// Anything answering the portal's Settings interface: the session bus, or a mock in tests
+pub trait SettingsBus {
+    fn read_setting(&self, namespace: &str, key: &str) -> Result<u32, Box<dyn Error>>;
+}
+
+pub struct SessionBus {
+    connection: Connection,
+}
+
+impl SessionBus {
+    pub fn connect() -> Result<SessionBus, Box<dyn Error>> {
+        Ok(SessionBus {
+            connection: Connection::session()?,
+        })
+    }
+
+    fn call(&self, method: &str, namespace: &str, key: &str) -> Result<u32, Box<dyn Error>> {
+        let reply = self.connection.call_method(
+            Some(PORTAL_DESTINATION),
+            PORTAL_PATH,
+            Some(SETTINGS_INTERFACE),
+            method,
+            &(namespace, key),
+        )?;
+        let value: OwnedValue = reply.body().deserialize()?;
+        setting_value(&value)
+    }
+
+    // Subscribed to the color scheme only, the other settings don't concern us
+    pub fn color_scheme_changes(&self) -> Result<ColorSchemeChanges, Box<dyn Error>> {
+        let proxy = Proxy::new_owned(
+            self.connection.clone(),
+            PORTAL_DESTINATION,
+            PORTAL_PATH,
+            SETTINGS_INTERFACE,
+        )?;
+        let signals = proxy.receive_signal_with_args(
+            "SettingChanged",
+            &[(0, APPEARANCE_NAMESPACE), (1, COLOR_SCHEME_KEY)],
+        )?;
+        Ok(ColorSchemeChanges { signals })
+    }
+}
+
+// Read wraps the setting in one more variant than ReadOne and SettingChanged do
+fn setting_value(value: &Value) -> Result<u32, Box<dyn Error>> {
+    match value {
+        Value::Value(inner) => Ok(u32::try_from(&**inner)?),
+        value => Ok(u32::try_from(value)?),
+    }
+}
+
+// The color schemes the portal announces with its SettingChanged signal, each one blocking
+// until the desktop's preference changes. Ends with the connection.
+pub struct ColorSchemeChanges {
+    signals: SignalIterator<'static>,
+}
+
+impl Iterator for ColorSchemeChanges {
+    type Item = ColorScheme;
+
+    fn next(&mut self) -> Option<ColorScheme> {
+        self.signals.find_map(|signal| {
+            let (namespace, key, value): (String, String, OwnedValue) =
+                signal.body().deserialize().ok()?;
+            (namespace == APPEARANCE_NAMESPACE && key == COLOR_SCHEME_KEY).then(|| {
+                setting_value(&value)
+                    .map_or(ColorScheme::NoPreference, ColorScheme::from_portal_value)
+            })
+        })
+    }
+}
+
+impl SettingsBus for SessionBus {
+    fn read_setting(&self, namespace: &str, key: &str) -> Result<u32, Box<dyn Error>> {
+        // ReadOne only exists since version 2 of the portal, Read is deprecated but everywhere
+        self.call("ReadOne", namespace, key)
+            .or_else(|_| self.call("Read", namespace, key))
+    }
+}
+
+pub fn color_scheme_from(bus: &impl SettingsBus) -> ColorScheme {
+    match bus.read_setting(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY) {
+        Ok(value) => ColorScheme::from_portal_value(value),
+        Err(_) => ColorScheme::NoPreference,
+    }
+}
+
+// Connected once
+fn session_bus() -> Result<&'static SessionBus, Box<dyn Error>> {
+    static SESSION_BUS: OnceLock<Result<SessionBus, String>> = OnceLock::new();
+    let session_bus = SESSION_BUS.get_or_init(|| {
+        SessionBus::connect().map_err(|err| format!("couldn't connect to the session bus: {err}"))
+    });
+    match session_bus {
+        Ok(session_bus) => Ok(session_bus),
+        Err(err) => Err(err.clone().into()),
+    }
+}
+
+// No portal means no preference. Fails without a bus.
+pub fn system_color_scheme() -> Result<ColorScheme, Box<dyn Error>> {
+    Ok(color_scheme_from(session_bus()?))
+}
+
+pub fn system_color_scheme_changes() -> Result<ColorSchemeChanges, Box<dyn Error>> {
+    session_bus()?.color_scheme_changes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::os::unix::net::UnixStream;
+
+    // Answers from a map, and remembers what was asked
+    #[derive(Default)]
+    struct MockBus {
+        settings: HashMap<(String, String), u32>,
+        requests: RefCell<Vec<(String, String)>>,
+    }
+
+    impl MockBus {
+        fn with_color_scheme(value: u32) -> MockBus {
+            let mut mock_bus = MockBus::default();
+            mock_bus.settings.insert(
+                (
+                    APPEARANCE_NAMESPACE.to_string(),
+                    COLOR_SCHEME_KEY.to_string(),
+                ),
+                value,
+            );
+            mock_bus
+        }
+    }
+
+    impl SettingsBus for MockBus {
+        fn read_setting(&self, namespace: &str, key: &str) -> Result<u32, Box<dyn Error>> {
+            let request = (namespace.to_string(), key.to_string());
+            self.requests.borrow_mut().push(request.clone());
+            self.settings
+                .get(&request)
+                .copied()
+                .ok_or_else(|| "org.freedesktop.portal.Error.NotFound".into())
+        }
+    }
+
+    #[test]
+    fn test_reads_the_portal_color_scheme() {
+        let mock_bus = MockBus::with_color_scheme(2);
+        assert_eq!(color_scheme_from(&mock_bus), ColorScheme::PreferLight);
+        assert_eq!(
+            mock_bus.requests.borrow().as_slice(),
+            [(
+                "org.freedesktop.appearance".to_string(),
+                "color-scheme".to_string()
+            )]
+        );
+        assert_eq!(
+            color_scheme_from(&MockBus::with_color_scheme(1)),
+            ColorScheme::PreferDark
+        );
+    }
+
+    #[test]
+    fn test_missing_or_unknown_values_mean_no_preference() {
+        assert_eq!(
+            color_scheme_from(&MockBus::default()),
+            ColorScheme::NoPreference
+        );
+        assert_eq!(
+            color_scheme_from(&MockBus::with_color_scheme(0)),
+            ColorScheme::NoPreference
+        );
+        assert_eq!(
+            color_scheme_from(&MockBus::with_color_scheme(7)),
+            ColorScheme::NoPreference
+        );
+    }
+
+    // Stands in for the desktop portal
+    struct MockPortal {
+        color_scheme: u32,
+        // Before version 2 of the portal there's only Read
+        has_read_one: bool,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.portal.Settings")]
+    impl MockPortal {
+        fn read_one(&self, _namespace: &str, _key: &str) -> zbus::fdo::Result<OwnedValue> {
+            match self.has_read_one {
+                true => Ok(OwnedValue::from(self.color_scheme)),
+                false => Err(zbus::fdo::Error::UnknownMethod("ReadOne".to_string())),
+            }
+        }
+
+        fn read(&self, _namespace: &str, _key: &str) -> zbus::fdo::Result<OwnedValue> {
+            OwnedValue::try_from(Value::new(Value::from(self.color_scheme)))
+                .map_err(|err| zbus::fdo::Error::Failed(err.to_string()))
+        }
+    }
+
+    // The session bus talking to a mock portal over a private connection, no bus daemon
+    // needed. The portal's end of the connection is returned to emit signals with.
+    fn connect_to_portal(portal: MockPortal) -> (SessionBus, Connection) {
+        let (portal_stream, app_stream) = UnixStream::pair().unwrap();
+        let portal_connection = std::thread::spawn(move || {
+            zbus::blocking::connection::Builder::async_io_unix_stream(portal_stream)
+                .server(zbus::Guid::generate())
+                .unwrap()
+                .p2p()
+                .serve_at(PORTAL_PATH, portal)
+                .unwrap()
+                .build()
+                .unwrap()
+        });
+        let connection = zbus::blocking::connection::Builder::async_io_unix_stream(app_stream)
+            .p2p()
+            .build()
+            .unwrap();
+        (SessionBus { connection }, portal_connection.join().unwrap())
+    }
+
+    #[test]
+    fn test_reads_both_portal_versions() {
+        let (session_bus, _portal_connection) = connect_to_portal(MockPortal {
+            color_scheme: 1,
+            has_read_one: true,
+        });
+        assert_eq!(color_scheme_from(&session_bus), ColorScheme::PreferDark);
+        // Read's value comes in one more variant
+        let (session_bus, _portal_connection) = connect_to_portal(MockPortal {
+            color_scheme: 2,
+            has_read_one: false,
+        });
+        assert_eq!(color_scheme_from(&session_bus), ColorScheme::PreferLight);
+    }
+
+    #[test]
+    fn test_color_scheme_changes() {
+        let (session_bus, portal_connection) = connect_to_portal(MockPortal {
+            color_scheme: 0,
+            has_read_one: true,
+        });
+        let mut changes = session_bus.color_scheme_changes().unwrap();
+        let setting_changed = |namespace: &str, key: &str, value: Value| {
+            portal_connection
+                .emit_signal(
+                    None::<()>,
+                    PORTAL_PATH,
+                    SETTINGS_INTERFACE,
+                    "SettingChanged",
+                    &(namespace, key, value),
+                )
+                .unwrap();
+        };
+        setting_changed(
+            "org.gnome.desktop.interface",
+            "font-name",
+            Value::from("Sans"),
+        );
+        setting_changed(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY, Value::from(2u32));
+        setting_changed(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY, Value::from(1u32));
+        assert_eq!(changes.next(), Some(ColorScheme::PreferLight));
+        assert_eq!(changes.next(), Some(ColorScheme::PreferDark));
+    }
+
+    #[test]
+    fn test_only_prefer_light_is_light() {
+        assert!(ColorScheme::NoPreference.is_dark());
+        assert!(ColorScheme::PreferDark.is_dark());
+        assert!(!ColorScheme::PreferLight.is_dark());
+    }
+}
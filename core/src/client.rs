@@ -0,0 +1,427 @@
+use std::error::Error;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+
+use super::i18n;
+use super::records::{CreatorRecord, MonitorStatus};
+
+// The daemon sends a keep-alive comment every 15 seconds, nothing for three of them means
+// the connection is gone
+const EVENTS_READ_TIMEOUT: Duration = Duration::from_secs(45);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+// The HTTP API of a daemon started with `yt-monitor serve`
+#[derive(Debug, Clone)]
+pub struct DaemonClient {
+    base_url: reqwest::Url,
+    client: reqwest::blocking::Client,
+}
+
+impl DaemonClient {
+    pub fn new(url: &str) -> Result<DaemonClient, Box<dyn Error>> {
+        let mut base_url = reqwest::Url::parse(url.trim())?;
+        if base_url.scheme() != "http" {
+            return Err(format!("{url}: the daemon only speaks http").into());
+        }
+        // So that joining "creators" keeps a path prefix, e.g. of a reverse proxy
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        Ok(DaemonClient {
+            base_url,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    pub fn creators(&self) -> Result<Vec<CreatorRecord>, Box<dyn Error>> {
+        self.request(reqwest::Method::GET, "creators")
+    }
+
+    pub fn status(&self) -> Result<MonitorStatus, Box<dyn Error>> {
+        self.request(reqwest::Method::GET, "status")
+    }
+
+    // The daemon tells the event streams about the refresh too
+    pub fn refresh(&self) -> Result<MonitorStatus, Box<dyn Error>> {
+        self.request(reqwest::Method::POST, "refresh")
+    }
+
+    fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Result<T, Box<dyn Error>> {
+        let response = self
+            .client
+            .request(method, self.base_url.join(path)?)
+            .send()?;
+        let status = response.status();
+        let body = response.text()?;
+        if !status.is_success() {
+            return Err(format!("{path}: {status}: {body}").into());
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    // reqwest's blocking client only has a timeout for the whole response, which an event
+    // stream never finishes, so this is plain HTTP/1.1 over a socket with a read timeout
+    pub fn events(&self) -> Result<EventStream<BufReader<TcpStream>>, Box<dyn Error>> {
+        let url = self.base_url.join("events")?;
+        let host = url.host_str().ok_or("the daemon URL has no host")?;
+        let port = url.port_or_known_default().unwrap_or(80);
+        let mut stream = TcpStream::connect((host, port))?;
+        stream.set_read_timeout(Some(EVENTS_READ_TIMEOUT))?;
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: {host}:{port}\r\nAccept: text/event-stream\r\n\r\n",
+            url.path()
+        );
+        stream.write_all(request.as_bytes())?;
+
+        let mut reader = BufReader::new(stream);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line)?;
+        if status_line.split_whitespace().nth(1) != Some("200") {
+            return Err(format!("events: {}", status_line.trim()).into());
+        }
+        // The headers, up to the blank line
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 {
+                return Err("the daemon closed the connection".into());
+            }
+            if header.trim().is_empty() {
+                break;
+            }
+        }
+        Ok(EventStream::new(reader))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerEvent {
+    pub event: String,
+    pub data: String,
+}
+
+// The messages of a text/event-stream, see
+// https://html.spec.whatwg.org/multipage/server-sent-events.html
+#[derive(Debug)]
+pub struct EventStream<R: BufRead> {
+    reader: R,
+}
+
+impl<R: BufRead> EventStream<R> {
+    pub fn new(reader: R) -> EventStream<R> {
+        EventStream { reader }
+    }
+
+    // Blocks until the next message, an error once the stream ends or times out
+    pub fn next_event(&mut self) -> Result<ServerEvent, Box<dyn Error>> {
+        let mut event = String::new();
+        let mut data: Vec<String> = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err("the daemon closed the connection".into());
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                // Keep-alive comments alone don't make a message
+                if event.is_empty() && data.is_empty() {
+                    continue;
+                }
+                return Ok(ServerEvent {
+                    event: match event.is_empty() {
+                        true => "message".to_string(),
+                        false => event,
+                    },
+                    data: data.join("\n"),
+                });
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => event = value.to_string(),
+                "data" => data.push(value.to_string()),
+                // id and retry don't matter here
+                _ => (),
+            }
+        }
+    }
+}
+
+// How long to wait before the attempt-th try to connect: right away, then 1s, 2s, 4s, ...
+// up to a minute
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    match attempt {
+        0 => Duration::ZERO,
+        _ => Duration::from_secs(1)
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(MAX_RECONNECT_DELAY),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DaemonUpdate {
+    Connected,
+    Creators(Vec<CreatorRecord>, MonitorStatus),
+    Disconnected { error: String, retry_in: Duration },
+}
+
+// Where next_update is with the daemon
+#[derive(Debug)]
+pub enum Connection {
+    Connecting { attempt: u32 },
+    Connected(EventStream<BufReader<TcpStream>>),
+}
+
+impl Default for Connection {
+    fn default() -> Self {
+        Connection::Connecting { attempt: 0 }
+    }
+}
+
+// Blocks until something happens with the daemon at daemon_url: the connection is made or
+// lost, or the daemon refreshed its creators. Meant to be called in a loop, reconnecting
+// with growing delays.
+pub fn next_update(daemon_url: &str, connection: Connection) -> (DaemonUpdate, Connection) {
+    let disconnected = |err: Box<dyn Error>, attempt: u32| {
+        (
+            DaemonUpdate::Disconnected {
+                error: err.to_string(),
+                retry_in: reconnect_delay(attempt),
+            },
+            Connection::Connecting { attempt },
+        )
+    };
+    let client = match DaemonClient::new(daemon_url) {
+        Ok(client) => client,
+        // Retrying doesn't fix the URL but keeps the status on screen
+        Err(err) => {
+            let attempt = match connection {
+                Connection::Connecting { attempt } => attempt + 1,
+                Connection::Connected(_) => 1,
+            };
+            thread::sleep(reconnect_delay(attempt - 1));
+            return disconnected(err, attempt);
+        }
+    };
+
+    match connection {
+        Connection::Connecting { attempt } => {
+            thread::sleep(reconnect_delay(attempt));
+            match client.events() {
+                Ok(events) => (DaemonUpdate::Connected, Connection::Connected(events)),
+                Err(err) => disconnected(err, attempt + 1),
+            }
+        }
+        Connection::Connected(mut events) => {
+            // The daemon sends the current status right after connecting, then after
+            // every refresh
+            let update = loop {
+                match events.next_event() {
+                    Ok(event) if event.event == "refreshed" => {
+                        break serde_json::from_str::<MonitorStatus>(&event.data)
+                            .map_err(Box::<dyn Error>::from)
+                            .and_then(|status| Ok((client.creators()?, status)));
+                    }
+                    // Live changes are followed by a refreshed event
+                    Ok(_) => (),
+                    Err(err) => break Err(err),
+                }
+            };
+            match update {
+                Ok((records, status)) => (
+                    DaemonUpdate::Creators(records, status),
+                    Connection::Connected(events),
+                ),
+                Err(err) => disconnected(err, 1),
+            }
+        }
+    }
+}
+
+// The daemon connection as the footer shows it
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    #[default]
+    Connecting,
+    Connected,
+    Disconnected {
+        error: String,
+        // Unix timestamp of the next try
+        retry_at: u64,
+    },
+}
+
+impl ConnectionStatus {
+    pub fn apply(&mut self, update: &DaemonUpdate, now: u64) {
+        *self = match update {
+            DaemonUpdate::Connected | DaemonUpdate::Creators(..) => ConnectionStatus::Connected,
+            DaemonUpdate::Disconnected { error, retry_in } => ConnectionStatus::Disconnected {
+                error: error.clone(),
+                retry_at: now + retry_in.as_secs(),
+            },
+        };
+    }
+
+    // "Connected to http://monitor.local:8080", "Lost the daemon at ...: ..., retrying in 8s"
+    pub fn describe(&self, url: &str, now: u64) -> String {
+        match self {
+            ConnectionStatus::Connecting => {
+                i18n::tr_args("daemon-connecting", &[("url", url.into())])
+            }
+            ConnectionStatus::Connected => {
+                i18n::tr_args("daemon-connected", &[("url", url.into())])
+            }
+            ConnectionStatus::Disconnected { error, retry_at } => i18n::tr_args(
+                "daemon-disconnected",
+                &[
+                    ("url", url.into()),
+                    ("error", error.as_str().into()),
+                    ("seconds", retry_at.saturating_sub(now).into()),
+                ],
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_event_stream() {
+        let mut events = EventStream::new(Cursor::new(
+            ": keep-alive\n\n\
+             event: live\ndata: {\"id\": \"UC123\"}\n\n\
+             data: first\r\ndata:second\r\n\r\n",
+        ));
+        assert_eq!(
+            events.next_event().unwrap(),
+            ServerEvent {
+                event: "live".to_string(),
+                data: "{\"id\": \"UC123\"}".to_string(),
+            }
+        );
+        assert_eq!(
+            events.next_event().unwrap(),
+            ServerEvent {
+                event: "message".to_string(),
+                data: "first\nsecond".to_string(),
+            }
+        );
+        assert!(events.next_event().is_err());
+    }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let delays: Vec<u64> = (0..9).map(|n| reconnect_delay(n).as_secs()).collect();
+        assert_eq!(delays, [0, 1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(reconnect_delay(u32::MAX), MAX_RECONNECT_DELAY);
+    }
+
+    #[test]
+    fn test_daemon_client_rejects_other_schemes() {
+        assert!(DaemonClient::new("https://monitor.local").is_err());
+        assert!(DaemonClient::new("monitor.local:8080").is_err());
+        assert!(DaemonClient::new("http://monitor.local:8080").is_ok());
+    }
+
+    // Answers one event stream with a refresh and the creators, then goes away
+    fn mock_daemon() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                // Up to the end of the headers, neither client sends a body
+                let mut request = String::new();
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    request += &line;
+                }
+                let response = if request.starts_with("GET /events ") {
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\r\n\
+                     : keep-alive\n\n\
+                     event: refreshed\n\
+                     data: {\"watchlist\":\"Friends\",\"total\":1,\"live\":1,\
+                     \"last_refreshed\":1700000000,\"last_error\":null,\
+                     \"quota_used_today\":null}\n\n"
+                        .to_string()
+                } else {
+                    let body = r#"[{"id":"UC123","name":"Kush","channel_id":"UC123",
+                        "channel_url":"","avatar_link":"","description":"",
+                        "subscribers":1200,"previous_subscribers":null,"is_live":true,
+                        "viewers":345,"last_live":null,"custom_order":null,"tags":[]}]"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_next_update() {
+        let url = mock_daemon();
+        let (update, connection) = next_update(&url, Connection::default());
+        assert!(matches!(update, DaemonUpdate::Connected), "{update:?}");
+
+        let (update, connection) = next_update(&url, connection);
+        let DaemonUpdate::Creators(records, status) = update else {
+            panic!("expected the creators, got {update:?}");
+        };
+        assert_eq!(records[0].name, "Kush");
+        assert_eq!(records[0].viewers, Some(345));
+        assert_eq!(status.watchlist, "Friends");
+        assert_eq!(status.last_refreshed, Some(1700000000));
+
+        // The mock daemon closed the event stream
+        let (update, connection) = next_update(&url, connection);
+        let DaemonUpdate::Disconnected { retry_in, .. } = update else {
+            panic!("expected a disconnection, got {update:?}");
+        };
+        assert_eq!(retry_in, Duration::from_secs(1));
+        assert!(matches!(connection, Connection::Connecting { attempt: 1 }));
+    }
+
+    #[test]
+    fn test_connection_status() {
+        let mut status = ConnectionStatus::default();
+        let url = "http://monitor.local:8080";
+        assert_eq!(
+            status.describe(url, 0),
+            "Connecting to the daemon at http://monitor.local:8080…"
+        );
+        status.apply(&DaemonUpdate::Connected, 0);
+        assert_eq!(
+            status.describe(url, 0),
+            "Connected to the daemon at http://monitor.local:8080"
+        );
+        status.apply(
+            &DaemonUpdate::Disconnected {
+                error: "connection refused".to_string(),
+                retry_in: Duration::from_secs(8),
+            },
+            100,
+        );
+        assert_eq!(
+            status.describe(url, 102),
+            "Lost the daemon at http://monitor.local:8080 (connection refused), retrying in 6s"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::export::{ExportColumn, ExportFormat};
+use super::i18n::{self, Language};
+use super::model::{DisplayFormat, ThemeType, ViewMode};
+use super::watchlists::WatchlistSortOption;
+
+const CONFIG_DIR_NAME: &str = "yt-monitor";
+// Overrides the config directory, handy for tests and for running several instances
+const CONFIG_DIR_ENV_VAR: &str = "YT_MONITOR_CONFIG_DIR";
+const SETTINGS_FILE_NAME: &str = "settings.json";
+// Where a settings file which can't be parsed is moved, instead of being overwritten
+const INVALID_SETTINGS_FILE_NAME: &str = "settings.invalid.json";
+
+pub const CARDS_PER_ROW_OPTIONS: [usize; 5] = [2, 3, 4, 5, 6];
+pub const POLLING_INTERVAL_OPTIONS: [u64; 5] = [15, 30, 60, 300, 900];
+
+// Where all the user files (watchlists, settings, ...) live, e.g. ~/.config/yt-monitor on
+// Linux. Falls back to the working directory if the platform doesn't have a config directory.
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV_VAR) {
+        return PathBuf::from(dir);
+    }
+    dirs::config_dir()
+        .map(|dir| dir.join(CONFIG_DIR_NAME))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+// Text shown above the summary in the header (or below the refresh status in the footer),
+// the built-in one if text is None
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaticText {
+    pub text: Option<String>,
+    pub shown: bool,
+}
+
+impl Default for StaticText {
+    fn default() -> Self {
+        StaticText {
+            text: None,
+            shown: true,
+        }
+    }
+}
+
+// How long before a scheduled stream the desktop notification comes, if at all
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpcomingNotice {
+    #[default]
+    Off,
+    MinutesBefore(u64),
+}
+
+impl UpcomingNotice {
+    pub const ALL: [UpcomingNotice; 5] = [
+        UpcomingNotice::Off,
+        UpcomingNotice::MinutesBefore(5),
+        UpcomingNotice::MinutesBefore(15),
+        UpcomingNotice::MinutesBefore(30),
+        UpcomingNotice::MinutesBefore(60),
+    ];
+
+    pub fn lead_secs(&self) -> Option<u64> {
+        match self {
+            UpcomingNotice::Off => None,
+            UpcomingNotice::MinutesBefore(minutes) => Some(minutes * 60),
+        }
+    }
+}
+
+impl fmt::Display for UpcomingNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpcomingNotice::Off => write!(f, "{}", i18n::tr("notice-off")),
+            UpcomingNotice::MinutesBefore(minutes) => write!(
+                f,
+                "{}",
+                i18n::tr_args("notice-minutes-before", &[("minutes", (*minutes).into())])
+            ),
+        }
+    }
+}
+
+// User preferences kept between sessions. Missing keys (older settings files) take the
+// default value, so new preferences can be added freely.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    pub theme: ThemeType,
+    // Used for watchlists which don't remember a sort option of their own yet
+    pub default_sort: WatchlistSortOption,
+    pub window_size: (u32, u32),
+    // None lets the window manager decide
+    pub window_position: Option<(i32, i32)>,
+    pub cards_per_row: usize,
+    pub polling_interval_secs: u64,
+    pub animations_enabled: bool,
+    pub view_mode: ViewMode,
+    pub display_format: DisplayFormat,
+    pub language: Language,
+    pub header_text: StaticText,
+    pub footer_text: StaticText,
+    // Only the tray icon shows up at startup, if there is a tray
+    pub start_hidden: bool,
+    // e.g. http://monitor.local:8080, the window then shows the creators of that daemon
+    // instead of refreshing a watchlist itself
+    pub daemon_url: Option<String>,
+    // What the last export was written with
+    pub export_format: ExportFormat,
+    pub export_columns: Vec<ExportColumn>,
+    pub upcoming_notice: UpcomingNotice,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        UserSettings {
+            // Because dark as default is cool :D
+            theme: ThemeType::Dark,
+            default_sort: WatchlistSortOption::default(),
+            window_size: (1600, 800),
+            window_position: None,
+            cards_per_row: 4,
+            polling_interval_secs: 60,
+            animations_enabled: true,
+            view_mode: ViewMode::Grid,
+            display_format: DisplayFormat::Abbreviated,
+            language: Language::English,
+            header_text: StaticText::default(),
+            footer_text: StaticText::default(),
+            start_hidden: false,
+            daemon_url: None,
+            export_format: ExportFormat::default(),
+            export_columns: ExportColumn::DEFAULT.to_vec(),
+            upcoming_notice: UpcomingNotice::Off,
+        }
+    }
+}
+
+impl UserSettings {
+    // A missing settings file gives the default settings in the desktop's language (until
+    // one is picked). One which can't be read or parsed gives the default settings too, along
+    // with the error for the binaries to print.
+    pub fn load(config_dir: &Path) -> (UserSettings, Option<String>) {
+        let path = config_dir.join(SETTINGS_FILE_NAME);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let user_settings = UserSettings {
+                    language: Language::from_system(),
+                    ..UserSettings::default()
+                };
+                return (user_settings, None);
+            }
+            Err(err) => {
+                let error = format!("Couldn't read the settings: {err}");
+                return (UserSettings::default(), Some(error));
+            }
+        };
+        match serde_json::from_str::<UserSettings>(&contents) {
+            Ok(user_settings) => (user_settings.clamped(), None),
+            // Moved aside, the next save would overwrite it otherwise
+            Err(err) => {
+                let invalid_path = config_dir.join(INVALID_SETTINGS_FILE_NAME);
+                let error = match fs::rename(&path, &invalid_path) {
+                    Ok(()) => format!(
+                        "Couldn't parse the settings, they were moved to {}: {err}",
+                        invalid_path.display()
+                    ),
+                    Err(rename_err) => format!(
+                        "Couldn't parse the settings: {err}, nor move them aside: {rename_err}"
+                    ),
+                };
+                (UserSettings::default(), Some(error))
+            }
+        }
+    }
+
+    // Values the window doesn't offer are brought within its options, some of them can't be
+    // used at all (e.g. 0 cards per row, polling without a pause)
+    fn clamped(self) -> UserSettings {
+        UserSettings {
+            cards_per_row: clamp_to_options(self.cards_per_row, &CARDS_PER_ROW_OPTIONS),
+            polling_interval_secs: clamp_to_options(
+                self.polling_interval_secs,
+                &POLLING_INTERVAL_OPTIONS,
+            ),
+            ..self
+        }
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(config_dir)?;
+        fs::write(
+            config_dir.join(SETTINGS_FILE_NAME),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+}
+
+// The options are sorted
+fn clamp_to_options<T: Ord + Copy>(value: T, options: &[T]) -> T {
+    value.clamp(options[0], options[options.len() - 1])
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::model::{AllowedFieldNamesForSorting, SortDirection};
+    use super::super::testing::temp_config_dir;
+    use super::*;
+
+    #[test]
+    fn test_user_settings_round_trip() {
+        let config_dir = temp_config_dir("settings");
+        assert_eq!(
+            UserSettings::load(&config_dir),
+            (
+                UserSettings {
+                    language: Language::from_system(),
+                    ..UserSettings::default()
+                },
+                None
+            )
+        );
+        assert_eq!(UserSettings::default().language, Language::English);
+
+        let user_settings = UserSettings {
+            theme: ThemeType::Light,
+            default_sort: WatchlistSortOption {
+                sort_option: AllowedFieldNamesForSorting::LastLive,
+                sort_direction: SortDirection::Ascending,
+            },
+            window_size: (1024, 768),
+            window_position: Some((10, 20)),
+            cards_per_row: 3,
+            polling_interval_secs: 300,
+            animations_enabled: false,
+            view_mode: ViewMode::List,
+            display_format: DisplayFormat::Full,
+            language: Language::French,
+            header_text: StaticText {
+                text: Some("Our streamers".to_string()),
+                shown: true,
+            },
+            footer_text: StaticText {
+                text: None,
+                shown: false,
+            },
+            start_hidden: true,
+            daemon_url: Some("http://monitor.local:8080".to_string()),
+            export_format: ExportFormat::Markdown,
+            export_columns: vec![ExportColumn::Name, ExportColumn::Viewers],
+            upcoming_notice: UpcomingNotice::MinutesBefore(15),
+        };
+        user_settings.save(&config_dir).unwrap();
+        assert_eq!(UserSettings::load(&config_dir), (user_settings, None));
+    }
+
+    #[test]
+    fn test_user_settings_out_of_range() {
+        let config_dir = temp_config_dir("settings-range");
+        fs::write(
+            config_dir.join(SETTINGS_FILE_NAME),
+            r#"{"cards_per_row": 0, "polling_interval_secs": 0}"#,
+        )
+        .unwrap();
+        let (user_settings, error) = UserSettings::load(&config_dir);
+        assert_eq!(user_settings.cards_per_row, 2);
+        assert_eq!(user_settings.polling_interval_secs, 15);
+        assert_eq!(error, None);
+
+        fs::write(
+            config_dir.join(SETTINGS_FILE_NAME),
+            r#"{"cards_per_row": 40, "polling_interval_secs": 86400}"#,
+        )
+        .unwrap();
+        let (user_settings, _) = UserSettings::load(&config_dir);
+        assert_eq!(user_settings.cards_per_row, 6);
+        assert_eq!(user_settings.polling_interval_secs, 900);
+    }
+
+    #[test]
+    fn test_user_settings_invalid_file_is_kept() {
+        let config_dir = temp_config_dir("settings-invalid");
+        let contents = r#"{"theme": "Light", "cards_per_row": "#;
+        fs::write(config_dir.join(SETTINGS_FILE_NAME), contents).unwrap();
+        let (user_settings, error) = UserSettings::load(&config_dir);
+        assert_eq!(user_settings, UserSettings::default());
+        assert!(error.unwrap().starts_with("Couldn't parse the settings"));
+        // Saving doesn't lose it
+        user_settings.save(&config_dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(config_dir.join(INVALID_SETTINGS_FILE_NAME)).unwrap(),
+            contents
+        );
+    }
+
+    #[test]
+    fn test_user_settings_missing_keys_take_defaults() {
+        let user_settings: UserSettings =
+            serde_json::from_str(r#"{"theme": "Light", "cards_per_row": 5}"#).unwrap();
+        assert_eq!(
+            user_settings,
+            UserSettings {
+                theme: ThemeType::Light,
+                cards_per_row: 5,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::PathBuf;
+
+use super::i18n;
+use super::records::CreatorRecord;
+use super::views;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Csv,
+        ExportFormat::Json,
+        ExportFormat::Markdown,
+    ];
+
+    // Name on the command line, which doesn't change with the language
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "markdown",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ExportFormat> {
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.key() == key)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message_id = match self {
+            ExportFormat::Csv => "export-csv",
+            ExportFormat::Json => "export-json",
+            ExportFormat::Markdown => "export-markdown",
+        };
+        write!(f, "{}", i18n::tr(message_id))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportColumn {
+    Name,
+    ChannelUrl,
+    Subscribers,
+    // Since the previous subscriber count
+    SubscriberChange,
+    Live,
+    Viewers,
+    LastLive,
+    Tags,
+}
+
+impl ExportColumn {
+    pub const ALL: [ExportColumn; 8] = [
+        ExportColumn::Name,
+        ExportColumn::ChannelUrl,
+        ExportColumn::Subscribers,
+        ExportColumn::SubscriberChange,
+        ExportColumn::Live,
+        ExportColumn::Viewers,
+        ExportColumn::LastLive,
+        ExportColumn::Tags,
+    ];
+
+    // What a status report needs
+    pub const DEFAULT: [ExportColumn; 5] = [
+        ExportColumn::Name,
+        ExportColumn::Subscribers,
+        ExportColumn::SubscriberChange,
+        ExportColumn::Live,
+        ExportColumn::LastLive,
+    ];
+
+    // Name on the command line
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExportColumn::Name => "name",
+            ExportColumn::ChannelUrl => "channel-url",
+            ExportColumn::Subscribers => "subscribers",
+            ExportColumn::SubscriberChange => "change",
+            ExportColumn::Live => "live",
+            ExportColumn::Viewers => "viewers",
+            ExportColumn::LastLive => "last-live",
+            ExportColumn::Tags => "tags",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ExportColumn> {
+        ExportColumn::ALL
+            .into_iter()
+            .find(|column| column.key() == key)
+    }
+
+    // Of the CSV header and the JSON objects, the names of the daemon's API where it has them
+    fn field_name(&self) -> &'static str {
+        match self {
+            ExportColumn::Name => "name",
+            ExportColumn::ChannelUrl => "channel_url",
+            ExportColumn::Subscribers => "subscribers",
+            ExportColumn::SubscriberChange => "subscriber_change",
+            ExportColumn::Live => "is_live",
+            ExportColumn::Viewers => "viewers",
+            ExportColumn::LastLive => "last_live",
+            ExportColumn::Tags => "tags",
+        }
+    }
+
+    // Of the Markdown header and the checkboxes of the window
+    pub fn title(&self) -> String {
+        let message_id = match self {
+            ExportColumn::Name => "column-name",
+            ExportColumn::ChannelUrl => "column-channel",
+            ExportColumn::Subscribers => "column-subscribers",
+            ExportColumn::SubscriberChange => "column-change",
+            ExportColumn::Live => "column-live",
+            ExportColumn::Viewers => "column-viewers",
+            ExportColumn::LastLive => "column-last-live",
+            ExportColumn::Tags => "column-tags",
+        };
+        i18n::tr(message_id)
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(
+            self,
+            ExportColumn::Subscribers | ExportColumn::SubscriberChange | ExportColumn::Viewers
+        )
+    }
+
+    fn value(&self, record: &CreatorRecord) -> Value {
+        match self {
+            ExportColumn::Name => record.name.clone().into(),
+            ExportColumn::ChannelUrl => record.channel_url.clone().into(),
+            ExportColumn::Subscribers => record.subscribers.into(),
+            ExportColumn::SubscriberChange => subscriber_change(record).into(),
+            ExportColumn::Live => record.is_live.into(),
+            ExportColumn::Viewers => record.viewers.into(),
+            ExportColumn::LastLive => record.last_live.into(),
+            ExportColumn::Tags => record.tags.clone().into(),
+        }
+    }
+
+    // Plain values for CSV, the way the window shows them for Markdown
+    fn cell(&self, record: &CreatorRecord, format: ExportFormat) -> String {
+        let markdown = format == ExportFormat::Markdown;
+        let count = |count: Option<u64>| match (count, markdown) {
+            (Some(count), true) => i18n::format_count(count as i64),
+            (Some(count), false) => count.to_string(),
+            (None, _) => String::new(),
+        };
+        match self {
+            ExportColumn::Name => record.name.clone(),
+            ExportColumn::ChannelUrl => record.channel_url.clone(),
+            ExportColumn::Subscribers => count(record.subscribers),
+            ExportColumn::SubscriberChange => match subscriber_change(record) {
+                Some(change) if markdown && change > 0 => {
+                    format!("+{}", i18n::format_count(change))
+                }
+                Some(change) if markdown => i18n::format_count(change),
+                Some(change) => format!("{change:+}"),
+                None => String::new(),
+            },
+            ExportColumn::Live if markdown => match record.is_live {
+                true => i18n::tr("live-badge"),
+                false => String::new(),
+            },
+            ExportColumn::Live => record.is_live.to_string(),
+            ExportColumn::Viewers => count(record.viewers),
+            ExportColumn::LastLive => match (record.last_live, markdown) {
+                (Some(last_live), _) => views::format_timestamp(&last_live.to_string()),
+                (None, true) => i18n::tr("never-live"),
+                (None, false) => String::new(),
+            },
+            ExportColumn::Tags => record.tags.join(", "),
+        }
+    }
+}
+
+// None unless both counts are known
+fn subscriber_change(record: &CreatorRecord) -> Option<i64> {
+    Some(record.subscribers? as i64 - record.previous_subscribers? as i64)
+}
+
+// The columns of records, in the given order, in format
+pub fn export(records: &[CreatorRecord], columns: &[ExportColumn], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => export_csv(records, columns),
+        ExportFormat::Json => export_json(records, columns),
+        ExportFormat::Markdown => export_markdown(records, columns),
+    }
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn export_csv(records: &[CreatorRecord], columns: &[ExportColumn]) -> String {
+    let line = |cells: Vec<String>| cells.join(",") + "\n";
+    let header = line(
+        columns
+            .iter()
+            .map(|column| column.field_name().to_string())
+            .collect(),
+    );
+    let rows = records.iter().map(|record| {
+        line(
+            columns
+                .iter()
+                .map(|column| csv_field(&column.cell(record, ExportFormat::Csv)))
+                .collect(),
+        )
+    });
+    std::iter::once(header).chain(rows).collect()
+}
+
+fn export_json(records: &[CreatorRecord], columns: &[ExportColumn]) -> String {
+    let objects: Vec<Value> = records
+        .iter()
+        .map(|record| {
+            let object: Map<String, Value> = columns
+                .iter()
+                .map(|column| (column.field_name().to_string(), column.value(record)))
+                .collect();
+            Value::Object(object)
+        })
+        .collect();
+    serde_json::to_string_pretty(&objects).unwrap_or_default() + "\n"
+}
+
+// A GitHub flavored Markdown table, numbers right-aligned
+fn export_markdown(records: &[CreatorRecord], columns: &[ExportColumn]) -> String {
+    // A pipe would end the cell, a line break the table
+    let escape = |cell: String| cell.replace('|', "\\|").replace(['\n', '\r'], " ");
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let header = line(
+        columns
+            .iter()
+            .map(|column| escape(column.title()))
+            .collect(),
+    );
+    let separator = line(
+        columns
+            .iter()
+            .map(|column| match column.is_number() {
+                true => "---:".to_string(),
+                false => "---".to_string(),
+            })
+            .collect(),
+    );
+    let rows = records.iter().map(|record| {
+        line(
+            columns
+                .iter()
+                .map(|column| escape(column.cell(record, ExportFormat::Markdown)))
+                .collect(),
+        )
+    });
+    [header, separator].into_iter().chain(rows).collect()
+}
+
+// e.g. ~/Documents/friends-2024-02-29.md
+pub fn default_path(watchlist_name: &str, format: ExportFormat, now: u64) -> PathBuf {
+    let file_stem: String = watchlist_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    // "YYYY-MM-DD HH:MM UTC"
+    let date: String = views::format_timestamp(&now.to_string())
+        .chars()
+        .take(10)
+        .collect();
+    dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(format!("{file_stem}-{date}.{}", format.extension()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::records::creator_records;
+    use super::*;
+
+    fn records() -> Vec<CreatorRecord> {
+        creator_records(
+            &serde_json::from_str(
+                r#"{
+                    "names": ["Kush", "Doe, Jane | JD"],
+                    "subscribers": ["1234567", "980"],
+                    "previous_subscribers": ["1234000", "1000"],
+                    "is_live_status": ["false", "true"],
+                    "last_live": ["1709210040", ""],
+                    "viewers": ["", "345"]
+                }"#,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_export_csv() {
+        assert_eq!(
+            export(&records(), &ExportColumn::DEFAULT, ExportFormat::Csv),
+            "name,subscribers,subscriber_change,is_live,last_live\n\
+             Kush,1234567,+567,false,2024-02-29 12:34 UTC\n\
+             \"Doe, Jane | JD\",980,-20,true,\n"
+        );
+    }
+
+    #[test]
+    fn test_export_json() {
+        let columns = [
+            ExportColumn::Name,
+            ExportColumn::Viewers,
+            ExportColumn::Tags,
+        ];
+        let json: Value =
+            serde_json::from_str(&export(&records(), &columns, ExportFormat::Json)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"name": "Kush", "viewers": null, "tags": []},
+                {"name": "Doe, Jane | JD", "viewers": 345, "tags": []}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_export_markdown() {
+        assert_eq!(
+            export(&records(), &ExportColumn::DEFAULT, ExportFormat::Markdown),
+            "\
+| Name | Subscribers | Change | Live | Last live |
+| --- | ---: | ---: | --- | --- |
+| Kush | 1,234,567 | +567 |  | 2024-02-29 12:34 UTC |
+| Doe, Jane \\| JD | 980 | -20 | LIVE | never |
+"
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        for column in ExportColumn::ALL {
+            assert_eq!(ExportColumn::from_key(column.key()), Some(column));
+        }
+        assert_eq!(
+            ExportFormat::from_key("markdown"),
+            Some(ExportFormat::Markdown)
+        );
+        assert_eq!(ExportFormat::from_key("xml"), None);
+    }
+
+    #[test]
+    fn test_default_path() {
+        let path = default_path("Team Favourites", ExportFormat::Markdown, 1709210040);
+        assert_eq!(path.file_name().unwrap(), "team-favourites-2024-02-29.md");
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use super::i18n;
+use super::model::YTCreator;
+use super::sorting::{parse_bool, parse_count};
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum SubscriberRange {
+    #[default]
+    Any,
+    Under1K,
+    From1KTo10K,
+    From10KTo100K,
+    From100KTo1M,
+    Over1M,
+}
+
+impl SubscriberRange {
+    pub const ALL: [SubscriberRange; 6] = [
+        SubscriberRange::Any,
+        SubscriberRange::Under1K,
+        SubscriberRange::From1KTo10K,
+        SubscriberRange::From10KTo100K,
+        SubscriberRange::From100KTo1M,
+        SubscriberRange::Over1M,
+    ];
+
+    // Lower bound is inclusive, upper bound exclusive
+    fn bounds(&self) -> (i64, Option<i64>) {
+        match self {
+            SubscriberRange::Any => (i64::MIN, None),
+            SubscriberRange::Under1K => (i64::MIN, Some(1_000)),
+            SubscriberRange::From1KTo10K => (1_000, Some(10_000)),
+            SubscriberRange::From10KTo100K => (10_000, Some(100_000)),
+            SubscriberRange::From100KTo1M => (100_000, Some(1_000_000)),
+            SubscriberRange::Over1M => (1_000_000, None),
+        }
+    }
+
+    pub fn contains(&self, subscriber_count: i64) -> bool {
+        let (lower, upper) = self.bounds();
+        subscriber_count >= lower && upper.is_none_or(|upper| subscriber_count < upper)
+    }
+}
+
+impl fmt::Display for SubscriberRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message_id = match self {
+            SubscriberRange::Any => "subscribers-any",
+            SubscriberRange::Under1K => "subscribers-under-1k",
+            SubscriberRange::From1KTo10K => "subscribers-1k-10k",
+            SubscriberRange::From10KTo100K => "subscribers-10k-100k",
+            SubscriberRange::From100KTo1M => "subscribers-100k-1m",
+            SubscriberRange::Over1M => "subscribers-over-1m",
+        };
+        write!(f, "{}", i18n::tr(message_id))
+    }
+}
+
+// Decides which creators are shown, it never changes the data (or the order) itself.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CreatorFilter {
+    pub search_query: String,
+    pub live_only: bool,
+    pub subscriber_range: SubscriberRange,
+    // A creator is shown if it has any of these tags (all creators if empty)
+    pub tags: Vec<String>,
+}
+
+impl CreatorFilter {
+    pub fn is_active(&self) -> bool {
+        self != &CreatorFilter::default()
+    }
+
+    pub fn toggle_tag(&mut self, tag: &str, is_selected: bool) {
+        self.tags.retain(|selected_tag| selected_tag != tag);
+        if is_selected {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    fn matches(&self, obj: &YTCreator, idx: usize, matcher: &SkimMatcherV2) -> bool {
+        if self.live_only && !parse_bool(&obj.is_live_status[idx]) {
+            return false;
+        }
+        if !self
+            .subscriber_range
+            .contains(parse_count(&obj.subscribers[idx]))
+        {
+            return false;
+        }
+        if !self.tags.is_empty() {
+            let creator_tags = obj.tags_of(idx);
+            if !self.tags.iter().any(|tag| creator_tags.contains(tag)) {
+                return false;
+            }
+        }
+        let query = self.search_query.trim();
+        query.is_empty()
+            || matcher.fuzzy_match(&obj.names[idx], query).is_some()
+            || matcher.fuzzy_match(&obj.descriptions[idx], query).is_some()
+    }
+
+    // Indices (into obj, in obj's order) of the creators passing every filter
+    pub fn visible_indices(&self, obj: &YTCreator) -> Vec<usize> {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        (0..obj.names.len())
+            .filter(|idx| self.matches(obj, *idx, &matcher))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::model::get_json_data;
+    use super::*;
+
+    fn visible_names(filter: &CreatorFilter) -> Vec<String> {
+        let obj = get_json_data(Some("test_assets/sort_data.json"));
+        filter
+            .visible_indices(&obj)
+            .into_iter()
+            .map(|idx| obj.names[idx].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_filter_shows_everything_in_order() {
+        assert!(!CreatorFilter::default().is_active());
+        assert_eq!(
+            visible_names(&CreatorFilter::default()),
+            ["Émile", "alice", "Bob", "Zoë"]
+        );
+    }
+
+    #[test]
+    fn test_search_is_fuzzy_over_name_and_description() {
+        let mut filter = CreatorFilter {
+            search_query: "ALC".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(visible_names(&filter), ["alice"]);
+        filter.search_query = "gamr".to_string();
+        assert_eq!(visible_names(&filter), ["Bob"]);
+    }
+
+    #[test]
+    fn test_live_only() {
+        let filter = CreatorFilter {
+            live_only: true,
+            ..Default::default()
+        };
+        assert_eq!(visible_names(&filter), ["alice", "Zoë"]);
+    }
+
+    #[test]
+    fn test_subscriber_range() {
+        assert!(SubscriberRange::Under1K.contains(999));
+        assert!(!SubscriberRange::Under1K.contains(1_000));
+        assert!(SubscriberRange::Over1M.contains(1_000_000));
+        let filter = CreatorFilter {
+            subscriber_range: SubscriberRange::From1KTo10K,
+            ..Default::default()
+        };
+        assert!(visible_names(&filter).is_empty());
+    }
+
+    #[test]
+    fn test_tags_match_any_selected() {
+        let mut filter = CreatorFilter::default();
+        filter.toggle_tag("music", true);
+        assert_eq!(visible_names(&filter), ["Zoë"]);
+        filter.toggle_tag("rust", true);
+        assert_eq!(visible_names(&filter), ["Émile", "Bob", "Zoë"]);
+        filter.toggle_tag("music", false);
+        assert_eq!(visible_names(&filter), ["Émile", "Bob"]);
+    }
+
+    #[test]
+    fn test_filters_compose() {
+        let filter = CreatorFilter {
+            live_only: true,
+            tags: vec!["gaming".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(visible_names(&filter), ["alice"]);
+    }
+}
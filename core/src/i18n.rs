@@ -0,0 +1,342 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, RwLock};
+use unic_langid::LanguageIdentifier;
+
+// Every language has its catalogue in locales/, English is used for missing messages
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::French];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::French => "fr",
+        }
+    }
+
+    fn catalogue(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en-US.ftl"),
+            Language::French => include_str!("../locales/fr.ftl"),
+        }
+    }
+
+    // Separator between groups of thousands in numbers
+    fn group_separator(&self) -> &'static str {
+        match self {
+            Language::English => ",",
+            // Narrow no-break space
+            Language::French => "\u{202f}",
+        }
+    }
+
+    fn decimal_separator(&self) -> &'static str {
+        match self {
+            Language::English => ".",
+            Language::French => ",",
+        }
+    }
+
+    // "fr-CA", "fr_FR.UTF-8"... English for the languages without a catalogue
+    pub fn from_locale(locale: &str) -> Language {
+        let primary = locale
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code().split('-').next() == Some(primary.as_str()))
+            .unwrap_or_default()
+    }
+
+    pub fn from_system() -> Language {
+        sys_locale::get_locale()
+            .map(|locale| Language::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+// Languages are listed under their own name, so that they can be found whatever the
+// current language is
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Language::English => "English",
+            Language::French => "Français",
+        };
+        write!(f, "{name}")
+    }
+}
+
+pub struct Localizer {
+    language: Language,
+    // The requested language first, then the fallback
+    bundles: Vec<FluentBundle<FluentResource>>,
+    // What went wrong loading the catalogues or formatting messages, for the binaries to print
+    errors: Mutex<Vec<String>>,
+}
+
+impl fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Localizer")
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+fn create_bundle(language: Language, errors: &mut Vec<String>) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = language.code().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // The Unicode isolation marks show up as boxes with some fonts
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(language.catalogue().to_string()).unwrap_or_else(
+        |(resource, errs)| {
+            errors.push(format!(
+                "Couldn't parse the {} catalogue: {errs:?}",
+                language.code()
+            ));
+            resource
+        },
+    );
+    if let Err(errs) = bundle.add_resource(resource) {
+        errors.push(format!(
+            "Couldn't load the {} catalogue: {errs:?}",
+            language.code()
+        ));
+    }
+    bundle
+}
+
+impl Localizer {
+    pub fn new(language: Language) -> Self {
+        let mut errors = Vec::new();
+        let mut bundles = vec![create_bundle(language, &mut errors)];
+        if language != Language::English {
+            bundles.push(create_bundle(Language::English, &mut errors));
+        }
+        Localizer {
+            language,
+            bundles,
+            errors: Mutex::new(errors),
+        }
+    }
+
+    fn errors(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.errors.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // The errors since the last call
+    pub fn take_errors(&self) -> Vec<String> {
+        std::mem::take(&mut *self.errors())
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    // The message id itself if no catalogue has it, easier to spot than an empty label
+    pub fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errs = vec![];
+            let value = bundle.format_pattern(pattern, args, &mut errs);
+            if !errs.is_empty() {
+                self.errors()
+                    .push(format!("Couldn't format the message {id}: {errs:?}"));
+            }
+            return value.into_owned();
+        }
+        id.to_string()
+    }
+
+    // 1234567 -> "1,234,567" in English, "1 234 567" in French
+    pub fn format_count(&self, count: i64) -> String {
+        let digits = count.unsigned_abs().to_string();
+        let groups = digits
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|group| std::str::from_utf8(group).unwrap_or_default())
+            .collect::<Vec<&str>>();
+        let sign = if count < 0 { "-" } else { "" };
+        format!("{sign}{}", groups.join(self.language.group_separator()))
+    }
+
+    // 1234 -> "1.2K", 34567 -> "34.5K", 123456 -> "123K". Truncated rather than rounded, like
+    // YouTube does, so that a channel never looks bigger than it is.
+    pub fn format_compact_count(&self, count: i64) -> String {
+        const UNITS: [(u64, &str); 3] = [
+            (1_000_000_000, "count-billions"),
+            (1_000_000, "count-millions"),
+            (1_000, "count-thousands"),
+        ];
+        let Some((unit, message_id)) = UNITS.iter().find(|(unit, _)| count.unsigned_abs() >= *unit)
+        else {
+            return self.format_count(count);
+        };
+        let tenths = count.unsigned_abs() * 10 / unit;
+        // A decimal only while it adds something: "34.5K" but "123K" and "2M"
+        let mantissa = if tenths >= 1_000 || tenths.is_multiple_of(10) {
+            (tenths / 10).to_string()
+        } else {
+            format!(
+                "{}{}{}",
+                tenths / 10,
+                self.language.decimal_separator(),
+                tenths % 10
+            )
+        };
+        let sign = if count < 0 { "-" } else { "" };
+        let mut args = FluentArgs::new();
+        args.set("count", format!("{sign}{mantissa}"));
+        self.format(message_id, Some(&args))
+    }
+}
+
+// The language of the whole UI, changed from the settings
+fn localizer() -> &'static RwLock<Arc<Localizer>> {
+    static LOCALIZER: OnceLock<RwLock<Arc<Localizer>>> = OnceLock::new();
+    LOCALIZER.get_or_init(|| RwLock::new(Arc::new(Localizer::new(Language::default()))))
+}
+
+fn current() -> Arc<Localizer> {
+    match localizer().read() {
+        Ok(localizer) => Arc::clone(&localizer),
+        Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+    }
+}
+
+pub fn set_language(language: Language) {
+    if current().language() == language {
+        return;
+    }
+    let new_localizer = Localizer::new(language);
+    // Still to be printed
+    new_localizer.errors().splice(0..0, current().take_errors());
+    let new_localizer = Arc::new(new_localizer);
+    match localizer().write() {
+        Ok(mut localizer) => *localizer = new_localizer,
+        Err(poisoned) => *poisoned.into_inner() = new_localizer,
+    }
+}
+
+pub fn take_errors() -> Vec<String> {
+    current().take_errors()
+}
+
+pub fn tr(id: &str) -> String {
+    current().format(id, None)
+}
+
+pub fn tr_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    current().format(id, Some(&fluent_args))
+}
+
+pub fn format_count(count: i64) -> String {
+    current().format_count(count)
+}
+
+pub fn format_compact_count(count: i64) -> String {
+    current().format_compact_count(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message_ids(catalogue: &str) -> Vec<&str> {
+        let mut ids = catalogue
+            .lines()
+            .filter(|line| line.chars().next().is_some_and(|c| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect::<Vec<&str>>();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_catalogues_have_the_same_messages() {
+        let english = message_ids(Language::English.catalogue());
+        assert!(english.contains(&"app-title"));
+        for language in Language::ALL {
+            assert_eq!(message_ids(language.catalogue()), english, "{language}");
+        }
+    }
+
+    #[test]
+    fn test_format_messages() {
+        let french = Localizer::new(Language::French);
+        assert_eq!(french.format("view-list", None), "Liste");
+        let mut args = FluentArgs::new();
+        args.set("is_live", "true");
+        assert_eq!(
+            french.format("card-is-live", Some(&args)),
+            "En direct ? Oui"
+        );
+        let english = Localizer::new(Language::English);
+        assert_eq!(english.format("card-is-live", Some(&args)), "Is Live?: Yes");
+        assert_eq!(english.format("no-such-message", None), "no-such-message");
+    }
+
+    #[test]
+    fn test_format_count() {
+        let english = Localizer::new(Language::English);
+        assert_eq!(english.format_count(0), "0");
+        assert_eq!(english.format_count(999), "999");
+        assert_eq!(english.format_count(1_234_567), "1,234,567");
+        assert_eq!(english.format_count(-12_345), "-12,345");
+        let french = Localizer::new(Language::French);
+        assert_eq!(french.format_count(1_234_567), "1\u{202f}234\u{202f}567");
+    }
+
+    #[test]
+    fn test_format_errors_are_kept() {
+        let english = Localizer::new(Language::English);
+        assert!(english.take_errors().is_empty());
+        // Without the count
+        assert_eq!(english.format("tag-section", None), "{$tag} ({$count})");
+        assert_eq!(english.take_errors().len(), 1);
+        assert!(english.take_errors().is_empty());
+    }
+
+    #[test]
+    fn test_format_compact_count() {
+        let english = Localizer::new(Language::English);
+        assert_eq!(english.format_compact_count(999), "999");
+        assert_eq!(english.format_compact_count(1_234), "1.2K");
+        assert_eq!(english.format_compact_count(34_567), "34.5K");
+        assert_eq!(english.format_compact_count(123_456), "123K");
+        assert_eq!(english.format_compact_count(999_999), "999K");
+        assert_eq!(english.format_compact_count(2_000_000), "2M");
+        assert_eq!(english.format_compact_count(1_290_000), "1.2M");
+        assert_eq!(english.format_compact_count(-1_500), "-1.5K");
+        let french = Localizer::new(Language::French);
+        assert_eq!(french.format_compact_count(1_290_000), "1,2 M");
+        assert_eq!(french.format_compact_count(3_100_000_000), "3,1 Md");
+    }
+
+    #[test]
+    fn test_language_from_locale() {
+        assert_eq!(Language::from_locale("fr-CA"), Language::French);
+        assert_eq!(Language::from_locale("fr_FR.UTF-8"), Language::French);
+        assert_eq!(Language::from_locale("en-GB"), Language::English);
+        assert_eq!(Language::from_locale("de-DE"), Language::English);
+        assert_eq!(Language::from_locale(""), Language::English);
+    }
+}
@@ -0,0 +1,451 @@
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::model::{self, YTCreator, MAX_EXPECTED_ITEMS};
+use super::sources::ApiClient;
+
+// A channel of a YouTube subscriptions export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    pub channel_id: String,
+    pub name: String,
+}
+
+// subscriptions.csv of Google Takeout, or the OPML file of the old subscription manager
+pub fn read_subscriptions(path: &Path) -> Result<Vec<Subscription>, Box<dyn Error>> {
+    parse_subscriptions(&fs::read_to_string(path)?)
+}
+
+pub fn parse_subscriptions(contents: &str) -> Result<Vec<Subscription>, Box<dyn Error>> {
+    let contents = contents.trim_start_matches('\u{feff}').trim_start();
+    let subscriptions = match contents.starts_with('<') {
+        true => parse_opml(contents),
+        false => parse_takeout_csv(contents),
+    };
+    if subscriptions.is_empty() {
+        return Err("no YouTube channels in this file".into());
+    }
+    Ok(subscriptions)
+}
+
+// "Channel Id,Channel Url,Channel Title", the header is translated in some languages so
+// the columns fall back to Takeout's order
+fn parse_takeout_csv(contents: &str) -> Vec<Subscription> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().map(csv_record).unwrap_or_default();
+    let column = |title: &str, fallback: usize| {
+        header
+            .iter()
+            .position(|cell| cell.trim().eq_ignore_ascii_case(title))
+            .unwrap_or(fallback)
+    };
+    let (id_column, title_column) = (column("channel id", 0), column("channel title", 2));
+    lines
+        .map(csv_record)
+        .filter_map(|record| {
+            Some(Subscription {
+                channel_id: record.get(id_column)?.trim().to_string(),
+                name: record.get(title_column)?.trim().to_string(),
+            })
+        })
+        .filter(|subscription| !subscription.channel_id.is_empty())
+        .collect()
+}
+
+// The cells of one line, quoted cells may hold commas and doubled quotes
+fn csv_record(line: &str) -> Vec<String> {
+    let mut cells = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let cell = cells.last_mut().unwrap();
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => cells.push(String::new()),
+            _ => cell.push(c),
+        }
+    }
+    cells
+}
+
+// <outline title="Name" xmlUrl="https://www.youtube.com/feeds/videos.xml?channel_id=UC..."/>
+fn parse_opml(contents: &str) -> Vec<Subscription> {
+    contents
+        .split("<outline")
+        .skip(1)
+        .filter_map(|element| {
+            let tag = &element[..element.find('>')?];
+            let feed_url = xml_attribute(tag, "xmlUrl")?;
+            let (_, channel_id) = feed_url.split_once("channel_id=")?;
+            let channel_id = channel_id.split('&').next().unwrap_or_default();
+            let name = xml_attribute(tag, "title").or_else(|| xml_attribute(tag, "text"))?;
+            Some(Subscription {
+                channel_id: channel_id.trim().to_string(),
+                name: name.trim().to_string(),
+            })
+        })
+        .filter(|subscription| !subscription.channel_id.is_empty())
+        .collect()
+}
+
+fn xml_attribute(tag: &str, name: &str) -> Option<String> {
+    ['"', '\''].into_iter().find_map(|quote| {
+        let start = format!(" {name}={quote}");
+        let (_, value) = tag.split_once(&start)?;
+        let (value, _) = value.split_once(quote)?;
+        Some(unescape_xml(value))
+    })
+}
+
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+// Most subscriptions a single list request returns
+const SUBSCRIPTIONS_PER_PAGE: usize = 50;
+
+// One page of the signed in account's subscriptions
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SubscriptionsPage {
+    pub subscriptions: Vec<Subscription>,
+    // Of all the pages, as the API reports it
+    pub total: Option<usize>,
+    // None on the last page
+    pub next_page_token: Option<String>,
+}
+
+// How far the fetching of the account's subscriptions got
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportProgress {
+    pub fetched: usize,
+    pub total: Option<usize>,
+}
+
+// client has to be signed in, mine=true is the account of the token. 1 quota unit a page.
+pub fn fetch_subscriptions_page(
+    client: &dyn ApiClient,
+    page_token: Option<&str>,
+) -> Result<SubscriptionsPage, Box<dyn Error>> {
+    let mut params = vec![
+        ("part", "snippet".to_string()),
+        ("mine", "true".to_string()),
+        ("maxResults", SUBSCRIPTIONS_PER_PAGE.to_string()),
+    ];
+    if let Some(page_token) = page_token {
+        params.push(("pageToken", page_token.to_string()));
+    }
+    let response = client.get("subscriptions", &params)?;
+    let subscriptions = response["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let snippet = &item["snippet"];
+            Some(Subscription {
+                channel_id: snippet["resourceId"]["channelId"].as_str()?.to_string(),
+                name: snippet["title"].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+    Ok(SubscriptionsPage {
+        subscriptions,
+        total: response["pageInfo"]["totalResults"]
+            .as_u64()
+            .map(|total| total as usize),
+        next_page_token: response["nextPageToken"]
+            .as_str()
+            .filter(|page_token| !page_token.is_empty())
+            .map(str::to_string),
+    })
+}
+
+// Every page, progress is called after each of them
+pub fn fetch_my_subscriptions(
+    client: &dyn ApiClient,
+    mut progress: impl FnMut(ImportProgress),
+) -> Result<Vec<Subscription>, Box<dyn Error>> {
+    let mut subscriptions = Vec::new();
+    let mut page_token = None;
+    loop {
+        let page = fetch_subscriptions_page(client, page_token.as_deref())?;
+        subscriptions.extend(page.subscriptions);
+        progress(ImportProgress {
+            fetched: subscriptions.len(),
+            total: page.total,
+        });
+        match page.next_page_token {
+            Some(next_page_token) => page_token = Some(next_page_token),
+            None => return Ok(subscriptions),
+        }
+    }
+}
+
+// Creators without a channel id can only be told apart by their name
+fn is_in(obj: &YTCreator, subscription: &Subscription) -> bool {
+    let column = |field_name: &str| obj.get_field(field_name).cloned().unwrap_or_default();
+    let (names, channel_ids) = (column("names"), column("channel_ids"));
+    names.iter().enumerate().any(|(idx, name)| {
+        match channel_ids.get(idx).map(|channel_id| channel_id.trim()) {
+            Some(channel_id) if !channel_id.is_empty() => channel_id == subscription.channel_id,
+            _ => name.trim().to_lowercase() == subscription.name.to_lowercase(),
+        }
+    })
+}
+
+// The subscriptions which aren't in obj yet, every channel once
+pub fn new_subscriptions(obj: &YTCreator, subscriptions: &[Subscription]) -> Vec<Subscription> {
+    let mut new: Vec<Subscription> = Vec::new();
+    for subscription in subscriptions {
+        let seen = new
+            .iter()
+            .any(|other| other.channel_id == subscription.channel_id);
+        if !seen && !is_in(obj, subscription) {
+            new.push(subscription.clone());
+        }
+    }
+    new
+}
+
+// obj with the new subscriptions added at the end, not live and without numbers until the
+// next refresh from the YouTube Data API, which also takes the first subscriber count as the
+// previous one. Without an avatar link the window shows its bundled placeholder.
+pub fn merge_subscriptions(
+    obj: &YTCreator,
+    subscriptions: &[Subscription],
+) -> Result<YTCreator, Box<dyn Error>> {
+    let new = new_subscriptions(obj, subscriptions);
+    let count = obj.get_field("names").map_or(0, Vec::len);
+    if count + new.len() > MAX_EXPECTED_ITEMS {
+        return Err(format!(
+            "a watchlist holds at most {MAX_EXPECTED_ITEMS} creators, {} more fit in this one",
+            MAX_EXPECTED_ITEMS.saturating_sub(count)
+        )
+        .into());
+    }
+    let mut columns = serde_json::to_value(obj)?;
+    let Value::Object(columns_by_name) = &mut columns else {
+        return Err("the creators aren't a JSON object".into());
+    };
+    for (offset, subscription) in new.iter().enumerate() {
+        for (field_name, values) in columns_by_name.iter_mut() {
+            let value = match field_name.as_str() {
+                "names" => subscription.name.clone(),
+                "channel_ids" => subscription.channel_id.clone(),
+                "is_live_status" => "false".to_string(),
+                // After the creators already there
+                "custom_order" => (count + offset).to_string(),
+                _ => String::new(),
+            };
+            if let Value::Array(values) = values {
+                values.push(value.into());
+            }
+        }
+    }
+    Ok(serde_json::from_value(columns)?)
+}
+
+// Adds the subscriptions to the watchlist file at path, returns how many were new
+pub fn import_into_watchlist(
+    path: &Path,
+    subscriptions: &[Subscription],
+) -> Result<usize, Box<dyn Error>> {
+    let obj = model::read_json(&path.to_string_lossy())?;
+    let merged = merge_subscriptions(&obj, subscriptions)?;
+    fs::write(path, serde_json::to_string_pretty(&merged)? + "\n")?;
+    let count = |obj: &YTCreator| obj.get_field("names").map_or(0, Vec::len);
+    Ok(count(&merged) - count(&obj))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::sources::OAuthClient;
+    use super::super::testing::temp_config_dir;
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn subscription(channel_id: &str, name: &str) -> Subscription {
+        Subscription {
+            channel_id: channel_id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_read_takeout_csv() {
+        assert_eq!(
+            read_subscriptions(Path::new("test_assets/subscriptions.csv")).unwrap(),
+            [
+                subscription("UCalice", "Alice"),
+                subscription("UCbob", "Bob, the \"builder\"")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_opml() {
+        assert_eq!(
+            read_subscriptions(Path::new("test_assets/subscriptions.opml")).unwrap(),
+            [
+                subscription("UCalice", "Alice"),
+                subscription("UCtom", "Tom & Jerry")
+            ]
+        );
+        assert!(parse_subscriptions("<opml><body></body></opml>").is_err());
+    }
+
+    // Serves test_assets/subscriptions_page_<n>.json by page token, and a 401 without the
+    // access token. Header names have any case.
+    fn mock_api(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for _ in 0..requests {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = String::new();
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    request += &line.to_lowercase();
+                    request.push('\n');
+                }
+                let page = match request.contains("pagetoken=page2") {
+                    true => 2,
+                    false => 1,
+                };
+                let (status, body) = match request.contains("authorization: bearer secret") {
+                    true => (
+                        "200 OK",
+                        fs::read_to_string(format!("test_assets/subscriptions_page_{page}.json"))
+                            .unwrap(),
+                    ),
+                    false => (
+                        "401 Unauthorized",
+                        r#"{"error": {"message": "Invalid Credentials"}}"#.to_string(),
+                    ),
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_fetch_my_subscriptions() {
+        let client = OAuthClient::with_api_url("secret".to_string(), &mock_api(2));
+        let mut progress = Vec::new();
+        let subscriptions =
+            fetch_my_subscriptions(&client, |fetched| progress.push(fetched)).unwrap();
+        assert_eq!(
+            subscriptions,
+            [
+                subscription("UCalice", "Alice"),
+                subscription("UCbob", "Bob"),
+                subscription("UCtom", "Tom & Jerry")
+            ]
+        );
+        assert_eq!(
+            progress,
+            [
+                ImportProgress {
+                    fetched: 2,
+                    total: Some(3)
+                },
+                ImportProgress {
+                    fetched: 3,
+                    total: Some(3)
+                }
+            ]
+        );
+
+        let client = OAuthClient::with_api_url("expired".to_string(), &mock_api(1));
+        let err = fetch_my_subscriptions(&client, |_| {}).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "subscriptions: 401 Unauthorized: Invalid Credentials"
+        );
+    }
+
+    #[test]
+    fn test_merge_subscriptions() {
+        let obj: YTCreator = serde_json::from_str(
+            r#"{
+                "names": ["Alice", "Bob"],
+                "avatar_links": ["a.png", "b.png"],
+                "descriptions": ["", ""],
+                "is_live_status": ["true", "false"],
+                "subscribers": ["10", "20"],
+                "channel_ids": ["UCalice", ""]
+            }"#,
+        )
+        .unwrap();
+        let subscriptions = [
+            subscription("UCalice", "Alice (new name)"),
+            subscription("UCbob", "bob"),
+            subscription("UCtom", "Tom"),
+            subscription("UCtom", "Tom"),
+        ];
+        assert_eq!(
+            new_subscriptions(&obj, &subscriptions),
+            [subscription("UCtom", "Tom")]
+        );
+
+        let merged = merge_subscriptions(&obj, &subscriptions).unwrap();
+        assert_eq!(merged.get_field("names").unwrap(), &["Alice", "Bob", "Tom"]);
+        assert_eq!(
+            merged.get_field("channel_ids").unwrap(),
+            &["UCalice", "", "UCtom"]
+        );
+        assert_eq!(
+            merged.get_field("is_live_status").unwrap(),
+            &["true", "false", "false"]
+        );
+        assert_eq!(merged.get_field("subscribers").unwrap()[2], "");
+        assert_eq!(
+            merged.get_field("avatar_links").unwrap(),
+            &["a.png", "b.png", ""]
+        );
+
+        let too_many: Vec<Subscription> = (0..MAX_EXPECTED_ITEMS)
+            .map(|n| subscription(&format!("UC{n}"), &format!("Creator {n}")))
+            .collect();
+        assert!(merge_subscriptions(&obj, &too_many).is_err());
+    }
+
+    #[test]
+    fn test_import_into_watchlist() {
+        let path = temp_config_dir("import")
+            .join("watchlists")
+            .join("friends.json");
+        fs::write(&path, r#"{"names": [], "avatar_links": [], "descriptions": [], "is_live_status": [], "subscribers": []}"#).unwrap();
+
+        let subscriptions = [subscription("UCalice", "Alice")];
+        assert_eq!(import_into_watchlist(&path, &subscriptions).unwrap(), 1);
+        // Already there the second time
+        assert_eq!(import_into_watchlist(&path, &subscriptions).unwrap(), 0);
+        let obj = model::read_json(&path.to_string_lossy()).unwrap();
+        assert_eq!(
+            obj.channel_url(0),
+            "https://www.youtube.com/channel/UCalice"
+        );
+    }
+}
@@ -0,0 +1,23 @@
+// The monitoring without any window: the creators of a watchlist and where their data comes
+// from, sorting and filtering, the user's files and the periodic refresh. The window, the
+// command line and the terminal UI of yt-monitor are built on it, other tools can embed it.
+pub mod appearance;
+pub mod client;
+pub mod config;
+pub mod export;
+pub mod filter;
+pub mod i18n;
+pub mod import;
+pub mod metrics;
+pub mod model;
+pub mod monitor;
+pub mod records;
+pub mod sorting;
+pub mod sources;
+pub mod status;
+pub mod styling;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod themes;
+pub mod views;
+pub mod watchlists;
@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use super::monitor::Monitor;
+use super::records::{self, CreatorRecord};
+
+// The creators and the refreshes of monitor in the Prometheus text format, see
+// https://prometheus.io/docs/instrumenting/exposition_formats/
+pub fn format_metrics(monitor: &Monitor, now: u64) -> String {
+    let records = records::creator_records(&monitor.json_obj);
+    let refresh_status = &monitor.refresh_status;
+    let mut metrics = String::new();
+
+    creator_gauge(
+        &mut metrics,
+        &records,
+        "yt_creator_subscribers",
+        "Subscribers of the creator",
+        |record| record.subscribers,
+    );
+    creator_gauge(
+        &mut metrics,
+        &records,
+        "yt_creator_live",
+        "1 if the creator is live, 0 otherwise",
+        |record| Some(record.is_live as u64),
+    );
+    creator_gauge(
+        &mut metrics,
+        &records,
+        "yt_creator_viewers",
+        "Concurrent viewers of the creator's stream",
+        |record| record.viewers,
+    );
+
+    if let Some(last_duration) = refresh_status.last_duration {
+        header(
+            &mut metrics,
+            "yt_monitor_poll_duration_seconds",
+            "How long the last refresh took",
+            "gauge",
+        );
+        let _ = writeln!(
+            metrics,
+            "yt_monitor_poll_duration_seconds {}",
+            last_duration.as_secs_f64()
+        );
+    }
+    header(
+        &mut metrics,
+        "yt_monitor_api_errors_total",
+        "Failed refreshes from the YouTube Data API",
+        "counter",
+    );
+    let _ = writeln!(
+        metrics,
+        "yt_monitor_api_errors_total {}",
+        refresh_status.api_errors
+    );
+    if refresh_status.uses_api {
+        header(
+            &mut metrics,
+            "yt_monitor_quota_used_units",
+            "YouTube Data API units used today",
+            "gauge",
+        );
+        let _ = writeln!(
+            metrics,
+            "yt_monitor_quota_used_units {}",
+            refresh_status.quota.used_today(now)
+        );
+    }
+    metrics
+}
+
+// One line per creator with a value
+fn creator_gauge(
+    metrics: &mut String,
+    records: &[CreatorRecord],
+    name: &str,
+    help: &str,
+    value: impl Fn(&CreatorRecord) -> Option<u64>,
+) {
+    header(metrics, name, help, "gauge");
+    for record in records {
+        if let Some(value) = value(record) {
+            let _ = writeln!(
+                metrics,
+                "{name}{{channel=\"{}\",name=\"{}\"}} {value}",
+                escape_label(&record.id),
+                escape_label(&record.name)
+            );
+        }
+    }
+}
+
+fn header(metrics: &mut String, name: &str, help: &str, metric_type: &str) {
+    let _ = writeln!(metrics, "# HELP {name} {help}");
+    let _ = writeln!(metrics, "# TYPE {name} {metric_type}");
+}
+
+// Backslashes, double quotes and line feeds have to be escaped in label values
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::testing::temp_config_dir;
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_metrics() {
+        let config_dir = temp_config_dir("metrics");
+        fs::write(
+            config_dir.join("watchlists").join("friends.json"),
+            r#"{
+                "names": ["Kush", "Jane \"JD\" Doe"],
+                "avatar_links": ["", ""],
+                "descriptions": ["", ""],
+                "subscribers": ["1200", "hidden"],
+                "is_live_status": ["true", "false"],
+                "channel_ids": ["UC123", ""],
+                "viewers": ["345", ""]
+            }"#,
+        )
+        .unwrap();
+        let mut monitor = Monitor::open(config_dir, Some("friends")).unwrap();
+        monitor.refresh().unwrap();
+        monitor.refresh_status.last_duration = Some(Duration::from_millis(250));
+        monitor.refresh_status.api_errors = 2;
+
+        let metrics = format_metrics(&monitor, 0);
+        let lines: Vec<&str> = metrics
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                r#"yt_creator_subscribers{channel="UC123",name="Kush"} 1200"#,
+                r#"yt_creator_live{channel="UC123",name="Kush"} 1"#,
+                r#"yt_creator_live{channel="Jane \"JD\" Doe",name="Jane \"JD\" Doe"} 0"#,
+                r#"yt_creator_viewers{channel="UC123",name="Kush"} 345"#,
+                "yt_monitor_poll_duration_seconds 0.25",
+                "yt_monitor_api_errors_total 2",
+            ]
+        );
+        assert!(metrics.contains("# TYPE yt_monitor_api_errors_total counter\n"));
+    }
+}
@@ -0,0 +1,689 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+
+use std::error::Error;
+use std::fmt;
+
+use super::i18n;
+
+pub use super::filter::{CreatorFilter, SubscriberRange};
+pub use super::sorting::{AllowedFieldNamesForSorting, SortDirection};
+pub use super::styling::{CardAnimation, CardColors, CardScheme};
+pub use super::views::{CardFormat, DisplayFormat, ListColumn, ViewMode};
+// The modules on top of the creators, so that model::* brings everything about them
+pub use super::{filter, sorting, sources, styling, views};
+
+pub const MAX_EXPECTED_ITEMS: usize = 12;
+pub const JSON_FILE_PATH: &str = "list_users.json";
+// A scheduled stream which hasn't started this long after its time was probably cancelled
+pub const UPCOMING_GRACE_SECS: u64 = 3_600;
+
+// This also adds an impl: get_field to get the corresponding field from the field name (&str)
+macro_rules! get_struct_names {
+    (
+        $(#[$struct_meta:meta])*
+        pub struct $name:ident {
+            $(pub $fname:ident : $ftype:ty), *
+        }
+    ) => {
+        $(#[$struct_meta])*
+        pub struct $name {
+            // Used directly by the modules on top of the creators (sorting, views, ...)
+            $(pub(crate) $fname : $ftype),*
+        }
+
+        impl $name {
+            fn field_names() -> &'static [&'static str] {
+                static NAMES: &'static [&'static str] = &[$(stringify!($fname)), *];
+                NAMES
+            }
+
+            pub fn get_field(&self, field_name: &str) -> Option<&Vec<String>> {
+                match field_name {
+                    $(stringify!($fname) => Some(&self.$fname)),
+                    *,
+                    &_ => None
+                }
+            }
+
+            fn slice_to(&self, count_items: usize) -> YTCreator {
+                YTCreator {
+                    $($fname: self.$fname.get(0..count_items).expect(&format!("Not enough elements to be sliced into, maybe check the input {count_items} again.")).to_vec()),
+                    *
+                }
+            }
+
+            fn set_field(&mut self, field_name: &str, field_data: Vec<String>) -> &mut YTCreator {
+                match field_name {
+                    $(stringify!($fname) => {
+                        self.$fname = field_data
+                    }),
+                    *,
+                    &_ => ()
+                };
+                self
+            }
+        }
+    }
+}
+
+// Fields missing from the JSON file (older data files) are filled in by fill_missing_fields
+get_struct_names! {
+    #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+    #[serde(default)]
+    pub struct YTCreator {
+        pub names: Vec<String>,
+        pub avatar_links: Vec<String>,
+        pub descriptions: Vec<String>,
+        pub is_live_status: Vec<String>,
+        pub subscribers: Vec<String>,
+        pub previous_subscribers: Vec<String>,
+        pub last_live: Vec<String>,
+        pub custom_order: Vec<String>,
+        pub tags: Vec<String>,
+        pub channel_ids: Vec<String>,
+        pub viewers: Vec<String>,
+        pub scheduled_start: Vec<String>
+    }
+}
+
+impl YTCreator {
+    fn size(&self) -> usize {
+        let mut lengths_all: Vec<usize> = vec![];
+        let mut msges: String = "".to_string();
+        for field_name in YTCreator::field_names().iter() {
+            let len_field: usize = self.get_field(field_name).unwrap().len();
+            lengths_all.push(len_field);
+            if len_field > MAX_EXPECTED_ITEMS {
+                msges += format!(
+                    "Found: {len_field} but got {MAX_EXPECTED_ITEMS} for the given field_name: {field_name}\n"
+                ).as_str();
+            };
+        }
+
+        if !msges.is_empty() {
+            panic!("Found more items than expected. {msges}");
+        }
+        assert!(
+            lengths_all
+                .windows(2)
+                .all(|single_len| single_len[0] == single_len[1]),
+            "Not all fields have equal length. Check the input data again."
+        );
+        self.names.len()
+    }
+
+    // Every field needs an item for every creator. Data read from a file is checked with it
+    // before size(), which panics on uneven fields.
+    fn check_lengths(&self) -> Result<(), Box<dyn Error>> {
+        let count = self.names.len();
+        for field_name in YTCreator::field_names() {
+            let len_field = self.get_field(field_name).map_or(0, Vec::len);
+            if len_field != count {
+                return Err(
+                    format!("{field_name} has {len_field} items for {count} creators").into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    // Value to use for the item at idx when an optional field is missing from the data file,
+    // None for fields that have to be present.
+    fn default_field_value(&self, field_name: &str, idx: usize) -> Option<String> {
+        match field_name {
+            // No history yet, so no growth either
+            "previous_subscribers" => self.subscribers.get(idx).cloned(),
+            // Never seen live
+            "last_live" => Some(String::new()),
+            // Keep the order of the file
+            "custom_order" => Some(idx.to_string()),
+            "tags" => Some(String::new()),
+            // Unknown, the channel is then searched by name
+            "channel_ids" => Some(String::new()),
+            // Only known during a stream, from the YouTube Data API
+            "viewers" => Some(String::new()),
+            // Unix timestamp of the next scheduled stream or premiere, from the API too
+            "scheduled_start" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    fn fill_missing_fields(&mut self) -> &mut YTCreator {
+        let total_count = self.names.len();
+        for field_name in YTCreator::field_names() {
+            if !self.get_field(field_name).unwrap().is_empty() {
+                continue;
+            }
+            let default_data: Option<Vec<String>> = (0..total_count)
+                .map(|idx| self.default_field_value(field_name, idx))
+                .collect();
+            if let Some(field_data) = default_data {
+                self.set_field(field_name, field_data);
+            }
+        }
+        self
+    }
+
+    // Tags are stored comma separated per creator, e.g. "rust, gaming"
+    pub fn tags_of(&self, idx: usize) -> Vec<String> {
+        self.tags
+            .get(idx)
+            .map(|tags| {
+                tags.split(',')
+                    .map(|tag| tag.trim().to_lowercase())
+                    .filter(|tag| !tag.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Every tag used by at least one creator, sorted and without duplicates
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut all_tags: Vec<String> = (0..self.names.len())
+            .flat_map(|idx| self.tags_of(idx))
+            .collect();
+        all_tags.sort();
+        all_tags.dedup();
+        all_tags
+    }
+
+    // Groups the creators at the given indices by tag (groups sorted by tag name), keeping
+    // their order within every group. Creators with several tags show up in each of their
+    // groups, untagged creators end up in a last group with an empty tag.
+    pub fn group_by_tag(&self, indices: &[usize]) -> Vec<(String, Vec<usize>)> {
+        let mut groups: Vec<(String, Vec<usize>)> = self
+            .all_tags()
+            .into_iter()
+            .map(|tag| (tag, Vec::new()))
+            .collect();
+        let mut untagged: Vec<usize> = Vec::new();
+        for idx in indices {
+            let creator_tags = self.tags_of(*idx);
+            if creator_tags.is_empty() {
+                untagged.push(*idx);
+            }
+            for (tag, members) in groups.iter_mut() {
+                if creator_tags.contains(tag) {
+                    members.push(*idx);
+                }
+            }
+        }
+        groups.retain(|(_, members)| !members.is_empty());
+        if !untagged.is_empty() {
+            groups.push((String::new(), untagged));
+        }
+        groups
+    }
+
+    // The cards of the tag sections from top to bottom, as the arrow keys go through them.
+    // Collapsed sections show no cards.
+    pub fn tag_sections_order(&self, indices: &[usize], collapsed_tags: &[String]) -> Vec<usize> {
+        self.group_by_tag(indices)
+            .into_iter()
+            .filter(|(tag, _)| !collapsed_tags.contains(tag))
+            .flat_map(|(_, members)| members)
+            .collect()
+    }
+
+    // When the creator's next stream is scheduled, None while live or without one
+    pub fn scheduled_start_of(&self, idx: usize, now: u64) -> Option<u64> {
+        upcoming_start(
+            self.is_live_status.get(idx)?,
+            self.scheduled_start.get(idx)?,
+            now,
+        )
+    }
+
+    // The creators at the given indices with a stream coming up, the soonest first
+    pub fn upcoming(&self, indices: &[usize], now: u64) -> Vec<usize> {
+        let mut upcoming: Vec<(u64, usize)> = indices
+            .iter()
+            .filter_map(|idx| Some((self.scheduled_start_of(*idx, now)?, *idx)))
+            .collect();
+        upcoming.sort();
+        upcoming.into_iter().map(|(_, idx)| idx).collect()
+    }
+
+    // The creator's channel if its id is known, a YouTube search for the name otherwise
+    pub fn channel_url(&self, idx: usize) -> String {
+        match self
+            .channel_ids
+            .get(idx)
+            .map(|channel_id| channel_id.trim())
+        {
+            Some(channel_id) if !channel_id.is_empty() => {
+                format!("https://www.youtube.com/channel/{channel_id}")
+            }
+            _ => reqwest::Url::parse_with_params(
+                "https://www.youtube.com/results",
+                [("search_query", self.names[idx].as_str())],
+            )
+            .map(String::from)
+            .unwrap_or_default(),
+        }
+    }
+
+    // New YTCreator with the items at the given indices, in that order
+    pub fn select(&self, indices: &[usize]) -> YTCreator {
+        let mut new_yt_creator: YTCreator = YTCreator::default();
+        for field_name in YTCreator::field_names() {
+            let data_field = self.get_field(field_name).unwrap();
+            let new_data_field: Vec<String> = indices
+                .iter()
+                .map(|idx| data_field.get(*idx).unwrap().to_string())
+                .collect();
+            new_yt_creator.set_field(field_name, new_data_field);
+        }
+        new_yt_creator
+    }
+
+    // Sorts in the default direction of the given field, see sort_by_direction
+    pub fn sort_by(
+        &self,
+        field_name: AllowedFieldNamesForSorting,
+    ) -> Option<(YTCreator, Vec<usize>)> {
+        self.sort_by_direction(field_name, field_name.default_direction())
+    }
+
+    // Returns the sorted YTCreator along with the original index of every item, so that
+    // other per-item data (photos, live status) can be rearranged the same way.
+    pub fn sort_by_direction(
+        &self,
+        field_name: AllowedFieldNamesForSorting,
+        direction: SortDirection,
+    ) -> Option<(YTCreator, Vec<usize>)> {
+        let new_indices = sorting::sorted_indices(self, field_name, direction);
+        Some((self.select(&new_indices), new_indices))
+    }
+}
+
+// The scheduled_start value of a creator who isn't live, unless it's long past
+pub fn upcoming_start(is_live_status: &str, scheduled_start: &str, now: u64) -> Option<u64> {
+    if sorting::parse_bool(is_live_status) {
+        return None;
+    }
+    let scheduled_start = sorting::parse_timestamp(scheduled_start)?;
+    (scheduled_start + UPCOMING_GRACE_SECS > now).then_some(scheduled_start)
+}
+
+// Straight from the documentation
+pub fn read_json(file_path: &str) -> Result<YTCreator, Box<dyn Error>> {
+    let file = File::open(file_path)?;
+    let reader = BufReader::new(file);
+
+    // Read the JSON contents of the file as an instance of `YTCreator`.
+    let mut u: YTCreator = serde_json::from_reader(reader)?;
+    u.fill_missing_fields();
+    u.check_lengths()?;
+    if u.size() > MAX_EXPECTED_ITEMS {
+        Ok(u.slice_to(MAX_EXPECTED_ITEMS))
+    } else {
+        Ok(u)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Eq, Serialize, Deserialize)]
+pub enum ThemeType {
+    // Light or Dark, following the desktop's color scheme preference
+    Auto,
+    Light,
+    #[default]
+    Dark,
+    HighContrast,
+    // Name of a theme loaded from the themes directory, see themes::load_themes
+    Custom(String),
+}
+
+impl fmt::Display for ThemeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeType::Auto => write!(f, "{}", i18n::tr("theme-auto")),
+            ThemeType::Light => write!(f, "{}", i18n::tr("theme-light")),
+            ThemeType::Dark => write!(f, "{}", i18n::tr("theme-dark")),
+            ThemeType::HighContrast => write!(f, "{}", i18n::tr("theme-high-contrast")),
+            ThemeType::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+pub fn get_json_data(json_path: Option<&str>) -> YTCreator {
+    let json_file_path = json_path.unwrap_or(JSON_FILE_PATH);
+    read_json(json_file_path).unwrap()
+}
+
+pub fn get_live_status(live_status: Option<&Vec<String>>) -> Vec<bool> {
+    let mut out_status: Vec<bool> = Vec::new();
+    let status_as_strings = live_status.expect("Didn't find any data in is_live_status field.");
+    for status in status_as_strings {
+        let _true_str = String::from("true");
+        let _false_str = String::from("false");
+        let bool_output: bool = status.trim().parse().unwrap();
+        out_status.push(bool_output);
+    }
+    out_status
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::testing::temp_config_dir;
+    use super::*;
+
+    // Testing YTCreator struct methods
+    #[test]
+    fn test_yt_creator_size_empty() {
+        let mock_yt_creator = YTCreator::default();
+        assert_eq!(mock_yt_creator.size(), 0);
+    }
+
+    #[test]
+    fn test_yt_creator_size_non_empty() {
+        let mock_yt_creator = YTCreator {
+            names: vec!["Kush".to_string()],
+            avatar_links: vec!["sample".to_string()],
+            descriptions: vec!["Dev".to_string()],
+            is_live_status: vec!["true".to_string()],
+            subscribers: vec!["200".to_string()],
+            previous_subscribers: vec!["150".to_string()],
+            last_live: vec!["".to_string()],
+            custom_order: vec!["0".to_string()],
+            tags: vec!["rust".to_string()],
+            channel_ids: vec!["UC123".to_string()],
+            viewers: vec!["345".to_string()],
+            scheduled_start: vec!["".to_string()],
+        };
+        assert_eq!(mock_yt_creator.size(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not all fields have equal length")]
+    fn test_yt_creator_size_invalid() {
+        YTCreator {
+            names: vec!["Kush".to_string(), "Another".to_string()],
+            avatar_links: vec!["sample".to_string()],
+            descriptions: vec!["Dev".to_string(), "Another".to_string()],
+            is_live_status: vec!["true".to_string()],
+            subscribers: vec!["200".to_string()],
+            ..Default::default()
+        }
+        .size();
+    }
+
+    #[test]
+    fn test_yt_creator_get_field_names() {
+        assert_eq!(
+            YTCreator::field_names(),
+            vec![
+                "names",
+                "avatar_links",
+                "descriptions",
+                "is_live_status",
+                "subscribers",
+                "previous_subscribers",
+                "last_live",
+                "custom_order",
+                "tags",
+                "channel_ids",
+                "viewers",
+                "scheduled_start"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yt_creator_get_field() {
+        let mock_yt_creator = get_json_data(Some("test_assets/sample_data.json"));
+        let output_names = mock_yt_creator.get_field("names");
+        assert!(output_names.is_some());
+        // Testing for just one field is enough IMO
+        assert_eq!(output_names.unwrap(), &vec!["Kush", "Kushashwa"]);
+    }
+
+    #[test]
+    fn test_yt_creator_get_field_invalid() {
+        let mock_yt_creator = get_json_data(Some("test_assets/sample_data.json"));
+        assert!(mock_yt_creator.get_field("doesn't_exist").is_none());
+    }
+
+    #[test]
+    fn test_get_json_data_valid_file() {
+        let expected_output: YTCreator = YTCreator {
+            names: ["Kush", "Kushashwa"].iter().map(|&s|s.into()).collect(),
+            avatar_links: ["https://avatars.githubusercontent.com/u/19997320?v=4", "https://media-exp1.licdn.com/dms/image/C4D03AQGiAbH1TT3fNA/profile-displayphoto-shrink_800_800/0/1642226109876?e=2147483647&v=beta&t=fcJojobq-NZv0oNX_WW9RrCsYsoTqz0TSYMcC6zOGco"].iter().map(|&s|s.into()).collect(),
+            descriptions: ["Developer", "Developer"].iter().map(|&s|s.into()).collect(),
+            is_live_status: ["true", "false"].iter().map(|&s|s.into()).collect(),
+            subscribers: ["100", "200"].iter().map(|&s|s.into()).collect(),
+            previous_subscribers: ["100", "200"].iter().map(|&s|s.into()).collect(),
+            last_live: ["", ""].iter().map(|&s|s.into()).collect(),
+            custom_order: ["0", "1"].iter().map(|&s|s.into()).collect(),
+            tags: ["", ""].iter().map(|&s|s.into()).collect(),
+            channel_ids: ["", ""].iter().map(|&s|s.into()).collect(),
+            viewers: ["", ""].iter().map(|&s|s.into()).collect(),
+            scheduled_start: ["", ""].iter().map(|&s|s.into()).collect()
+        };
+        assert_eq!(
+            get_json_data(Some("test_assets/sample_data.json")),
+            expected_output
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "No such file or directory")]
+    fn test_get_json_data_invalid_file() {
+        get_json_data(Some("invalid_files.json"));
+    }
+
+    #[test]
+    fn test_read_json_uneven_fields() {
+        let path = temp_config_dir("uneven").join("uneven.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "names": ["Kush", "Tom"],
+                "avatar_links": ["", ""],
+                "descriptions": ["", ""],
+                "subscribers": ["10", "20"],
+                "is_live_status": ["true", "false"],
+                "channel_ids": ["UC1"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            read_json(&path.to_string_lossy()).unwrap_err().to_string(),
+            "channel_ids has 1 items for 2 creators"
+        );
+    }
+
+    #[test]
+    fn test_get_live_status_valid() {
+        let sample_data_yt_creator: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
+        assert_eq!(
+            get_live_status(Some(&sample_data_yt_creator.is_live_status)),
+            vec![true, false]
+        );
+    }
+
+    #[test]
+    fn test_get_live_status_empty() {
+        let sample_data_yt_creator: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
+        assert_eq!(
+            get_live_status(Some(&sample_data_yt_creator.is_live_status)).len(),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Didn't find any data in is_live_status field.")]
+    fn test_get_live_status_invalid() {
+        get_live_status(None);
+    }
+
+    #[test]
+    fn test_yt_creator_fill_missing_fields_keeps_existing_data() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sort_data.json"));
+        assert_eq!(
+            yt_creator_mock.previous_subscribers,
+            ["250", "100", "290", "100"]
+        );
+        assert_eq!(yt_creator_mock.custom_order, ["2", "3", "0", "1"]);
+    }
+
+    #[test]
+    fn test_yt_creator_channel_url() {
+        let mut yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sort_data.json"));
+        assert_eq!(
+            yt_creator_mock.channel_url(0),
+            "https://www.youtube.com/results?search_query=%C3%89mile"
+        );
+        yt_creator_mock.channel_ids[0] = "UCabc".to_string();
+        assert_eq!(
+            yt_creator_mock.channel_url(0),
+            "https://www.youtube.com/channel/UCabc"
+        );
+    }
+
+    #[test]
+    fn test_yt_creator_tags() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sort_data.json"));
+        assert_eq!(yt_creator_mock.tags_of(2), ["rust", "gaming"]);
+        assert!(yt_creator_mock.tags_of(10).is_empty());
+        assert_eq!(
+            yt_creator_mock.all_tags(),
+            ["gaming", "music", "rust", "streaming"]
+        );
+    }
+
+    #[test]
+    fn test_yt_creator_group_by_tag() {
+        let mut yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sort_data.json"));
+        yt_creator_mock.tags[3] = "".to_string();
+        // Keeps the order of the given indices inside every group
+        assert_eq!(
+            yt_creator_mock.group_by_tag(&[3, 2, 1, 0]),
+            vec![
+                ("gaming".to_string(), vec![2, 1]),
+                ("rust".to_string(), vec![2, 0]),
+                ("streaming".to_string(), vec![1]),
+                ("".to_string(), vec![3]),
+            ]
+        );
+        // Only the given creators are grouped, empty groups are dropped
+        assert_eq!(
+            yt_creator_mock.group_by_tag(&[0]),
+            vec![("rust".to_string(), vec![0])]
+        );
+        assert_eq!(
+            yt_creator_mock.tag_sections_order(&[3, 2, 1, 0], &["rust".to_string()]),
+            vec![2, 1, 1, 3]
+        );
+    }
+
+    #[test]
+    fn test_yt_creator_upcoming() {
+        let mut yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sort_data.json"));
+        let now = 1_700_000_000;
+        // Émile in 2 hours, alice is live already, Bob an hour ago, Zoë never
+        yt_creator_mock.scheduled_start = [now + 7_200, now + 60, now - 3_600]
+            .iter()
+            .map(u64::to_string)
+            .chain([String::new()])
+            .collect();
+        assert_eq!(
+            yt_creator_mock.scheduled_start_of(0, now),
+            Some(now + 7_200)
+        );
+        assert_eq!(yt_creator_mock.scheduled_start_of(1, now), None);
+        assert_eq!(yt_creator_mock.scheduled_start_of(2, now), None);
+        assert_eq!(yt_creator_mock.upcoming(&[0, 1, 2, 3], now), [0]);
+
+        // Still waiting for Bob half an hour late
+        yt_creator_mock.scheduled_start[2] = (now - 1_800).to_string();
+        assert_eq!(yt_creator_mock.upcoming(&[0, 1, 2, 3], now), [2, 0]);
+        assert_eq!(yt_creator_mock.upcoming(&[0], now), [0]);
+    }
+
+    #[test]
+    fn test_yt_creator_sort_by_direction() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
+        let (new_yt_creator_mock, new_indices) = yt_creator_mock
+            .sort_by_direction(
+                AllowedFieldNamesForSorting::Subscribers,
+                SortDirection::Ascending,
+            )
+            .unwrap();
+        assert_eq!(
+            new_yt_creator_mock.subscribers,
+            ["100", "200", "300", "400"]
+        );
+        assert_eq!(new_indices, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_yt_creator_slice_to() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
+        assert_eq!(yt_creator_mock.slice_to(1).size(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough elements to be sliced into")]
+    fn test_yt_creator_slice_to_more_than_existing() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
+        yt_creator_mock.slice_to(3);
+    }
+
+    #[test]
+    fn test_yt_creator_slice_to_on_empty_valid() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
+        assert_eq!(yt_creator_mock.slice_to(0).size(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough elements to be sliced into")]
+    fn test_yt_creator_slice_to_on_empty_invalid() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
+        assert_eq!(yt_creator_mock.slice_to(2).size(), 0);
+    }
+
+    #[test]
+    fn test_yt_creator_set_field() {
+        let mut yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
+        let new_data: Vec<String> = vec![
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        ];
+        yt_creator_mock.set_field("subscribers", new_data);
+        assert_eq!(yt_creator_mock.subscribers, vec!["0", "0", "0", "0"]);
+    }
+
+    #[test]
+    fn test_yt_creator_sort_by_is_live_status() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
+        let sorted_tuple_with_indices =
+            yt_creator_mock.sort_by(AllowedFieldNamesForSorting::IsLiveStatus);
+        let new_yt_creator_mock = sorted_tuple_with_indices.unwrap().0;
+        assert_eq!(
+            new_yt_creator_mock.subscribers,
+            ["300", "100", "400", "200"]
+        );
+    }
+
+    #[test]
+    fn test_yt_creator_sort_by_subscribers() {
+        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
+        let new_yt_creator_mock = yt_creator_mock
+            .sort_by(AllowedFieldNamesForSorting::Subscribers)
+            .unwrap()
+            .0;
+        assert_ne!(
+            new_yt_creator_mock.avatar_links,
+            yt_creator_mock.avatar_links
+        );
+        assert_eq!(
+            new_yt_creator_mock.subscribers,
+            ["400", "300", "200", "100"]
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use super::appearance::{self, ColorScheme};
+use super::config::UserSettings;
+use super::i18n;
+use super::model::{self, CardScheme, ThemeType, YTCreator};
+use super::sources::{self, QuotaUsage};
+use super::status::RefreshStatus;
+use super::themes::{self, CustomTheme};
+use super::views;
+use super::watchlists::{self, Watchlist, WatchlistSortOption, WatchlistsState};
+
+// The data and polling shared by the frontends without a window: the settings and the
+// creators of a watchlist, refreshed from its file and the YouTube Data API like the window
+// does. Sort options and themes picked here are remembered for the window too.
+#[derive(Debug)]
+pub struct Monitor {
+    pub config_dir: PathBuf,
+    pub settings: UserSettings,
+    pub watchlist: Watchlist,
+    pub watchlists_state: WatchlistsState,
+    pub custom_themes: Vec<CustomTheme>,
+    pub color_scheme: ColorScheme,
+    pub sort_option: WatchlistSortOption,
+    // Sorted by sort_option
+    pub json_obj: YTCreator,
+    pub live_status: Vec<bool>,
+    pub refresh_status: RefreshStatus,
+    // What went wrong without keeping it from opening, e.g. a theme file which couldn't be
+    // read, for the binaries to print
+    pub warnings: Vec<String>,
+}
+
+impl Monitor {
+    // The watchlist with the given name, the one shown last in the window otherwise. Nothing
+    // is read from it before refresh.
+    pub fn open(
+        config_dir: PathBuf,
+        watchlist_name: Option<&str>,
+    ) -> Result<Monitor, Box<dyn Error>> {
+        let (settings, settings_error) = UserSettings::load(&config_dir);
+        i18n::set_language(settings.language);
+        let watchlists = watchlists::discover_watchlists(&config_dir);
+        if let Some(name) = watchlist_name {
+            if !watchlists.iter().any(|watchlist| watchlist.name == name) {
+                return Err(format!("no watchlist named {name}").into());
+            }
+        }
+        let watchlists_state = WatchlistsState::load(&config_dir);
+        let watchlist = watchlists::find_watchlist(
+            &watchlists,
+            watchlist_name.or(watchlists_state.active.as_deref()),
+        );
+        let sort_option = watchlists_state
+            .sort_option_of(&watchlist.name)
+            .unwrap_or(settings.default_sort);
+        let mut warnings: Vec<String> = settings_error.into_iter().collect();
+        let color_scheme = match settings.theme {
+            ThemeType::Auto => appearance::system_color_scheme().unwrap_or_else(|err| {
+                warnings.push(format!("Couldn't read the color scheme: {err}"));
+                ColorScheme::default()
+            }),
+            _ => ColorScheme::default(),
+        };
+        let (custom_themes, skipped_themes) = themes::load_themes(&config_dir);
+        warnings.extend(skipped_themes);
+        Ok(Monitor {
+            custom_themes,
+            refresh_status: RefreshStatus {
+                quota: QuotaUsage::load(&config_dir),
+                uses_api: sources::api_key().is_some(),
+                ..Default::default()
+            },
+            config_dir,
+            settings,
+            watchlist,
+            watchlists_state,
+            color_scheme,
+            sort_option,
+            json_obj: YTCreator::default(),
+            live_status: Vec::new(),
+            warnings,
+        })
+    }
+
+    pub fn refresh(&mut self) -> Result<(), Box<dyn Error>> {
+        let fetched = self.fetch().run();
+        self.apply_fetched(fetched)
+    }
+
+    // The reading and fetching part of a refresh, to run without the monitor
+    pub fn fetch(&self) -> Fetch {
+        Fetch {
+            watchlist_path: self.watchlist.path.clone(),
+            config_dir: self.config_dir.clone(),
+            quota: self.refresh_status.quota,
+        }
+    }
+
+    // Errors end up in refresh_status. Fails if the file couldn't be read, the previous data
+    // stays then. A failed API request leaves the file's values.
+    pub fn apply_fetched(&mut self, fetched: Fetched) -> Result<(), Box<dyn Error>> {
+        self.refresh_status.quota = fetched.quota;
+        let json_obj = match fetched.json_obj {
+            Ok(json_obj) => json_obj,
+            Err(err) => {
+                self.refresh_status.failed(err.to_string());
+                return Err(err);
+            }
+        };
+        match fetched.api_result {
+            None | Some(Ok(())) => self.refresh_status.succeeded(fetched.now),
+            Some(Err(err)) => {
+                self.refresh_status.api_errors += 1;
+                self.refresh_status.failed(err.to_string());
+            }
+        }
+        self.refresh_status.last_duration = Some(fetched.duration);
+        self.json_obj = json_obj;
+        self.sort();
+        Ok(())
+    }
+
+    pub fn set_sort_option(&mut self, sort_option: WatchlistSortOption) {
+        self.sort_option = sort_option;
+        self.sort();
+    }
+
+    // Remembers the sort option of the watchlist, like choosing it in the window does
+    pub fn save_sort_option(&mut self) -> Result<(), Box<dyn Error>> {
+        self.watchlists_state
+            .set_sort_option(&self.watchlist.name, self.sort_option);
+        self.watchlists_state.save(&self.config_dir)
+    }
+
+    fn sort(&mut self) {
+        let sorted = self.json_obj.sort_by_direction(
+            self.sort_option.sort_option,
+            self.sort_option.sort_direction,
+        );
+        if let Some((sorted_json_obj, _)) = sorted {
+            self.json_obj = sorted_json_obj;
+        }
+        self.live_status = model::get_live_status(self.json_obj.get_field("is_live_status"));
+    }
+
+    pub fn set_theme(&mut self, theme: ThemeType) {
+        if theme == ThemeType::Auto {
+            // No session bus, no preference
+            self.color_scheme = appearance::system_color_scheme().unwrap_or_default();
+        }
+        self.settings.theme = theme;
+    }
+
+    pub fn theme_choices(&self) -> Vec<ThemeType> {
+        themes::theme_choices(&self.custom_themes)
+    }
+
+    pub fn card_scheme(&self) -> CardScheme {
+        CardScheme::for_theme(&self.settings.theme, &self.custom_themes, self.color_scheme)
+    }
+
+    pub fn polling_interval(&self) -> Duration {
+        Duration::from_secs(self.settings.polling_interval_secs)
+    }
+
+    pub fn live_count(&self) -> usize {
+        self.live_status.iter().filter(|is_live| **is_live).count()
+    }
+}
+
+// What a refresh reads and asks the YouTube Data API, apart from the monitor: a monitor shared
+// between threads stays available meanwhile
+#[derive(Debug)]
+pub struct Fetch {
+    watchlist_path: PathBuf,
+    config_dir: PathBuf,
+    quota: QuotaUsage,
+}
+
+// For Monitor::apply_fetched
+#[derive(Debug)]
+pub struct Fetched {
+    json_obj: Result<YTCreator, Box<dyn Error>>,
+    quota: QuotaUsage,
+    // None without an API key
+    api_result: Option<Result<(), Box<dyn Error>>>,
+    now: u64,
+    duration: Duration,
+}
+
+impl Fetch {
+    pub fn run(mut self) -> Fetched {
+        let started = Instant::now();
+        let now = views::unix_now();
+        let mut json_obj = model::read_json(&self.watchlist_path.to_string_lossy());
+        let api_result = match &mut json_obj {
+            Ok(json_obj) => {
+                sources::update_from_api(json_obj, &mut self.quota, &self.config_dir, now)
+            }
+            Err(_) => None,
+        };
+        Fetched {
+            json_obj,
+            quota: self.quota,
+            api_result,
+            now,
+            duration: started.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::model::{AllowedFieldNamesForSorting, SortDirection};
+    use super::super::testing::temp_config_dir;
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_refresh_sorts_like_the_window() {
+        let config_dir = temp_config_dir("refresh");
+        fs::write(
+            config_dir.join("watchlists").join("friends.json"),
+            r#"{
+                "names": ["Kush", "Tom"],
+                "avatar_links": ["", ""],
+                "descriptions": ["", ""],
+                "subscribers": ["10", "20"],
+                "is_live_status": ["true", "false"]
+            }"#,
+        )
+        .unwrap();
+        let by_name = WatchlistSortOption {
+            sort_option: AllowedFieldNamesForSorting::Name,
+            sort_direction: SortDirection::Descending,
+        };
+        let mut watchlists_state = WatchlistsState::default();
+        watchlists_state.set_sort_option("friends", by_name);
+        watchlists_state.save(&config_dir).unwrap();
+
+        let mut monitor = Monitor::open(config_dir.clone(), Some("friends")).unwrap();
+        monitor.refresh().unwrap();
+        assert_eq!(
+            monitor.json_obj.get_field("names").unwrap(),
+            &["Tom", "Kush"]
+        );
+        assert_eq!(monitor.live_status, [false, true]);
+        assert_eq!(monitor.live_count(), 1);
+        assert!(monitor.refresh_status.last_refreshed.is_some());
+        assert!(monitor.refresh_status.last_duration.is_some());
+
+        monitor.set_sort_option(WatchlistSortOption {
+            sort_option: AllowedFieldNamesForSorting::Subscribers,
+            sort_direction: SortDirection::Ascending,
+        });
+        assert_eq!(
+            monitor.json_obj.get_field("names").unwrap(),
+            &["Kush", "Tom"]
+        );
+
+        // The last data stays when the file goes away
+        fs::remove_file(config_dir.join("watchlists").join("friends.json")).unwrap();
+        assert!(monitor.refresh().is_err());
+        assert_eq!(monitor.json_obj.get_field("names").unwrap().len(), 2);
+        assert!(monitor.refresh_status.last_error.is_some());
+    }
+
+    #[test]
+    fn test_fetch_apart_from_the_monitor() {
+        let config_dir = temp_config_dir("fetch");
+        let path = config_dir.join("watchlists").join("friends.json");
+        let write_names = |names: &str| {
+            let contents = format!(
+                r#"{{"names": {names}, "avatar_links": ["", ""], "descriptions": ["", ""],
+                    "subscribers": ["10", "20"], "is_live_status": ["true", "false"]}}"#
+            );
+            fs::write(&path, contents).unwrap();
+        };
+        write_names(r#"["Kush", "Tom"]"#);
+        let mut monitor = Monitor::open(config_dir, Some("friends")).unwrap();
+        monitor.refresh().unwrap();
+
+        let fetch = monitor.fetch();
+        write_names(r#"["Kush", "Zoë"]"#);
+        // Still the last data while the fetch runs
+        let fetched = fetch.run();
+        assert_eq!(monitor.live_count(), 1);
+        let has_creator = |monitor: &Monitor, name: &str| {
+            monitor
+                .json_obj
+                .get_field("names")
+                .unwrap()
+                .iter()
+                .any(|known| known == name)
+        };
+        assert!(has_creator(&monitor, "Tom"));
+        monitor.apply_fetched(fetched).unwrap();
+        assert!(has_creator(&monitor, "Zoë"));
+        assert!(!has_creator(&monitor, "Tom"));
+
+        fs::remove_file(&path).unwrap();
+        let fetched = monitor.fetch().run();
+        assert!(monitor.apply_fetched(fetched).is_err());
+        assert_eq!(monitor.json_obj.get_field("names").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_open_unknown_watchlist() {
+        let config_dir = temp_config_dir("unknown");
+        assert_eq!(
+            Monitor::open(config_dir, Some("gone"))
+                .unwrap_err()
+                .to_string(),
+            "no watchlist named gone"
+        );
+    }
+}
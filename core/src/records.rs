@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::model::YTCreator;
+use super::monitor::Monitor;
+use super::sorting::{parse_bool, parse_timestamp};
+
+// One creator as the daemon's HTTP API shows it, one object per creator instead of the
+// watchlist file's one array per field
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatorRecord {
+    // The channel id, the name for creators without one
+    pub id: String,
+    pub name: String,
+    pub channel_id: String,
+    pub channel_url: String,
+    pub avatar_link: String,
+    pub description: String,
+    // None if the watchlist doesn't have a number
+    pub subscribers: Option<u64>,
+    pub previous_subscribers: Option<u64>,
+    pub is_live: bool,
+    // Of the current stream, None when not live or hidden by the channel
+    pub viewers: Option<u64>,
+    // Unix timestamp, None if the creator was never seen live
+    pub last_live: Option<u64>,
+    pub custom_order: Option<u64>,
+    pub tags: Vec<String>,
+    // Unix timestamp of the next scheduled stream or premiere
+    pub scheduled_start: Option<u64>,
+}
+
+// In the order of obj
+pub fn creator_records(obj: &YTCreator) -> Vec<CreatorRecord> {
+    let field = |field_name: &str, idx: usize| {
+        obj.get_field(field_name)
+            .and_then(|values| values.get(idx))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    let count = obj.get_field("names").map_or(0, Vec::len);
+    (0..count)
+        .map(|idx| {
+            let channel_id = field("channel_ids", idx);
+            CreatorRecord {
+                id: match channel_id.is_empty() {
+                    true => field("names", idx),
+                    false => channel_id.clone(),
+                },
+                name: field("names", idx),
+                channel_url: obj.channel_url(idx),
+                channel_id,
+                avatar_link: field("avatar_links", idx),
+                description: field("descriptions", idx),
+                subscribers: field("subscribers", idx).parse().ok(),
+                previous_subscribers: field("previous_subscribers", idx).parse().ok(),
+                is_live: parse_bool(&field("is_live_status", idx)),
+                viewers: field("viewers", idx).parse().ok(),
+                last_live: parse_timestamp(&field("last_live", idx)),
+                custom_order: field("custom_order", idx).parse().ok(),
+                tags: obj.tags_of(idx),
+                scheduled_start: parse_timestamp(&field("scheduled_start", idx)),
+            }
+        })
+        .collect()
+}
+
+// Back to the watchlist file's columns, e.g. to show the creators of a daemon like the
+// ones of a local watchlist. Numbers the daemon doesn't know become empty strings.
+pub fn creators_from_records(records: &[CreatorRecord]) -> YTCreator {
+    let column = |value: fn(&CreatorRecord) -> String| -> Vec<String> {
+        records.iter().map(value).collect()
+    };
+    fn number(value: Option<u64>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+    serde_json::from_value(json!({
+        "names": column(|record| record.name.clone()),
+        "avatar_links": column(|record| record.avatar_link.clone()),
+        "descriptions": column(|record| record.description.clone()),
+        "is_live_status": column(|record| record.is_live.to_string()),
+        "subscribers": column(|record| number(record.subscribers)),
+        "previous_subscribers": column(|record| number(record.previous_subscribers)),
+        "last_live": column(|record| number(record.last_live)),
+        "custom_order": column(|record| number(record.custom_order)),
+        "tags": column(|record| record.tags.join(", ")),
+        "channel_ids": column(|record| record.channel_id.clone()),
+        "viewers": column(|record| number(record.viewers)),
+        "scheduled_start": column(|record| number(record.scheduled_start)),
+    }))
+    .expect("the columns are the fields of YTCreator")
+}
+
+// The creators of after whose live status differs from before, as they are in after.
+// Creators only in one of them didn't change.
+pub fn live_changes(before: &[CreatorRecord], after: &[CreatorRecord]) -> Vec<CreatorRecord> {
+    after
+        .iter()
+        .filter(|record| {
+            before
+                .iter()
+                .any(|old| old.id == record.id && old.is_live != record.is_live)
+        })
+        .cloned()
+        .collect()
+}
+
+// How the daemon's watchlist and its refreshes are doing
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorStatus {
+    pub watchlist: String,
+    pub total: usize,
+    pub live: usize,
+    // Unix timestamp of the last successful refresh
+    pub last_refreshed: Option<u64>,
+    pub last_error: Option<String>,
+    // None without a YouTube Data API key
+    pub quota_used_today: Option<u64>,
+}
+
+impl MonitorStatus {
+    pub fn of(monitor: &Monitor, now: u64) -> MonitorStatus {
+        let refresh_status = &monitor.refresh_status;
+        MonitorStatus {
+            watchlist: monitor.watchlist.name.clone(),
+            total: monitor.live_status.len(),
+            live: monitor.live_count(),
+            last_refreshed: refresh_status.last_refreshed,
+            last_error: refresh_status.last_error.clone(),
+            quota_used_today: refresh_status
+                .uses_api
+                .then(|| refresh_status.quota.used_today(now)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn records(json: &str) -> Vec<CreatorRecord> {
+        creator_records(&serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_creator_records() {
+        let records = records(
+            r#"{
+                "names": ["Kush", "Tom"],
+                "subscribers": ["1200", "unknown"],
+                "is_live_status": ["true", "false"],
+                "last_live": ["1700000000", ""],
+                "tags": ["Rust, gaming", ""],
+                "channel_ids": ["UC123", ""]
+            }"#,
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "UC123");
+        assert_eq!(
+            records[0].channel_url,
+            "https://www.youtube.com/channel/UC123"
+        );
+        assert_eq!(records[0].subscribers, Some(1200));
+        assert!(records[0].is_live);
+        assert_eq!(records[0].last_live, Some(1700000000));
+        assert_eq!(records[0].tags, ["rust", "gaming"]);
+        assert_eq!(records[1].id, "Tom");
+        assert_eq!(records[1].subscribers, None);
+        assert_eq!(records[1].last_live, None);
+    }
+
+    #[test]
+    fn test_creators_from_records() {
+        let before = records(
+            r#"{
+                "names": ["Kush", "Tom"],
+                "subscribers": ["1200", ""],
+                "is_live_status": ["true", "false"],
+                "last_live": ["1700000000", ""],
+                "tags": ["rust, gaming", ""],
+                "channel_ids": ["UC123", ""],
+                "viewers": ["345", ""],
+                "scheduled_start": ["", "1700007200"]
+            }"#,
+        );
+        let obj = creators_from_records(&before);
+        assert_eq!(obj.get_field("subscribers").unwrap(), &["1200", ""]);
+        assert_eq!(obj.get_field("tags").unwrap(), &["rust, gaming", ""]);
+        assert_eq!(creator_records(&obj), before);
+    }
+
+    #[test]
+    fn test_live_changes() {
+        let before = records(
+            r#"{"names": ["Kush", "Tom", "Ana"], "is_live_status": ["false", "true", "true"]}"#,
+        );
+        let after = records(
+            r#"{"names": ["Tom", "Kush", "Mia"], "is_live_status": ["false", "false", "true"]}"#,
+        );
+        let changed: Vec<String> = live_changes(&before, &after)
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        assert_eq!(changed, ["Tom"]);
+        assert!(live_changes(&[], &after).is_empty());
+    }
+}
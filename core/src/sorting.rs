@@ -0,0 +1,329 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use super::i18n;
+use super::model::YTCreator;
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AllowedFieldNamesForSorting {
+    #[default]
+    Subscribers,
+    IsLiveStatus,
+    Name,
+    RecentGrowth,
+    LastLive,
+    CustomOrder,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SortDirection {
+    Ascending,
+    #[default]
+    Descending,
+}
+
+impl fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message_id = match self {
+            SortDirection::Ascending => "order-ascending",
+            SortDirection::Descending => "order-descending",
+        };
+        write!(f, "{}", i18n::tr(message_id))
+    }
+}
+
+impl SortDirection {
+    pub fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+// Keys used (in this order) to break ties after the chosen key. They are always applied in
+// their default direction, so flipping the direction never shuffles equal items around.
+const TIEBREAK_KEYS: [AllowedFieldNamesForSorting; 3] = [
+    AllowedFieldNamesForSorting::Subscribers,
+    AllowedFieldNamesForSorting::Name,
+    AllowedFieldNamesForSorting::CustomOrder,
+];
+
+impl fmt::Display for AllowedFieldNamesForSorting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message_id = match self {
+            AllowedFieldNamesForSorting::Subscribers => "sort-subscribers",
+            AllowedFieldNamesForSorting::IsLiveStatus => "sort-is-live-status",
+            AllowedFieldNamesForSorting::Name => "sort-name",
+            AllowedFieldNamesForSorting::RecentGrowth => "sort-recent-growth",
+            AllowedFieldNamesForSorting::LastLive => "sort-last-live",
+            AllowedFieldNamesForSorting::CustomOrder => "sort-custom-order",
+        };
+        write!(f, "{}", i18n::tr(message_id))
+    }
+}
+
+impl AllowedFieldNamesForSorting {
+    pub const ALL: [AllowedFieldNamesForSorting; 6] = [
+        AllowedFieldNamesForSorting::Subscribers,
+        AllowedFieldNamesForSorting::IsLiveStatus,
+        AllowedFieldNamesForSorting::Name,
+        AllowedFieldNamesForSorting::RecentGrowth,
+        AllowedFieldNamesForSorting::LastLive,
+        AllowedFieldNamesForSorting::CustomOrder,
+    ];
+
+    // Names and the manual order read naturally A -> Z / first -> last, everything else
+    // is more useful biggest (or most recent) first.
+    pub fn default_direction(&self) -> SortDirection {
+        match self {
+            AllowedFieldNamesForSorting::Name | AllowedFieldNamesForSorting::CustomOrder => {
+                SortDirection::Ascending
+            }
+            _ => SortDirection::Descending,
+        }
+    }
+
+    // Name on the command line, which doesn't change with the language
+    pub fn key(&self) -> &'static str {
+        match self {
+            AllowedFieldNamesForSorting::Subscribers => "subscribers",
+            AllowedFieldNamesForSorting::IsLiveStatus => "live",
+            AllowedFieldNamesForSorting::Name => "name",
+            AllowedFieldNamesForSorting::RecentGrowth => "recent-growth",
+            AllowedFieldNamesForSorting::LastLive => "last-live",
+            AllowedFieldNamesForSorting::CustomOrder => "custom-order",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<AllowedFieldNamesForSorting> {
+        AllowedFieldNamesForSorting::ALL
+            .into_iter()
+            .find(|sort_option| sort_option.key() == key)
+    }
+
+    // Ascending comparison of the items at idx_a and idx_b for this key.
+    fn compare(&self, obj: &YTCreator, idx_a: usize, idx_b: usize) -> Ordering {
+        match self {
+            AllowedFieldNamesForSorting::Subscribers => {
+                parse_count(&obj.subscribers[idx_a]).cmp(&parse_count(&obj.subscribers[idx_b]))
+            }
+            AllowedFieldNamesForSorting::IsLiveStatus => {
+                parse_bool(&obj.is_live_status[idx_a]).cmp(&parse_bool(&obj.is_live_status[idx_b]))
+            }
+            AllowedFieldNamesForSorting::Name => {
+                compare_names(&obj.names[idx_a], &obj.names[idx_b])
+            }
+            AllowedFieldNamesForSorting::RecentGrowth => {
+                growth(obj, idx_a).cmp(&growth(obj, idx_b))
+            }
+            AllowedFieldNamesForSorting::LastLive => {
+                parse_timestamp(&obj.last_live[idx_a]).cmp(&parse_timestamp(&obj.last_live[idx_b]))
+            }
+            AllowedFieldNamesForSorting::CustomOrder => {
+                parse_count(&obj.custom_order[idx_a]).cmp(&parse_count(&obj.custom_order[idx_b]))
+            }
+        }
+    }
+}
+
+pub fn parse_count(value: &str) -> i64 {
+    value.trim().parse().unwrap_or_default()
+}
+
+pub fn parse_bool(value: &str) -> bool {
+    value.trim().parse().unwrap_or_default()
+}
+
+// Unix timestamp (in seconds), None if the creator was never seen live.
+pub fn parse_timestamp(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+fn growth(obj: &YTCreator, idx: usize) -> i64 {
+    parse_count(&obj.subscribers[idx]) - parse_count(&obj.previous_subscribers[idx])
+}
+
+// Case and accent insensitive key, so that "émile" sorts next to "Emily" instead of after "Zoë".
+pub fn collation_key(name: &str) -> String {
+    name.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn compare_names(name_a: &str, name_b: &str) -> Ordering {
+    collation_key(name_a)
+        .cmp(&collation_key(name_b))
+        .then_with(|| name_a.cmp(name_b))
+}
+
+// Returns the indices of obj's items in sorted order. The sort is stable: items equal on the
+// chosen key and on every tiebreak key keep their current relative order.
+pub fn sorted_indices(
+    obj: &YTCreator,
+    field_name: AllowedFieldNamesForSorting,
+    direction: SortDirection,
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..obj.names.len()).collect();
+    indices.sort_by(|&idx_a, &idx_b| {
+        TIEBREAK_KEYS.iter().filter(|key| **key != field_name).fold(
+            direction.apply(field_name.compare(obj, idx_a, idx_b)),
+            |ordering, key| {
+                ordering.then_with(|| {
+                    key.default_direction()
+                        .apply(key.compare(obj, idx_a, idx_b))
+                })
+            },
+        )
+    });
+    indices
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::model::get_json_data;
+    use super::*;
+
+    fn sorted_names(
+        field_name: AllowedFieldNamesForSorting,
+        direction: SortDirection,
+    ) -> Vec<String> {
+        let obj = get_json_data(Some("test_assets/sort_data.json"));
+        sorted_indices(&obj, field_name, direction)
+            .into_iter()
+            .map(|idx| obj.names[idx].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_by_subscribers() {
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::Subscribers,
+                SortDirection::Descending
+            ),
+            ["Bob", "Émile", "Zoë", "alice"]
+        );
+        // Bob and Émile tie on subscribers, the tiebreak on name doesn't flip with direction
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::Subscribers,
+                SortDirection::Ascending
+            ),
+            ["alice", "Zoë", "Bob", "Émile"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_is_live_status() {
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::IsLiveStatus,
+                SortDirection::Descending
+            ),
+            ["Zoë", "alice", "Bob", "Émile"]
+        );
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::IsLiveStatus,
+                SortDirection::Ascending
+            ),
+            ["Bob", "Émile", "Zoë", "alice"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        assert_eq!(
+            sorted_names(AllowedFieldNamesForSorting::Name, SortDirection::Ascending),
+            ["alice", "Bob", "Émile", "Zoë"]
+        );
+        assert_eq!(
+            sorted_names(AllowedFieldNamesForSorting::Name, SortDirection::Descending),
+            ["Zoë", "Émile", "Bob", "alice"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_recent_growth() {
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::RecentGrowth,
+                SortDirection::Descending
+            ),
+            ["Zoë", "Émile", "Bob", "alice"]
+        );
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::RecentGrowth,
+                SortDirection::Ascending
+            ),
+            ["alice", "Bob", "Émile", "Zoë"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_last_live() {
+        // alice was never live, so she is the "oldest"
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::LastLive,
+                SortDirection::Descending
+            ),
+            ["Zoë", "Bob", "Émile", "alice"]
+        );
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::LastLive,
+                SortDirection::Ascending
+            ),
+            ["alice", "Émile", "Bob", "Zoë"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_custom_order() {
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::CustomOrder,
+                SortDirection::Ascending
+            ),
+            ["Bob", "Zoë", "Émile", "alice"]
+        );
+        assert_eq!(
+            sorted_names(
+                AllowedFieldNamesForSorting::CustomOrder,
+                SortDirection::Descending
+            ),
+            ["alice", "Émile", "Zoë", "Bob"]
+        );
+    }
+
+    #[test]
+    fn test_sort_is_stable_for_full_ties() {
+        let obj = get_json_data(Some("test_assets/more_data.json"));
+        let mut tied = obj.clone();
+        tied.custom_order = vec!["0".to_string(); 4];
+        tied.subscribers = vec!["100".to_string(); 4];
+        // "Kushashwa" appears three times with equal data, they must keep the file order
+        assert_eq!(
+            sorted_indices(
+                &tied,
+                AllowedFieldNamesForSorting::Name,
+                SortDirection::Ascending
+            ),
+            [0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_collation_key() {
+        assert_eq!(collation_key("Émile"), "emile");
+        assert_eq!(collation_key("ZOË"), "zoe");
+    }
+}
@@ -0,0 +1,844 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::model::YTCreator;
+
+const API_URL: &str = "https://www.googleapis.com/youtube/v3";
+// Without a key the watchlist files are the only source of data
+const API_KEY_ENV_VAR: &str = "YT_MONITOR_API_KEY";
+const QUOTA_FILE_NAME: &str = "quota.json";
+const UPCOMING_SEARCH_FILE_NAME: &str = "upcoming_search.json";
+// Units the YouTube Data API grants per project and per day by default
+pub const DAILY_QUOTA: u64 = 10_000;
+// Most ids a single list request accepts
+const MAX_IDS_PER_REQUEST: usize = 50;
+// A stream is among the latest few uploads of a channel
+const UPLOADS_CHECKED: usize = 5;
+// Units of a search.list request, every other request costs 1
+const SEARCH_COST: u64 = 100;
+// Streams scheduled before the latest uploads are searched for this seldom, a watchlist of 12
+// channels spends 2 400 units a day on it
+const UPCOMING_SEARCH_INTERVAL_SECS: u64 = 12 * 3_600;
+
+pub fn api_key() -> Option<String> {
+    std::env::var(API_KEY_ENV_VAR)
+        .ok()
+        .filter(|api_key| !api_key.trim().is_empty())
+}
+
+// The quota resets at midnight Pacific time, daylight saving is ignored
+fn quota_day(now: u64) -> u64 {
+    now.saturating_sub(8 * 3_600) / 86_400
+}
+
+// API units spent today, kept in the config directory so restarting doesn't reset the count
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaUsage {
+    day: u64,
+    used: u64,
+}
+
+impl QuotaUsage {
+    pub fn load(config_dir: &Path) -> QuotaUsage {
+        fs::read_to_string(config_dir.join(QUOTA_FILE_NAME))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(config_dir)?;
+        fs::write(
+            config_dir.join(QUOTA_FILE_NAME),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, units: u64, now: u64) {
+        let day = quota_day(now);
+        if day != self.day {
+            *self = QuotaUsage { day, used: 0 };
+        }
+        self.used += units;
+    }
+
+    pub fn used_today(&self, now: u64) -> u64 {
+        if quota_day(now) == self.day {
+            self.used
+        } else {
+            0
+        }
+    }
+}
+
+// The upcoming streams found by the last searches for them, kept in the config directory so
+// that the polls in between and after a restart still know them. Shared by every watchlist.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpcomingSearch {
+    // By channel id
+    channels: HashMap<String, SearchedChannel>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct SearchedChannel {
+    searched_at: u64,
+    // Of the soonest upcoming stream found
+    scheduled_start: Option<u64>,
+}
+
+impl UpcomingSearch {
+    pub fn load(config_dir: &Path) -> UpcomingSearch {
+        fs::read_to_string(config_dir.join(UPCOMING_SEARCH_FILE_NAME))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(config_dir)?;
+        fs::write(
+            config_dir.join(UPCOMING_SEARCH_FILE_NAME),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    fn is_due(&self, channel_id: &str, now: u64) -> bool {
+        self.channels
+            .get(channel_id)
+            .is_none_or(|channel| now >= channel.searched_at + UPCOMING_SEARCH_INTERVAL_SECS)
+    }
+
+    // Forgets the streams which should have started by now and the channels due for another
+    // search, e.g. of a watchlist which isn't refreshed anymore
+    fn prune(&mut self, now: u64) {
+        self.channels
+            .retain(|_, channel| now < channel.searched_at + UPCOMING_SEARCH_INTERVAL_SECS);
+        for channel in self.channels.values_mut() {
+            channel.scheduled_start = channel.scheduled_start.filter(|start| *start > now);
+        }
+    }
+}
+
+// GET requests to the YouTube Data API, a trait so that tests can answer them
+pub trait ApiClient {
+    fn get(&self, endpoint: &str, params: &[(&str, String)]) -> Result<Value, Box<dyn Error>>;
+}
+
+pub struct HttpClient {
+    api_key: String,
+    client: reqwest::blocking::Client,
+}
+
+impl HttpClient {
+    pub fn new(api_key: String) -> Self {
+        HttpClient {
+            api_key,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl ApiClient for HttpClient {
+    fn get(&self, endpoint: &str, params: &[(&str, String)]) -> Result<Value, Box<dyn Error>> {
+        let url = reqwest::Url::parse_with_params(
+            &format!("{API_URL}/{endpoint}"),
+            params
+                .iter()
+                .map(|(name, value)| (*name, value.as_str()))
+                .chain([("key", self.api_key.as_str())]),
+        )?;
+        read_response(endpoint, self.client.get(url).send()?)
+    }
+}
+
+// Requests on behalf of a signed in account, e.g. for its own subscriptions
+pub struct OAuthClient {
+    access_token: String,
+    api_url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl OAuthClient {
+    pub fn new(access_token: String) -> Self {
+        OAuthClient::with_api_url(access_token, API_URL)
+    }
+
+    // Tests answer from a local server
+    pub fn with_api_url(access_token: String, api_url: &str) -> Self {
+        OAuthClient {
+            access_token,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl ApiClient for OAuthClient {
+    fn get(&self, endpoint: &str, params: &[(&str, String)]) -> Result<Value, Box<dyn Error>> {
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/{endpoint}", self.api_url),
+            params.iter().map(|(name, value)| (*name, value.as_str())),
+        )?;
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(&self.access_token)
+            .send()?;
+        read_response(endpoint, response)
+    }
+}
+
+fn read_response(
+    endpoint: &str,
+    response: reqwest::blocking::Response,
+) -> Result<Value, Box<dyn Error>> {
+    let status = response.status();
+    let body: Value = serde_json::from_str(&response.text()?)?;
+    if !status.is_success() {
+        // e.g. "The request cannot be completed because you have exceeded your quota."
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        return Err(format!("{endpoint}: {status}: {message}").into());
+    }
+    Ok(body)
+}
+
+// Fetches with the key from the environment and keeps the count of the units used in
+// config_dir, failing to save it fails the update too. None without a key, the watchlist file
+// is all there is then.
+pub fn update_from_api(
+    obj: &mut YTCreator,
+    quota: &mut QuotaUsage,
+    config_dir: &Path,
+    now: u64,
+) -> Option<Result<(), Box<dyn Error>>> {
+    let client = HttpClient::new(api_key()?);
+    let mut upcoming_search = UpcomingSearch::load(config_dir);
+    let result = fetch_updates(&client, obj, quota, &mut upcoming_search, now);
+    // Failed requests are counted too
+    let saved = quota
+        .save(config_dir)
+        .map_err(|err| format!("couldn't save the quota usage: {err}").into())
+        .and_then(|()| {
+            upcoming_search
+                .save(config_dir)
+                .map_err(|err| format!("couldn't save the upcoming streams: {err}").into())
+        });
+    Some(result.and(saved))
+}
+
+fn get_counted(
+    client: &dyn ApiClient,
+    quota: &mut QuotaUsage,
+    now: u64,
+    endpoint: &str,
+    params: &[(&str, String)],
+) -> Result<Value, Box<dyn Error>> {
+    // Failed requests are charged too
+    let units = match endpoint {
+        "search" => SEARCH_COST,
+        _ => 1,
+    };
+    quota.record(units, now);
+    client.get(endpoint, params)
+}
+
+fn items(response: &Value) -> &[Value] {
+    response["items"].as_array().map_or(&[], Vec::as_slice)
+}
+
+// What the API answered about a channel
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct ChannelUpdate {
+    subscribers: Option<String>,
+    uploads_playlist: Option<String>,
+    // Start of the current stream
+    live_since: Option<u64>,
+    // End of the latest stream which is over
+    last_stream_end: Option<u64>,
+    // Of the current stream, missing when the channel hides it
+    viewers: Option<String>,
+    // Of the soonest scheduled stream or premiere, among the latest uploads or searched for
+    scheduled_start: Option<u64>,
+}
+
+// Refreshes the subscriber count, live status, last_live, viewers and scheduled_start of every
+// creator with a channel id, the others keep the values of the watchlist file. Costs 1 unit per
+// 50 channels, 1 per channel for its latest uploads and 1 per 50 of those uploads. Streams
+// scheduled before the latest uploads are searched for every UPCOMING_SEARCH_INTERVAL_SECS, for
+// 100 units per channel; until the next search of its channel a cancelled one stays.
+pub fn fetch_updates(
+    client: &dyn ApiClient,
+    obj: &mut YTCreator,
+    quota: &mut QuotaUsage,
+    upcoming_search: &mut UpcomingSearch,
+    now: u64,
+) -> Result<(), Box<dyn Error>> {
+    let channel_ids: Vec<&str> = obj
+        .channel_ids
+        .iter()
+        .map(|channel_id| channel_id.trim())
+        .filter(|channel_id| !channel_id.is_empty())
+        .collect();
+    let mut updates: HashMap<String, ChannelUpdate> = HashMap::new();
+
+    for chunk in channel_ids.chunks(MAX_IDS_PER_REQUEST) {
+        let response = get_counted(
+            client,
+            quota,
+            now,
+            "channels",
+            &[
+                ("part", "statistics,contentDetails".to_string()),
+                ("id", chunk.join(",")),
+                ("maxResults", MAX_IDS_PER_REQUEST.to_string()),
+            ],
+        )?;
+        for item in items(&response) {
+            let Some(channel_id) = item["id"].as_str() else {
+                continue;
+            };
+            updates.insert(
+                channel_id.to_string(),
+                ChannelUpdate {
+                    // Missing when the channel hides it
+                    subscribers: item["statistics"]["subscriberCount"]
+                        .as_str()
+                        .map(str::to_string),
+                    uploads_playlist: item["contentDetails"]["relatedPlaylists"]["uploads"]
+                        .as_str()
+                        .map(str::to_string),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    let mut video_channels: Vec<(String, String)> = Vec::new();
+    for (channel_id, update) in &updates {
+        let Some(uploads_playlist) = &update.uploads_playlist else {
+            continue;
+        };
+        let response = get_counted(
+            client,
+            quota,
+            now,
+            "playlistItems",
+            &[
+                ("part", "contentDetails".to_string()),
+                ("playlistId", uploads_playlist.clone()),
+                ("maxResults", UPLOADS_CHECKED.to_string()),
+            ],
+        )?;
+        video_channels.extend(items(&response).iter().filter_map(|item| {
+            let video_id = item["contentDetails"]["videoId"].as_str()?;
+            Some((video_id.to_string(), channel_id.clone()))
+        }));
+    }
+
+    upcoming_search.prune(now);
+    let searched: Vec<String> = updates
+        .keys()
+        .filter(|channel_id| upcoming_search.is_due(channel_id, now))
+        .cloned()
+        .collect();
+    for channel_id in &searched {
+        let response = get_counted(
+            client,
+            quota,
+            now,
+            "search",
+            &[
+                ("part", "id".to_string()),
+                ("channelId", channel_id.clone()),
+                ("eventType", "upcoming".to_string()),
+                ("type", "video".to_string()),
+                ("maxResults", UPLOADS_CHECKED.to_string()),
+            ],
+        )?;
+        for item in items(&response) {
+            let Some(video_id) = item["id"]["videoId"].as_str() else {
+                continue;
+            };
+            let video_channel = (video_id.to_string(), channel_id.clone());
+            if !video_channels.contains(&video_channel) {
+                video_channels.push(video_channel);
+            }
+        }
+    }
+
+    for chunk in video_channels.chunks(MAX_IDS_PER_REQUEST) {
+        let video_ids: Vec<&str> = chunk
+            .iter()
+            .map(|(video_id, _)| video_id.as_str())
+            .collect();
+        let response = get_counted(
+            client,
+            quota,
+            now,
+            "videos",
+            &[
+                ("part", "snippet,liveStreamingDetails".to_string()),
+                ("id", video_ids.join(",")),
+                ("maxResults", MAX_IDS_PER_REQUEST.to_string()),
+            ],
+        )?;
+        for item in items(&response) {
+            let Some((_, channel_id)) = chunk
+                .iter()
+                .find(|(video_id, _)| item["id"].as_str() == Some(video_id.as_str()))
+            else {
+                continue;
+            };
+            let Some(update) = updates.get_mut(channel_id) else {
+                continue;
+            };
+            let details = &item["liveStreamingDetails"];
+            let started = details["actualStartTime"].as_str().and_then(parse_rfc3339);
+            let ended = details["actualEndTime"].as_str().and_then(parse_rfc3339);
+            let scheduled = details["scheduledStartTime"]
+                .as_str()
+                .and_then(parse_rfc3339);
+            if item["snippet"]["liveBroadcastContent"] == "live" {
+                update.live_since = started.or(Some(now)).max(update.live_since);
+                update.viewers = details["concurrentViewers"].as_str().map(str::to_string);
+            } else if item["snippet"]["liveBroadcastContent"] == "upcoming" {
+                update.scheduled_start = update.scheduled_start.into_iter().chain(scheduled).min();
+            } else if ended.is_some() {
+                update.last_stream_end = ended.max(update.last_stream_end);
+            }
+        }
+    }
+
+    // The other channels keep what was found for them before, whichever watchlist it was for
+    for (channel_id, update) in updates.iter_mut() {
+        if searched.contains(channel_id) {
+            let searched_channel = SearchedChannel {
+                searched_at: now,
+                scheduled_start: update.scheduled_start,
+            };
+            upcoming_search
+                .channels
+                .insert(channel_id.clone(), searched_channel);
+        } else if let Some(searched_channel) = upcoming_search.channels.get(channel_id) {
+            update.scheduled_start = update
+                .scheduled_start
+                .into_iter()
+                .chain(searched_channel.scheduled_start)
+                .min();
+        }
+    }
+
+    for idx in 0..obj.names.len() {
+        let Some(update) = obj
+            .channel_ids
+            .get(idx)
+            .and_then(|channel_id| updates.get(channel_id.trim()))
+        else {
+            continue;
+        };
+        if let Some(subscribers) = &update.subscribers {
+            // e.g. imported without a count, its first one isn't growth
+            if obj.previous_subscribers[idx].trim().is_empty() {
+                obj.previous_subscribers[idx] = subscribers.clone();
+            }
+            obj.subscribers[idx] = subscribers.clone();
+        }
+        obj.is_live_status[idx] = update.live_since.is_some().to_string();
+        obj.viewers[idx] = update.viewers.clone().unwrap_or_default();
+        obj.scheduled_start[idx] = update
+            .scheduled_start
+            .map(|scheduled_start| scheduled_start.to_string())
+            .unwrap_or_default();
+        let known_last_live = obj.last_live[idx].trim().parse::<u64>().ok();
+        // A stream which ended before the one in the watchlist file doesn't replace it
+        let last_live = update
+            .live_since
+            .or(update.last_stream_end.max(known_last_live));
+        if let Some(last_live) = last_live {
+            obj.last_live[idx] = last_live.to_string();
+        }
+    }
+    Ok(())
+}
+
+// "2024-02-29T12:34:56Z" (or with fractional seconds) to a Unix timestamp, the API always
+// answers in UTC
+fn parse_rfc3339(value: &str) -> Option<u64> {
+    let (date, time) = value.trim_end_matches('Z').split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(str::parse::<u64>);
+    let (year, month, day) = (
+        date_parts.next()?.ok()?,
+        date_parts.next()?.ok()?,
+        date_parts.next()?.ok()?,
+    );
+    let time = time.split('.').next()?;
+    let mut time_parts = time.splitn(3, ':').map(str::parse::<u64>);
+    let (hours, minutes, secs) = (
+        time_parts.next()?.ok()?,
+        time_parts.next()?.ok()?,
+        time_parts.next()?.ok()?,
+    );
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * 86_400 + hours * 3_600 + minutes * 60 + secs)
+}
+
+// Days since 1970-01-01 of the given date, the inverse of views::civil_from_days
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let (era, year_of_era) = (year / 400, year % 400);
+    // Months counted from March, so that the leap day comes last
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    (era * 146_097 + day_of_era).saturating_sub(719_468)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::model::get_json_data;
+    use super::*;
+    use serde_json::json;
+    use std::cell::RefCell;
+
+    // Answers with canned responses, by endpoint and playlist, channel or channels asked for,
+    // and remembers the requests
+    struct MockClient {
+        responses: HashMap<String, Value>,
+        requests: RefCell<Vec<String>>,
+    }
+
+    impl ApiClient for MockClient {
+        fn get(&self, endpoint: &str, params: &[(&str, String)]) -> Result<Value, Box<dyn Error>> {
+            let request = match params.iter().find(|(name, _)| {
+                *name == "playlistId"
+                    || *name == "channelId"
+                    || endpoint == "channels" && *name == "id"
+            }) {
+                Some((_, id)) => format!("{endpoint}/{id}"),
+                None => endpoint.to_string(),
+            };
+            self.requests.borrow_mut().push(request.clone());
+            self.responses
+                .get(&request)
+                .cloned()
+                .ok_or_else(|| format!("{endpoint}: 403 Forbidden: quota exceeded").into())
+        }
+    }
+
+    fn mock_client() -> MockClient {
+        let alice = json!({
+            "id": "UCalice",
+            "statistics": {"subscriberCount": "1500"},
+            "contentDetails": {"relatedPlaylists": {"uploads": "UUalice"}}
+        });
+        let bob = json!({
+            "id": "UCbob",
+            "statistics": {"hiddenSubscriberCount": true},
+            "contentDetails": {"relatedPlaylists": {"uploads": "UUbob"}}
+        });
+        let responses = [
+            (
+                "channels/UCemile,UCalice,UCbob",
+                json!({"items": [alice, bob]}),
+            ),
+            ("channels/UCalice", json!({"items": [alice]})),
+            ("channels/UCbob", json!({"items": [bob]})),
+            (
+                "playlistItems/UUalice",
+                json!({"items": [{"contentDetails": {"videoId": "stream"}}]}),
+            ),
+            (
+                "playlistItems/UUbob",
+                json!({"items": [
+                    {"contentDetails": {"videoId": "premiere"}},
+                    {"contentDetails": {"videoId": "old-stream"}},
+                    {"contentDetails": {"videoId": "video"}}
+                ]}),
+            ),
+            // Scheduled long before the latest uploads
+            (
+                "search/UCalice",
+                json!({"items": [{"id": {"kind": "youtube#video", "videoId": "announced"}}]}),
+            ),
+            (
+                "search/UCbob",
+                json!({"items": [{"id": {"kind": "youtube#video", "videoId": "premiere"}}]}),
+            ),
+            (
+                "videos",
+                json!({"items": [
+                    {
+                        "id": "stream",
+                        "snippet": {"liveBroadcastContent": "live"},
+                        "liveStreamingDetails": {
+                            "actualStartTime": "2023-11-14T22:13:20Z",
+                            "concurrentViewers": "345"
+                        }
+                    },
+                    {
+                        "id": "old-stream",
+                        "snippet": {"liveBroadcastContent": "none"},
+                        "liveStreamingDetails": {
+                            "actualStartTime": "2023-11-01T10:00:00Z",
+                            "actualEndTime": "2023-11-01T12:00:00.5Z"
+                        }
+                    },
+                    {"id": "video", "snippet": {"liveBroadcastContent": "none"}},
+                    {
+                        "id": "premiere",
+                        "snippet": {"liveBroadcastContent": "upcoming"},
+                        "liveStreamingDetails": {"scheduledStartTime": "2023-11-15T00:13:20Z"}
+                    },
+                    {
+                        "id": "announced",
+                        "snippet": {"liveBroadcastContent": "upcoming"},
+                        "liveStreamingDetails": {"scheduledStartTime": "2023-11-20T18:00:00Z"}
+                    }
+                ]}),
+            ),
+        ];
+        MockClient {
+            responses: responses
+                .into_iter()
+                .map(|(request, response)| (request.to_string(), response))
+                .collect(),
+            requests: RefCell::new(Vec::new()),
+        }
+    }
+
+    // Émile, alice, Bob, Zoë; the API doesn't know Émile's channel and Zoë has no id
+    fn creators() -> YTCreator {
+        let mut obj = get_json_data(Some("test_assets/sort_data.json"));
+        obj.channel_ids = ["UCemile", "UCalice", "UCbob", ""]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        obj
+    }
+
+    // Not due for another search
+    fn searched_at(now: u64) -> UpcomingSearch {
+        let searched_channel = SearchedChannel {
+            searched_at: now,
+            scheduled_start: None,
+        };
+        UpcomingSearch {
+            channels: ["UCalice", "UCbob"]
+                .into_iter()
+                .map(|channel_id| (channel_id.to_string(), searched_channel))
+                .collect(),
+        }
+    }
+
+    // Only the creator of the given channel keeps its id
+    fn creators_of(channel_id: &str) -> YTCreator {
+        let mut obj = creators();
+        for known_channel_id in obj.channel_ids.iter_mut() {
+            if known_channel_id != channel_id {
+                known_channel_id.clear();
+            }
+        }
+        obj
+    }
+
+    #[test]
+    fn test_fetch_updates() {
+        let mut obj = creators();
+        obj.previous_subscribers[1] = String::new();
+        let before = obj.clone();
+        let client = mock_client();
+        let mut quota = QuotaUsage::default();
+        let now = 1_700_000_000;
+        fetch_updates(&client, &mut obj, &mut quota, &mut searched_at(now), now).unwrap();
+
+        assert_eq!(obj.subscribers[1], "1500");
+        assert_eq!(obj.previous_subscribers[1], "1500");
+        assert_eq!(obj.previous_subscribers[0], before.previous_subscribers[0]);
+        assert_eq!(obj.is_live_status[1], "true");
+        assert_eq!(obj.last_live[1], "1700000000");
+        assert_eq!(obj.viewers[1], "345");
+        // Hidden subscriber count: the file's value stays
+        assert_eq!(obj.subscribers[2], before.subscribers[2]);
+        assert_eq!(obj.is_live_status[2], "false");
+        assert_eq!(obj.last_live[2], "1698840000");
+        assert_eq!(obj.viewers[2], "");
+        // In 2 hours
+        assert_eq!(obj.scheduled_start[2], "1700007200");
+        assert_eq!(obj.scheduled_start[1], "");
+        assert_eq!(obj.subscribers[0], before.subscribers[0]);
+        assert_eq!(obj.is_live_status[3], before.is_live_status[3]);
+        // 1 channels request, 1 playlistItems request per channel, 1 videos request
+        assert_eq!(client.requests.borrow().len(), 4);
+        assert_eq!(quota.used_today(now), 4);
+    }
+
+    #[test]
+    fn test_upcoming_search() {
+        let mut obj = creators();
+        let client = mock_client();
+        let mut quota = QuotaUsage::default();
+        let mut upcoming_search = UpcomingSearch::default();
+        let now = 1_700_000_000;
+        fetch_updates(&client, &mut obj, &mut quota, &mut upcoming_search, now).unwrap();
+        // Alice's isn't among her latest uploads, Bob's premiere is in both
+        assert_eq!(obj.scheduled_start[1], "1700503200");
+        assert_eq!(obj.scheduled_start[2], "1700007200");
+        // 2 searches for 100 units each, their videos asked for with the uploads
+        assert_eq!(client.requests.borrow().len(), 6);
+        assert_eq!(quota.used_today(now), 204);
+        assert_eq!(upcoming_search.channels["UCalice"].searched_at, now);
+
+        // Kept until the next search
+        let mut obj = creators();
+        fetch_updates(
+            &client,
+            &mut obj,
+            &mut quota,
+            &mut upcoming_search,
+            now + 60,
+        )
+        .unwrap();
+        assert_eq!(obj.scheduled_start[1], "1700503200");
+        assert_eq!(client.requests.borrow().len(), 10);
+        assert_eq!(upcoming_search.channels["UCalice"].searched_at, now);
+        fetch_updates(
+            &client,
+            &mut obj,
+            &mut quota,
+            &mut upcoming_search,
+            now + UPCOMING_SEARCH_INTERVAL_SECS,
+        )
+        .unwrap();
+        assert_eq!(client.requests.borrow().len(), 16);
+    }
+
+    #[test]
+    fn test_upcoming_search_of_two_watchlists() {
+        let client = mock_client();
+        let mut quota = QuotaUsage::default();
+        let mut upcoming_search = UpcomingSearch::default();
+        let now = 1_700_000_000;
+        let mut alice_watchlist = creators_of("UCalice");
+        fetch_updates(
+            &client,
+            &mut alice_watchlist,
+            &mut quota,
+            &mut upcoming_search,
+            now,
+        )
+        .unwrap();
+        assert_eq!(alice_watchlist.scheduled_start[1], "1700503200");
+        // Bob hasn't been searched yet
+        let mut bob_watchlist = creators_of("UCbob");
+        fetch_updates(
+            &client,
+            &mut bob_watchlist,
+            &mut quota,
+            &mut upcoming_search,
+            now + 60,
+        )
+        .unwrap();
+        assert_eq!(bob_watchlist.scheduled_start[2], "1700007200");
+        assert_eq!(client.requests.borrow().len(), 8);
+
+        // Alice's stays without another search
+        let mut alice_watchlist = creators_of("UCalice");
+        fetch_updates(
+            &client,
+            &mut alice_watchlist,
+            &mut quota,
+            &mut upcoming_search,
+            now + 120,
+        )
+        .unwrap();
+        assert_eq!(alice_watchlist.scheduled_start[1], "1700503200");
+        assert_eq!(client.requests.borrow().len(), 11);
+        assert_eq!(upcoming_search.channels["UCbob"].searched_at, now + 60);
+    }
+
+    #[test]
+    fn test_upcoming_search_forgets_started_streams() {
+        let mut upcoming_search = searched_at(1_700_000_000);
+        let alice = upcoming_search.channels.get_mut("UCalice").unwrap();
+        alice.scheduled_start = Some(1_700_000_600);
+        upcoming_search.prune(1_700_000_600);
+        assert_eq!(upcoming_search.channels["UCalice"].scheduled_start, None);
+        // Searched again on the next refresh
+        upcoming_search.prune(1_700_000_000 + UPCOMING_SEARCH_INTERVAL_SECS);
+        assert!(upcoming_search.channels.is_empty());
+    }
+
+    #[test]
+    fn test_older_streams_dont_replace_last_live() {
+        let mut obj = creators();
+        obj.last_live[2] = "1700000000".to_string();
+        let mut quota = QuotaUsage::default();
+        let now = 1_700_000_000;
+        fetch_updates(
+            &mock_client(),
+            &mut obj,
+            &mut quota,
+            &mut searched_at(now),
+            now,
+        )
+        .unwrap();
+        assert_eq!(obj.last_live[2], "1700000000");
+    }
+
+    #[test]
+    fn test_errors_are_reported_and_charged() {
+        let mut client = mock_client();
+        client.responses.remove("playlistItems/UUbob");
+        let mut quota = QuotaUsage::default();
+        let now = 1_700_000_000;
+        let err = fetch_updates(
+            &client,
+            &mut creators(),
+            &mut quota,
+            &mut searched_at(now),
+            now,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("quota exceeded"));
+        // The failed request included
+        assert!(quota.used_today(now) >= 2);
+    }
+
+    #[test]
+    fn test_quota_resets_every_day() {
+        let mut quota = QuotaUsage::default();
+        // 2023-11-14 22:13 UTC, 14:13 in California
+        let now = 1_700_000_000;
+        quota.record(3, now);
+        quota.record(2, now + 3_600);
+        assert_eq!(quota.used_today(now + 3_600), 5);
+        // Midnight in California
+        let next_day = now + 10 * 3_600;
+        assert_eq!(quota.used_today(next_day), 0);
+        quota.record(1, next_day);
+        assert_eq!(quota.used_today(next_day), 1);
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_rfc3339("2023-11-14T22:13:20Z"), Some(1_700_000_000));
+        assert_eq!(
+            parse_rfc3339("2024-02-29T12:34:56.789Z"),
+            Some(1_709_210_096)
+        );
+        assert_eq!(parse_rfc3339("yesterday"), None);
+        assert_eq!(parse_rfc3339("2024-13-01T00:00:00Z"), None);
+    }
+}
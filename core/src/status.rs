@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use super::i18n;
+use super::sources::{QuotaUsage, DAILY_QUOTA};
+use super::views;
+
+// How the last refreshes went, summed up in the header and the footer
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RefreshStatus {
+    // Unix timestamp of the last successful refresh
+    pub last_refreshed: Option<u64>,
+    pub last_error: Option<String>,
+    pub quota: QuotaUsage,
+    // The quota only means something when the YouTube Data API is used
+    pub uses_api: bool,
+    // Failed requests to the YouTube Data API since the start
+    pub api_errors: u64,
+    // Of the last refresh which read the watchlist file
+    pub last_duration: Option<Duration>,
+}
+
+impl RefreshStatus {
+    pub fn succeeded(&mut self, now: u64) {
+        self.last_refreshed = Some(now);
+        self.last_error = None;
+    }
+
+    // The data of the last successful refresh stays on screen
+    pub fn failed(&mut self, err: String) {
+        self.last_error = Some(err);
+    }
+
+    // "3 of 12 live, last refreshed 20 seconds ago"
+    pub fn header_summary(&self, live: usize, total: usize, now: u64) -> String {
+        let refreshed = match self.last_refreshed {
+            Some(last_refreshed) => i18n::tr_args(
+                "summary-refreshed",
+                &[("ago", views::format_time_ago(last_refreshed, now).into())],
+            ),
+            None => i18n::tr("summary-not-refreshed"),
+        };
+        i18n::tr_args(
+            "header-summary",
+            &[
+                ("live", live.into()),
+                ("total", total.into()),
+                ("refreshed", refreshed.into()),
+            ],
+        )
+    }
+
+    // "Up to date · API quota: 1,234 / 10,000 units today", with the error of the last
+    // refresh instead of "Up to date" if it failed
+    pub fn footer_status(&self, now: u64) -> String {
+        let status = match &self.last_error {
+            Some(err) => i18n::tr_args("status-error", &[("error", err.as_str().into())]),
+            None => i18n::tr("status-ok"),
+        };
+        if !self.uses_api {
+            return status;
+        }
+        let quota = i18n::tr_args(
+            "status-quota",
+            &[
+                (
+                    "used",
+                    i18n::format_count(self.quota.used_today(now) as i64).into(),
+                ),
+                ("limit", i18n::format_count(DAILY_QUOTA as i64).into()),
+            ],
+        );
+        format!("{status} · {quota}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_header_summary() {
+        let mut refresh_status = RefreshStatus::default();
+        let now = 1_700_000_000;
+        assert_eq!(
+            refresh_status.header_summary(0, 12, now),
+            "0 of 12 live, not refreshed yet"
+        );
+        refresh_status.succeeded(now - 20);
+        assert_eq!(
+            refresh_status.header_summary(3, 12, now),
+            "3 of 12 live, last refreshed 20 seconds ago"
+        );
+    }
+
+    #[test]
+    fn test_footer_status() {
+        let mut refresh_status = RefreshStatus::default();
+        let now = 1_700_000_000;
+        refresh_status.succeeded(now);
+        assert_eq!(refresh_status.footer_status(now), "Up to date");
+        refresh_status.uses_api = true;
+        refresh_status.quota.record(1_234, now);
+        refresh_status.failed("videos: 403 Forbidden: quota exceeded".to_string());
+        assert_eq!(
+            refresh_status.footer_status(now),
+            "Refresh failed: videos: 403 Forbidden: quota exceeded · API quota: 1,234 / 10,000 units today"
+        );
+        // The last good data is still shown
+        assert_eq!(refresh_status.last_refreshed, Some(now));
+    }
+}
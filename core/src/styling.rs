@@ -0,0 +1,403 @@
+use super::appearance::ColorScheme;
+use super::model::ThemeType;
+use super::themes::{CardStyle, CustomTheme, HexColor, ThemePalette};
+
+const BLACK: HexColor = HexColor { r: 0, g: 0, b: 0 };
+const WHITE: HexColor = HexColor {
+    r: 0xff,
+    g: 0xff,
+    b: 0xff,
+};
+
+// From the Okabe-Ito palette, which stays distinguishable with every common kind of color
+// blindness. Live cards never rely on red vs green, and they also get a "LIVE" badge.
+pub const OKABE_ITO_ORANGE: HexColor = HexColor {
+    r: 0xe6,
+    g: 0x9f,
+    b: 0x00,
+};
+pub const OKABE_ITO_BLUE: HexColor = HexColor {
+    r: 0x00,
+    g: 0x72,
+    b: 0xb2,
+};
+pub const OKABE_ITO_YELLOW: HexColor = HexColor {
+    r: 0xf0,
+    g: 0xe4,
+    b: 0x42,
+};
+
+pub const HIGH_CONTRAST_PALETTE: ThemePalette = ThemePalette {
+    background: BLACK,
+    text: WHITE,
+    primary: OKABE_ITO_YELLOW,
+    success: HexColor {
+        r: 0x56,
+        g: 0xb4,
+        b: 0xe9,
+    },
+    danger: OKABE_ITO_ORANGE,
+};
+
+// What a card is drawn with, picked from the theme in use
+#[derive(Debug, Clone, PartialEq)]
+pub enum CardScheme {
+    Light,
+    Dark,
+    HighContrast,
+    Custom(CustomTheme),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CardColors {
+    pub background: HexColor,
+    pub text: HexColor,
+    pub border: HexColor,
+    pub border_width: f32,
+    pub border_radius: f32,
+    pub badge_background: HexColor,
+    pub badge_text: HexColor,
+}
+
+// Where a card is in its animations, the default is a card standing still
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CardAnimation {
+    // From 0 (just went live, still looks like the others) to 1 (looks live)
+    pub go_live: f32,
+    // From 1 (just moved) to 0 (back to its usual border)
+    pub highlight: f32,
+    pub badge_opacity: f32,
+}
+
+impl Default for CardAnimation {
+    fn default() -> Self {
+        CardAnimation {
+            go_live: 1.0,
+            highlight: 0.0,
+            badge_opacity: 1.0,
+        }
+    }
+}
+
+impl CardScheme {
+    pub fn for_theme(
+        theme: &ThemeType,
+        custom_themes: &[CustomTheme],
+        color_scheme: ColorScheme,
+    ) -> CardScheme {
+        match theme {
+            ThemeType::Auto if color_scheme.is_dark() => CardScheme::Dark,
+            ThemeType::Auto | ThemeType::Light => CardScheme::Light,
+            ThemeType::HighContrast => CardScheme::HighContrast,
+            ThemeType::Dark => CardScheme::Dark,
+            // The theme file might have been removed since, see theme_from_type
+            ThemeType::Custom(name) => custom_themes
+                .iter()
+                .find(|custom_theme| &custom_theme.name == name)
+                .map_or(CardScheme::Dark, |custom_theme| {
+                    CardScheme::Custom(custom_theme.clone())
+                }),
+        }
+    }
+
+    pub fn animated_card_colors(&self, is_live: bool, animation: &CardAnimation) -> CardColors {
+        let colors = match is_live && animation.go_live < 1.0 {
+            true => blend(
+                &self.card_colors(false),
+                &self.card_colors(true),
+                animation.go_live,
+            ),
+            false => self.card_colors(is_live),
+        };
+        // A moved card is outlined with the badge color for a moment
+        CardColors {
+            border: mix(colors.border, colors.badge_background, animation.highlight),
+            border_width: colors.border_width + 2.0 * animation.highlight,
+            ..colors
+        }
+    }
+
+    pub fn card_colors(&self, is_live: bool) -> CardColors {
+        match (self, is_live) {
+            (CardScheme::Light, false) => CardColors {
+                background: WHITE,
+                text: BLACK,
+                border: HexColor {
+                    r: 0xd0,
+                    g: 0xd0,
+                    b: 0xd0,
+                },
+                border_width: 1.0,
+                border_radius: 4.0,
+                badge_background: OKABE_ITO_BLUE,
+                badge_text: WHITE,
+            },
+            (CardScheme::Light, true) => CardColors {
+                background: HexColor {
+                    r: 0xe5,
+                    g: 0xf1,
+                    b: 0xf8,
+                },
+                text: BLACK,
+                border: OKABE_ITO_BLUE,
+                border_width: 3.0,
+                border_radius: 4.0,
+                badge_background: OKABE_ITO_BLUE,
+                badge_text: WHITE,
+            },
+            (CardScheme::Dark, false) => CardColors {
+                background: HexColor {
+                    r: 0x20,
+                    g: 0x22,
+                    b: 0x25,
+                },
+                text: WHITE,
+                border: HexColor {
+                    r: 0x40,
+                    g: 0x44,
+                    b: 0x4b,
+                },
+                border_width: 1.0,
+                border_radius: 4.0,
+                badge_background: OKABE_ITO_ORANGE,
+                badge_text: BLACK,
+            },
+            (CardScheme::Dark, true) => CardColors {
+                background: HexColor {
+                    r: 0x33,
+                    g: 0x28,
+                    b: 0x12,
+                },
+                text: WHITE,
+                border: OKABE_ITO_ORANGE,
+                border_width: 3.0,
+                border_radius: 4.0,
+                badge_background: OKABE_ITO_ORANGE,
+                badge_text: BLACK,
+            },
+            // Pure black and white, live cards stand out by a thick yellow border
+            (CardScheme::HighContrast, false) => CardColors {
+                background: BLACK,
+                text: WHITE,
+                border: WHITE,
+                border_width: 2.0,
+                border_radius: 0.0,
+                badge_background: OKABE_ITO_YELLOW,
+                badge_text: BLACK,
+            },
+            (CardScheme::HighContrast, true) => CardColors {
+                background: BLACK,
+                text: WHITE,
+                border: OKABE_ITO_YELLOW,
+                border_width: 5.0,
+                border_radius: 0.0,
+                badge_background: OKABE_ITO_YELLOW,
+                badge_text: BLACK,
+            },
+            (CardScheme::Custom(custom_theme), _) => custom_card_colors(custom_theme, is_live),
+        }
+    }
+
+    pub fn custom_theme(&self) -> Option<&CustomTheme> {
+        match self {
+            CardScheme::Custom(custom_theme) => Some(custom_theme),
+            _ => None,
+        }
+    }
+}
+
+// Whatever the theme file leaves out comes from its palette. A live card without its own
+// border color is outlined with the primary color, so it never looks like the others.
+fn custom_card_colors(custom_theme: &CustomTheme, is_live: bool) -> CardColors {
+    let palette = &custom_theme.palette;
+    let card_style: &CardStyle = match is_live {
+        true => &custom_theme.live_card,
+        false => &custom_theme.card,
+    };
+    let background = card_style.background.unwrap_or(palette.background);
+    let border = match (card_style.border_color, is_live) {
+        (Some(border_color), _) => border_color,
+        (None, true) => palette.primary,
+        (None, false) => background,
+    };
+    let badge_background = custom_theme
+        .live_card
+        .border_color
+        .unwrap_or(palette.primary);
+    CardColors {
+        background,
+        text: card_style.text.unwrap_or(palette.text),
+        border,
+        border_width: match is_live {
+            true => custom_theme.border_width.max(3.0),
+            false => custom_theme.border_width,
+        },
+        border_radius: custom_theme.border_radius,
+        badge_background,
+        badge_text: readable_text_on(badge_background),
+    }
+}
+
+// color_a at 0, color_b at 1 and in between
+pub fn mix(color_a: HexColor, color_b: HexColor, amount: f32) -> HexColor {
+    let amount = amount.clamp(0.0, 1.0);
+    let channel =
+        |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * amount).round() as u8;
+    HexColor {
+        r: channel(color_a.r, color_b.r),
+        g: channel(color_a.g, color_b.g),
+        b: channel(color_a.b, color_b.b),
+    }
+}
+
+// The badge only shows once the card is live, so it's taken from the live colors as is
+fn blend(from: &CardColors, to: &CardColors, amount: f32) -> CardColors {
+    CardColors {
+        background: mix(from.background, to.background, amount),
+        text: mix(from.text, to.text, amount),
+        border: mix(from.border, to.border, amount),
+        border_width: from.border_width + (to.border_width - from.border_width) * amount,
+        ..*to
+    }
+}
+
+// WCAG 2 relative luminance, from 0 (black) to 1 (white)
+pub fn relative_luminance(color: HexColor) -> f64 {
+    let linear = |channel: u8| {
+        let channel = f64::from(channel) / 255.0;
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+// From 1 (same color) to 21 (black on white), WCAG asks at least 4.5 for normal text
+pub fn contrast_ratio(color_a: HexColor, color_b: HexColor) -> f64 {
+    let (luminance_a, luminance_b) = (relative_luminance(color_a), relative_luminance(color_b));
+    (luminance_a.max(luminance_b) + 0.05) / (luminance_a.min(luminance_b) + 0.05)
+}
+
+pub fn readable_text_on(background: HexColor) -> HexColor {
+    if contrast_ratio(BLACK, background) >= contrast_ratio(WHITE, background) {
+        BLACK
+    } else {
+        WHITE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // WCAG AA for text, AAA for the high contrast theme, and 3:1 for the live border
+    const MIN_TEXT_CONTRAST: f64 = 4.5;
+    const MIN_HIGH_CONTRAST_TEXT_CONTRAST: f64 = 7.0;
+    const MIN_BORDER_CONTRAST: f64 = 3.0;
+
+    fn all_schemes() -> [CardScheme; 4] {
+        [
+            CardScheme::Light,
+            CardScheme::Dark,
+            CardScheme::HighContrast,
+            CardScheme::Custom(CustomTheme::bundled()),
+        ]
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert_eq!(contrast_ratio(WHITE, WHITE), 1.0);
+        assert!((contrast_ratio(BLACK, WHITE) - 21.0).abs() < 1e-9);
+        assert_eq!(
+            contrast_ratio(BLACK, OKABE_ITO_BLUE),
+            contrast_ratio(OKABE_ITO_BLUE, BLACK)
+        );
+        assert_eq!(readable_text_on(OKABE_ITO_YELLOW), BLACK);
+        assert_eq!(readable_text_on(OKABE_ITO_BLUE), WHITE);
+    }
+
+    #[test]
+    fn test_text_is_readable_for_every_theme_and_live_status() {
+        for scheme in all_schemes() {
+            let min_contrast = match scheme {
+                CardScheme::HighContrast => MIN_HIGH_CONTRAST_TEXT_CONTRAST,
+                _ => MIN_TEXT_CONTRAST,
+            };
+            for is_live in [false, true] {
+                let colors = scheme.card_colors(is_live);
+                let text_contrast = contrast_ratio(colors.text, colors.background);
+                assert!(
+                    text_contrast >= min_contrast,
+                    "{scheme:?} (live: {is_live}): text contrast {text_contrast:.2}"
+                );
+                let badge_contrast = contrast_ratio(colors.badge_text, colors.badge_background);
+                assert!(
+                    badge_contrast >= min_contrast,
+                    "{scheme:?} (live: {is_live}): badge contrast {badge_contrast:.2}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_live_cards_stand_out() {
+        for scheme in all_schemes() {
+            let (card, live_card) = (scheme.card_colors(false), scheme.card_colors(true));
+            assert_ne!(card.border, live_card.border, "{scheme:?}");
+            assert!(live_card.border_width > card.border_width, "{scheme:?}");
+            let border_contrast = contrast_ratio(live_card.border, live_card.background);
+            assert!(
+                border_contrast >= MIN_BORDER_CONTRAST,
+                "{scheme:?}: live border contrast {border_contrast:.2}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_animated_card_colors() {
+        for scheme in all_schemes() {
+            for is_live in [false, true] {
+                assert_eq!(
+                    scheme.animated_card_colors(is_live, &CardAnimation::default()),
+                    scheme.card_colors(is_live)
+                );
+            }
+            let just_live = CardAnimation {
+                go_live: 0.0,
+                ..CardAnimation::default()
+            };
+            let colors = scheme.animated_card_colors(true, &just_live);
+            assert_eq!(colors.background, scheme.card_colors(false).background);
+            let just_moved = CardAnimation {
+                highlight: 1.0,
+                ..CardAnimation::default()
+            };
+            let colors = scheme.animated_card_colors(false, &just_moved);
+            assert_eq!(colors.border, colors.badge_background);
+        }
+        assert_eq!(
+            mix(BLACK, WHITE, 0.5),
+            HexColor {
+                r: 128,
+                g: 128,
+                b: 128
+            }
+        );
+    }
+
+    #[test]
+    fn test_custom_theme_falls_back_to_its_palette() {
+        let custom_theme = CustomTheme::default();
+        let colors = CardScheme::Custom(custom_theme.clone()).card_colors(true);
+        assert_eq!(colors.background, custom_theme.palette.background);
+        assert_eq!(colors.text, custom_theme.palette.text);
+        assert_eq!(colors.border, custom_theme.palette.primary);
+        assert_eq!(colors.badge_background, custom_theme.palette.primary);
+        assert_eq!(
+            colors.badge_text,
+            readable_text_on(custom_theme.palette.primary)
+        );
+    }
+}
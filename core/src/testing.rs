@@ -0,0 +1,22 @@
+// Fixtures for the tests of this crate and of the binaries built on it (through the "testing"
+// feature)
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static TEMP_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+// A new config directory with empty watchlists and themes directories. The process id keeps
+// concurrent test runs apart, the counter the tests of one run.
+pub fn temp_config_dir(test_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "yt-monitor-test-{test_name}-{}-{}",
+        std::process::id(),
+        TEMP_DIRS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    for sub_dir in ["watchlists", "themes"] {
+        fs::create_dir_all(dir.join(sub_dir)).unwrap();
+    }
+    dir
+}
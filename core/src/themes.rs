@@ -0,0 +1,397 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::i18n;
+use super::model::ThemeType;
+
+const THEMES_DIR_NAME: &str = "themes";
+pub const BUNDLED_THEME_NAME: &str = "Custom";
+// Always available, a theme file with the same name in the config directory replaces it
+const BUNDLED_THEME: &str = include_str!("../themes/custom.toml");
+
+// Colors are written as "#rrggbb" in the theme files
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HexColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let digits = value.trim().trim_start_matches('#');
+        let channel = |idx: usize| {
+            digits
+                .get(idx..idx + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+        };
+        match (digits.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(HexColor { r, g, b }),
+            _ => Err(format!("Invalid color: {value}, expected #rrggbb")),
+        }
+    }
+}
+
+impl From<HexColor> for String {
+    fn from(color: HexColor) -> Self {
+        color.to_string()
+    }
+}
+
+impl fmt::Display for HexColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl fmt::Debug for HexColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThemePalette {
+    pub background: HexColor,
+    pub text: HexColor,
+    pub primary: HexColor,
+    pub success: HexColor,
+    pub danger: HexColor,
+}
+
+// Colors of a creator card, None keeps the card transparent / uses the palette text color
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CardStyle {
+    pub background: Option<HexColor>,
+    pub text: Option<HexColor>,
+    pub border_color: Option<HexColor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomTheme {
+    // Taken from the file name
+    #[serde(skip)]
+    pub name: String,
+    pub palette: ThemePalette,
+    pub card: CardStyle,
+    // Used instead of card for creators who are live
+    pub live_card: CardStyle,
+    pub border_width: f32,
+    pub border_radius: f32,
+    pub font_size: u16,
+}
+
+// Used for whatever a theme file leaves out
+impl Default for CustomTheme {
+    fn default() -> Self {
+        let black = HexColor { r: 0, g: 0, b: 0 };
+        CustomTheme {
+            name: String::new(),
+            palette: ThemePalette {
+                background: HexColor {
+                    r: 255,
+                    g: 255,
+                    b: 255,
+                },
+                text: black,
+                primary: HexColor {
+                    r: 128,
+                    g: 128,
+                    b: 0,
+                },
+                success: HexColor { r: 0, g: 255, b: 0 },
+                danger: HexColor { r: 255, g: 0, b: 0 },
+            },
+            card: CardStyle::default(),
+            live_card: CardStyle::default(),
+            border_width: 2.0,
+            border_radius: 2.0,
+            font_size: 20,
+        }
+    }
+}
+
+impl CustomTheme {
+    pub fn bundled() -> CustomTheme {
+        let mut bundled_theme: CustomTheme =
+            toml::from_str(BUNDLED_THEME).expect("The bundled theme should be valid");
+        bundled_theme.name = BUNDLED_THEME_NAME.to_string();
+        bundled_theme
+    }
+
+    fn from_file(path: &Path) -> Result<CustomTheme, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut custom_theme: CustomTheme = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&contents)?,
+            _ => toml::from_str(&contents)?,
+        };
+        custom_theme.name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(custom_theme)
+    }
+
+    // Writes <config_dir>/themes/<name>.toml, replacing the theme of the same name
+    pub fn save(&self, config_dir: &Path) -> Result<(), Box<dyn Error>> {
+        let themes_dir = config_dir.join(THEMES_DIR_NAME);
+        fs::create_dir_all(&themes_dir)?;
+        let file_name = self.name.replace(['/', '\\'], "_");
+        fs::write(
+            themes_dir.join(format!("{file_name}.toml")),
+            toml::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+}
+
+// The bundled theme followed by every *.toml / *.json theme in the config directory (sorted
+// by name). Files which can't be parsed are skipped, the second list says why.
+pub fn load_themes(config_dir: &Path) -> (Vec<CustomTheme>, Vec<String>) {
+    let mut theme_paths: Vec<_> = fs::read_dir(config_dir.join(THEMES_DIR_NAME))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|ext| ext == "toml" || ext == "json")
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    theme_paths.sort();
+
+    let mut custom_themes = vec![CustomTheme::bundled()];
+    let mut skipped = Vec::new();
+    for path in theme_paths {
+        match CustomTheme::from_file(&path) {
+            Ok(custom_theme) => {
+                custom_themes.retain(|loaded_theme| loaded_theme.name != custom_theme.name);
+                custom_themes.push(custom_theme);
+            }
+            Err(err) => skipped.push(format!("Skipping the theme {}: {err}", path.display())),
+        }
+    }
+    (custom_themes, skipped)
+}
+
+// The built-in themes, then the custom ones
+pub fn theme_choices(custom_themes: &[CustomTheme]) -> Vec<ThemeType> {
+    [
+        ThemeType::Auto,
+        ThemeType::Dark,
+        ThemeType::Light,
+        ThemeType::HighContrast,
+    ]
+    .into_iter()
+    .chain(
+        custom_themes
+            .iter()
+            .map(|custom_theme| ThemeType::Custom(custom_theme.name.clone())),
+    )
+    .collect()
+}
+
+// Every color which can be changed from the theme editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeColorField {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Danger,
+    CardBackground,
+    CardText,
+    CardBorder,
+    LiveBackground,
+    LiveText,
+    LiveBorder,
+}
+
+impl ThemeColorField {
+    pub const ALL: [ThemeColorField; 11] = [
+        ThemeColorField::Background,
+        ThemeColorField::Text,
+        ThemeColorField::Primary,
+        ThemeColorField::Success,
+        ThemeColorField::Danger,
+        ThemeColorField::CardBackground,
+        ThemeColorField::CardText,
+        ThemeColorField::CardBorder,
+        ThemeColorField::LiveBackground,
+        ThemeColorField::LiveText,
+        ThemeColorField::LiveBorder,
+    ];
+
+    pub fn label(&self) -> String {
+        let message_id = match self {
+            ThemeColorField::Background => "color-background",
+            ThemeColorField::Text => "color-text",
+            ThemeColorField::Primary => "color-primary",
+            ThemeColorField::Success => "color-success",
+            ThemeColorField::Danger => "color-danger",
+            ThemeColorField::CardBackground => "color-card-background",
+            ThemeColorField::CardText => "color-card-text",
+            ThemeColorField::CardBorder => "color-card-border",
+            ThemeColorField::LiveBackground => "color-live-background",
+            ThemeColorField::LiveText => "color-live-text",
+            ThemeColorField::LiveBorder => "color-live-border",
+        };
+        i18n::tr(message_id)
+    }
+
+    // Palette colors are required, card colors can be left empty
+    pub fn is_optional(&self) -> bool {
+        !matches!(
+            self,
+            ThemeColorField::Background
+                | ThemeColorField::Text
+                | ThemeColorField::Primary
+                | ThemeColorField::Success
+                | ThemeColorField::Danger
+        )
+    }
+
+    pub fn get(&self, custom_theme: &CustomTheme) -> Option<HexColor> {
+        match self {
+            ThemeColorField::Background => Some(custom_theme.palette.background),
+            ThemeColorField::Text => Some(custom_theme.palette.text),
+            ThemeColorField::Primary => Some(custom_theme.palette.primary),
+            ThemeColorField::Success => Some(custom_theme.palette.success),
+            ThemeColorField::Danger => Some(custom_theme.palette.danger),
+            ThemeColorField::CardBackground => custom_theme.card.background,
+            ThemeColorField::CardText => custom_theme.card.text,
+            ThemeColorField::CardBorder => custom_theme.card.border_color,
+            ThemeColorField::LiveBackground => custom_theme.live_card.background,
+            ThemeColorField::LiveText => custom_theme.live_card.text,
+            ThemeColorField::LiveBorder => custom_theme.live_card.border_color,
+        }
+    }
+
+    // Parses the text typed in the editor, invalid colors (and empty required ones) are
+    // ignored so that the theme doesn't flicker while typing.
+    pub fn set_from_input(&self, custom_theme: &mut CustomTheme, input: &str) {
+        let color = if input.trim().is_empty() && self.is_optional() {
+            None
+        } else {
+            match HexColor::try_from(input.to_string()) {
+                Ok(color) => Some(color),
+                Err(_) => return,
+            }
+        };
+        match (self, color) {
+            (ThemeColorField::Background, Some(color)) => custom_theme.palette.background = color,
+            (ThemeColorField::Text, Some(color)) => custom_theme.palette.text = color,
+            (ThemeColorField::Primary, Some(color)) => custom_theme.palette.primary = color,
+            (ThemeColorField::Success, Some(color)) => custom_theme.palette.success = color,
+            (ThemeColorField::Danger, Some(color)) => custom_theme.palette.danger = color,
+            (ThemeColorField::CardBackground, color) => custom_theme.card.background = color,
+            (ThemeColorField::CardText, color) => custom_theme.card.text = color,
+            (ThemeColorField::CardBorder, color) => custom_theme.card.border_color = color,
+            (ThemeColorField::LiveBackground, color) => custom_theme.live_card.background = color,
+            (ThemeColorField::LiveText, color) => custom_theme.live_card.text = color,
+            (ThemeColorField::LiveBorder, color) => custom_theme.live_card.border_color = color,
+            (_, None) => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::testing::temp_config_dir;
+    use super::*;
+
+    #[test]
+    fn test_hex_color() {
+        let color = HexColor::try_from("#FF8000".to_string()).unwrap();
+        assert_eq!(
+            color,
+            HexColor {
+                r: 255,
+                g: 128,
+                b: 0
+            }
+        );
+        assert_eq!(color.to_string(), "#ff8000");
+        assert!(HexColor::try_from("#ff80".to_string()).is_err());
+        assert!(HexColor::try_from("#gg8000".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_bundled_theme() {
+        let custom_theme = CustomTheme::bundled();
+        assert_eq!(custom_theme.name, BUNDLED_THEME_NAME);
+        assert_eq!(custom_theme.palette.text, HexColor { r: 0, g: 0, b: 0 });
+        assert_eq!(custom_theme.card, CardStyle::default());
+    }
+
+    #[test]
+    fn test_load_themes() {
+        let config_dir = temp_config_dir("themes");
+        let themes_dir = config_dir.join(THEMES_DIR_NAME);
+        fs::write(
+            themes_dir.join("ocean.json"),
+            r##"{"palette": {"background": "#001133", "text": "#ffffff", "primary": "#0088ff",
+                "success": "#00ff88", "danger": "#ff4444"}, "font_size": 16}"##,
+        )
+        .unwrap();
+        fs::write(themes_dir.join("broken.toml"), "palette = 3").unwrap();
+        fs::write(
+            themes_dir.join("Custom.toml"),
+            BUNDLED_THEME.replace("20", "24"),
+        )
+        .unwrap();
+
+        let (custom_themes, skipped) = load_themes(&config_dir);
+        let names: Vec<&str> = custom_themes
+            .iter()
+            .map(|custom_theme| custom_theme.name.as_str())
+            .collect();
+        assert_eq!(names, ["Custom", "ocean"]);
+        // The file in the config directory replaces the bundled theme
+        assert_eq!(custom_themes[0].font_size, 24);
+        assert_eq!(custom_themes[1].font_size, 16);
+        assert_eq!(custom_themes[1].live_card, CardStyle::default());
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].contains("broken.toml"));
+    }
+
+    #[test]
+    fn test_save_theme_round_trip() {
+        let config_dir = temp_config_dir("save-theme");
+        let mut custom_theme = CustomTheme {
+            name: "mine".to_string(),
+            ..Default::default()
+        };
+        ThemeColorField::CardBorder.set_from_input(&mut custom_theme, "#123456");
+        custom_theme.save(&config_dir).unwrap();
+        assert_eq!(load_themes(&config_dir).0[1], custom_theme);
+    }
+
+    #[test]
+    fn test_theme_color_field_set_from_input() {
+        let mut custom_theme = CustomTheme::bundled();
+        let background = custom_theme.palette.background;
+        // Half typed colors are ignored
+        ThemeColorField::Background.set_from_input(&mut custom_theme, "#12");
+        assert_eq!(custom_theme.palette.background, background);
+        // Required colors can't be cleared, optional ones can
+        ThemeColorField::Background.set_from_input(&mut custom_theme, "");
+        assert_eq!(custom_theme.palette.background, background);
+        ThemeColorField::LiveBackground.set_from_input(&mut custom_theme, "");
+        assert_eq!(ThemeColorField::LiveBackground.get(&custom_theme), None);
+        ThemeColorField::Danger.set_from_input(&mut custom_theme, "#010203");
+        assert_eq!(
+            ThemeColorField::Danger.get(&custom_theme),
+            Some(HexColor { r: 1, g: 2, b: 3 })
+        );
+    }
+}
@@ -0,0 +1,494 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::i18n;
+use super::model::YTCreator;
+use super::sorting::{parse_bool, parse_timestamp, AllowedFieldNamesForSorting, SortDirection};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewMode {
+    #[default]
+    Grid,
+    // Smaller avatars and text, more cards per row
+    CompactGrid,
+    // One line per creator, sortable by clicking the column headers
+    List,
+}
+
+impl ViewMode {
+    pub const ALL: [ViewMode; 3] = [ViewMode::Grid, ViewMode::CompactGrid, ViewMode::List];
+
+    pub fn columns(&self, cards_per_row: usize) -> usize {
+        match self {
+            ViewMode::CompactGrid => cards_per_row + 2,
+            _ => cards_per_row,
+        }
+    }
+
+    pub fn avatar_size(&self) -> u16 {
+        match self {
+            ViewMode::Grid => 130,
+            ViewMode::CompactGrid => 64,
+            ViewMode::List => 32,
+        }
+    }
+
+    pub fn card_padding(&self) -> u16 {
+        match self {
+            ViewMode::Grid => 20,
+            ViewMode::CompactGrid => 8,
+            ViewMode::List => 4,
+        }
+    }
+
+    pub fn font_size(&self, font_size: u16) -> u16 {
+        match self {
+            ViewMode::Grid => font_size,
+            _ => font_size * 3 / 4,
+        }
+    }
+
+    // Rows of the main grid stretch to fill the window, the ones in tag sections can't
+    pub fn section_row_height(&self) -> f32 {
+        match self {
+            ViewMode::Grid => 220.0,
+            ViewMode::CompactGrid => 120.0,
+            ViewMode::List => 40.0,
+        }
+    }
+}
+
+impl fmt::Display for ViewMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message_id = match self {
+            ViewMode::Grid => "view-grid",
+            ViewMode::CompactGrid => "view-compact-grid",
+            ViewMode::List => "view-list",
+        };
+        write!(f, "{}", i18n::tr(message_id))
+    }
+}
+
+// Abbreviated: "1.2M" subscribers, "3 days ago", with the exact value on hover. Full:
+// "1,234,567" and "2024-02-29 12:34 UTC".
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayFormat {
+    #[default]
+    Abbreviated,
+    Full,
+}
+
+impl DisplayFormat {
+    pub const ALL: [DisplayFormat; 2] = [DisplayFormat::Abbreviated, DisplayFormat::Full];
+}
+
+impl fmt::Display for DisplayFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message_id = match self {
+            DisplayFormat::Abbreviated => "format-abbreviated",
+            DisplayFormat::Full => "format-full",
+        };
+        write!(f, "{}", i18n::tr(message_id))
+    }
+}
+
+// What the cards of one frame are rendered with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardFormat {
+    pub view_mode: ViewMode,
+    pub display_format: DisplayFormat,
+    // Unix timestamp the relative times are counted from
+    pub now: u64,
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListColumn {
+    Avatar,
+    Name,
+    Subscribers,
+    Live,
+    LastLive,
+}
+
+impl ListColumn {
+    pub const ALL: [ListColumn; 5] = [
+        ListColumn::Avatar,
+        ListColumn::Name,
+        ListColumn::Subscribers,
+        ListColumn::Live,
+        ListColumn::LastLive,
+    ];
+
+    pub fn title(&self) -> String {
+        match self {
+            ListColumn::Avatar => String::new(),
+            ListColumn::Name => i18n::tr("column-name"),
+            ListColumn::Subscribers => i18n::tr("column-subscribers"),
+            ListColumn::Live => i18n::tr("column-live"),
+            ListColumn::LastLive => i18n::tr("column-last-live"),
+        }
+    }
+
+    // None for the columns which can't be sorted by
+    pub fn sort_key(&self) -> Option<AllowedFieldNamesForSorting> {
+        match self {
+            ListColumn::Avatar => None,
+            ListColumn::Name => Some(AllowedFieldNamesForSorting::Name),
+            ListColumn::Subscribers => Some(AllowedFieldNamesForSorting::Subscribers),
+            ListColumn::Live => Some(AllowedFieldNamesForSorting::IsLiveStatus),
+            ListColumn::LastLive => Some(AllowedFieldNamesForSorting::LastLive),
+        }
+    }
+
+    pub fn width_portion(&self) -> u16 {
+        match self {
+            ListColumn::Avatar | ListColumn::Live => 1,
+            ListColumn::Name => 4,
+            ListColumn::Subscribers => 2,
+            ListColumn::LastLive => 3,
+        }
+    }
+
+    // The title, with an arrow if the list is sorted by this column
+    pub fn header(
+        &self,
+        sort_option: AllowedFieldNamesForSorting,
+        sort_direction: SortDirection,
+    ) -> String {
+        match (self.sort_key() == Some(sort_option), sort_direction) {
+            (true, SortDirection::Ascending) => format!("{} ▲", self.title()),
+            (true, SortDirection::Descending) => format!("{} ▼", self.title()),
+            (false, _) => self.title(),
+        }
+    }
+
+    // Text of the cell for the creator at idx, the avatar is an image instead
+    pub fn cell(&self, obj: &YTCreator, idx: usize, format: &CardFormat) -> String {
+        match self {
+            ListColumn::Avatar => String::new(),
+            ListColumn::Name => obj.names[idx].clone(),
+            ListColumn::Subscribers => {
+                format_subscribers(&obj.subscribers[idx], format.display_format)
+            }
+            ListColumn::Live if parse_bool(&obj.is_live_status[idx]) => i18n::tr("live-badge"),
+            ListColumn::Live => String::new(),
+            ListColumn::LastLive => {
+                match (parse_timestamp(&obj.last_live[idx]), format.display_format) {
+                    (Some(timestamp), DisplayFormat::Abbreviated) => {
+                        format_time_ago(timestamp, format.now)
+                    }
+                    _ => format_timestamp(&obj.last_live[idx]),
+                }
+            }
+        }
+    }
+
+    // Exact value of an abbreviated cell, shown on hover
+    pub fn tooltip(&self, obj: &YTCreator, idx: usize, format: &CardFormat) -> Option<String> {
+        match self {
+            ListColumn::Subscribers => {
+                exact_subscribers(&obj.subscribers[idx], format.display_format)
+            }
+            ListColumn::LastLive => exact_last_live(&obj.last_live[idx], format.display_format),
+            _ => None,
+        }
+    }
+}
+
+// Clicking the column the list is already sorted by flips the direction, any other column
+// sorts by it in its natural direction.
+pub fn sort_after_header_click(
+    sort_option: AllowedFieldNamesForSorting,
+    sort_direction: SortDirection,
+    clicked: AllowedFieldNamesForSorting,
+) -> (AllowedFieldNamesForSorting, SortDirection) {
+    match (clicked == sort_option, sort_direction) {
+        (true, SortDirection::Ascending) => (clicked, SortDirection::Descending),
+        (true, SortDirection::Descending) => (clicked, SortDirection::Ascending),
+        (false, _) => (clicked, clicked.default_direction()),
+    }
+}
+
+// Written the way the UI language does it, left as is if it isn't a number
+pub fn format_subscribers(value: &str, display_format: DisplayFormat) -> String {
+    match (value.trim().parse::<i64>(), display_format) {
+        (Ok(count), DisplayFormat::Abbreviated) => i18n::format_compact_count(count),
+        (Ok(count), DisplayFormat::Full) => i18n::format_count(count),
+        (Err(_), _) => value.to_string(),
+    }
+}
+
+// None when the count is already shown in full
+pub fn exact_subscribers(value: &str, display_format: DisplayFormat) -> Option<String> {
+    let count = value.trim().parse::<i64>().ok()?;
+    let exact = i18n::format_count(count);
+    (format_subscribers(value, display_format) != exact).then_some(exact)
+}
+
+// None when the time is already shown as a date, or if the creator was never seen live
+pub fn exact_last_live(value: &str, display_format: DisplayFormat) -> Option<String> {
+    parse_timestamp(value)?;
+    (display_format == DisplayFormat::Abbreviated).then(|| format_timestamp(value))
+}
+
+// The card line about the creator's streams, None if they were never seen live. For a live
+// creator last_live is when the current stream started.
+pub fn format_last_live(value: &str, is_live: bool, format: &CardFormat) -> Option<String> {
+    let timestamp = parse_timestamp(value)?;
+    let (message_id, arg_name, arg) = match (is_live, format.display_format) {
+        (true, DisplayFormat::Abbreviated) => (
+            "card-live-for",
+            "duration",
+            format_duration(format.now.saturating_sub(timestamp)),
+        ),
+        (true, DisplayFormat::Full) => ("card-live-since", "date", format_timestamp(value)),
+        (false, DisplayFormat::Abbreviated) => (
+            "card-last-live-ago",
+            "ago",
+            format_time_ago(timestamp, format.now),
+        ),
+        (false, DisplayFormat::Full) => ("card-last-live-on", "date", format_timestamp(value)),
+    };
+    Some(i18n::tr_args(message_id, &[(arg_name, arg.into())]))
+}
+
+// The card line about the creator's next scheduled stream, see YTCreator::scheduled_start_of
+pub fn format_scheduled_start(scheduled_start: u64, format: &CardFormat) -> String {
+    let secs = scheduled_start.saturating_sub(format.now);
+    match (format.display_format, secs) {
+        (DisplayFormat::Full, _) => i18n::tr_args(
+            "card-starts-on",
+            &[(
+                "date",
+                format_timestamp(&scheduled_start.to_string()).into(),
+            )],
+        ),
+        // Late streams too, until the grace period is over
+        (DisplayFormat::Abbreviated, 0..60) => i18n::tr("card-starting-soon"),
+        (DisplayFormat::Abbreviated, 60..86_400) => i18n::tr_args(
+            "card-starts-in",
+            &[("duration", format_duration(secs).into())],
+        ),
+        (DisplayFormat::Abbreviated, _) => {
+            i18n::tr_args("card-starts-in-days", &[("count", (secs / 86_400).into())])
+        }
+    }
+}
+
+// "1h 12m", "5m"
+pub fn format_duration(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3_600, secs % 3_600 / 60);
+    match hours {
+        0 => i18n::tr_args("duration-minutes", &[("minutes", minutes.into())]),
+        _ => i18n::tr_args(
+            "duration-hours-minutes",
+            &[("hours", hours.into()), ("minutes", minutes.into())],
+        ),
+    }
+}
+
+// "3 days ago". Anything within the last few seconds, or in the future (the clocks of
+// whoever wrote the watchlist might be off), is "just now".
+pub fn format_time_ago(timestamp: u64, now: u64) -> String {
+    let secs = now.saturating_sub(timestamp);
+    let (message_id, count) = match secs {
+        0..10 => return i18n::tr("time-just-now"),
+        10..60 => ("time-seconds-ago", secs),
+        60..3_600 => ("time-minutes-ago", secs / 60),
+        3_600..86_400 => ("time-hours-ago", secs / 3_600),
+        86_400..2_592_000 => ("time-days-ago", secs / 86_400),
+        2_592_000..31_536_000 => ("time-months-ago", secs / 2_592_000),
+        _ => ("time-years-ago", secs / 31_536_000),
+    };
+    i18n::tr_args(message_id, &[("count", count.into())])
+}
+
+// "YYYY-MM-DD HH:MM UTC", "never" for creators who were never seen live
+pub fn format_timestamp(value: &str) -> String {
+    let Some(timestamp) = parse_timestamp(value) else {
+        return i18n::tr("never-live");
+    };
+    let (days, secs_of_day) = (timestamp / 86_400, timestamp % 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60
+    )
+}
+
+// Date of the given day since 1970-01-01, see
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let (era, day_of_era) = (days / 146_097, days % 146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months counted from March, so that the leap day comes last
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::model::get_json_data;
+    use super::*;
+
+    #[test]
+    fn test_compact_grid_fits_more_cards() {
+        assert_eq!(ViewMode::Grid.columns(4), 4);
+        assert_eq!(ViewMode::CompactGrid.columns(4), 6);
+        assert!(ViewMode::CompactGrid.avatar_size() < ViewMode::Grid.avatar_size());
+        assert_eq!(ViewMode::CompactGrid.font_size(20), 15);
+    }
+
+    fn card_format(display_format: DisplayFormat) -> CardFormat {
+        CardFormat {
+            view_mode: ViewMode::Grid,
+            display_format,
+            now: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_list_cells() {
+        let obj = get_json_data(Some("test_assets/sort_data.json"));
+        let row = |idx| {
+            ListColumn::ALL
+                .iter()
+                .map(|column| column.cell(&obj, idx, &card_format(DisplayFormat::Abbreviated)))
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(row(1)[..4], ["", "alice", "100", "LIVE"]);
+        assert_eq!(row(1)[4], "never");
+        assert_eq!(row(2)[3], "");
+    }
+
+    #[test]
+    fn test_header_marks_the_sorted_column() {
+        let header = |column: ListColumn| {
+            column.header(
+                AllowedFieldNamesForSorting::Subscribers,
+                SortDirection::Descending,
+            )
+        };
+        assert_eq!(header(ListColumn::Subscribers), "Subscribers ▼");
+        assert_eq!(header(ListColumn::Name), "Name");
+        assert_eq!(header(ListColumn::Avatar), "");
+    }
+
+    #[test]
+    fn test_header_click_sorts_or_flips() {
+        assert_eq!(
+            sort_after_header_click(
+                AllowedFieldNamesForSorting::Subscribers,
+                SortDirection::Descending,
+                AllowedFieldNamesForSorting::Name
+            ),
+            (AllowedFieldNamesForSorting::Name, SortDirection::Ascending)
+        );
+        assert_eq!(
+            sort_after_header_click(
+                AllowedFieldNamesForSorting::Name,
+                SortDirection::Ascending,
+                AllowedFieldNamesForSorting::Name
+            ),
+            (AllowedFieldNamesForSorting::Name, SortDirection::Descending)
+        );
+    }
+
+    #[test]
+    fn test_abbreviated_subscribers_have_the_exact_count_on_hover() {
+        let abbreviated = DisplayFormat::Abbreviated;
+        assert_eq!(format_subscribers("1234567", abbreviated), "1.2M");
+        assert_eq!(
+            exact_subscribers("1234567", abbreviated).as_deref(),
+            Some("1,234,567")
+        );
+        assert_eq!(
+            format_subscribers("1234567", DisplayFormat::Full),
+            "1,234,567"
+        );
+        assert_eq!(exact_subscribers("1234567", DisplayFormat::Full), None);
+        // Nothing to add when the short form is already exact
+        assert_eq!(exact_subscribers("100", abbreviated), None);
+        assert_eq!(format_subscribers("n/a", abbreviated), "n/a");
+    }
+
+    #[test]
+    fn test_format_time_ago() {
+        let now = 1_700_000_000;
+        assert_eq!(format_time_ago(now - 5, now), "just now");
+        assert_eq!(format_time_ago(now - 30, now), "30 seconds ago");
+        assert_eq!(format_time_ago(now + 30, now), "just now");
+        assert_eq!(format_time_ago(now - 60, now), "1 minute ago");
+        assert_eq!(format_time_ago(now - 3 * 3_600, now), "3 hours ago");
+        assert_eq!(format_time_ago(now - 3 * 86_400 - 10, now), "3 days ago");
+        assert_eq!(format_time_ago(now - 400 * 86_400, now), "1 year ago");
+    }
+
+    #[test]
+    fn test_format_last_live() {
+        let format = card_format(DisplayFormat::Abbreviated);
+        let started = (format.now - 72 * 60 - 5).to_string();
+        assert_eq!(
+            format_last_live(&started, true, &format).as_deref(),
+            Some("Live for 1h 12m")
+        );
+        let three_days_ago = (format.now - 3 * 86_400).to_string();
+        assert_eq!(
+            format_last_live(&three_days_ago, false, &format).as_deref(),
+            Some("Last live 3 days ago")
+        );
+        assert_eq!(
+            format_last_live("1700000000", false, &card_format(DisplayFormat::Full)).as_deref(),
+            Some("Last live 2023-11-14 22:13 UTC")
+        );
+        assert_eq!(format_last_live("", false, &format), None);
+    }
+
+    #[test]
+    fn test_format_scheduled_start() {
+        let format = card_format(DisplayFormat::Abbreviated);
+        assert_eq!(
+            format_scheduled_start(format.now + 2 * 3_600, &format),
+            "Starts in 2h 0m"
+        );
+        assert_eq!(
+            format_scheduled_start(format.now + 3 * 86_400 + 60, &format),
+            "Starts in 3 days"
+        );
+        assert_eq!(
+            format_scheduled_start(format.now - 600, &format),
+            "Starting soon"
+        );
+        assert_eq!(
+            format_scheduled_start(1700007200, &card_format(DisplayFormat::Full)),
+            "Starts 2023-11-15 00:13 UTC"
+        );
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp("0"), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp("1700000000"), "2023-11-14 22:13 UTC");
+        // 2024 is a leap year
+        assert_eq!(format_timestamp("1709210096"), "2024-02-29 12:34 UTC");
+        assert_eq!(format_timestamp(""), "never");
+    }
+}
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::model::{AllowedFieldNamesForSorting, SortDirection, JSON_FILE_PATH};
+
+const WATCHLISTS_DIR_NAME: &str = "watchlists";
+const WATCHLISTS_STATE_FILE_NAME: &str = "watchlists.json";
+pub const DEFAULT_WATCHLIST_NAME: &str = "Default";
+
+// A named list of creators, stored in the same JSON format as list_users.json
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Watchlist {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl fmt::Display for Watchlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchlistSortOption {
+    pub sort_option: AllowedFieldNamesForSorting,
+    pub sort_direction: SortDirection,
+}
+
+// What we remember about the watchlists between sessions: the one last shown and the sort
+// option picked for each of them (by name).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchlistsState {
+    pub active: Option<String>,
+    pub sort_options: HashMap<String, WatchlistSortOption>,
+}
+
+impl WatchlistsState {
+    // A missing or unreadable state file just means we start from scratch
+    pub fn load(config_dir: &Path) -> WatchlistsState {
+        fs::read_to_string(config_dir.join(WATCHLISTS_STATE_FILE_NAME))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(config_dir)?;
+        fs::write(
+            config_dir.join(WATCHLISTS_STATE_FILE_NAME),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn sort_option_of(&self, watchlist_name: &str) -> Option<WatchlistSortOption> {
+        self.sort_options.get(watchlist_name).copied()
+    }
+
+    pub fn set_sort_option(&mut self, watchlist_name: &str, sort_option: WatchlistSortOption) {
+        self.sort_options
+            .insert(watchlist_name.to_string(), sort_option);
+    }
+}
+
+// The default list (list_users.json in the working directory, if there is one) followed by
+// every *.json file in the watchlists directory of config_dir, named after the file.
+pub fn discover_watchlists(config_dir: &Path) -> Vec<Watchlist> {
+    let mut watchlists: Vec<Watchlist> = Vec::new();
+    if Path::new(JSON_FILE_PATH).is_file() {
+        watchlists.push(Watchlist {
+            name: DEFAULT_WATCHLIST_NAME.to_string(),
+            path: PathBuf::from(JSON_FILE_PATH),
+        });
+    }
+
+    let mut stored_watchlists: Vec<Watchlist> = fs::read_dir(config_dir.join(WATCHLISTS_DIR_NAME))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| {
+                    let name = path.file_stem()?.to_string_lossy().to_string();
+                    Some(Watchlist { name, path })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    stored_watchlists.sort_by(|a, b| a.name.cmp(&b.name));
+    watchlists.extend(stored_watchlists);
+    watchlists
+}
+
+// The watchlist with the given name, else the first one, else the default list (whose file
+// might not exist)
+pub fn find_watchlist(watchlists: &[Watchlist], name: Option<&str>) -> Watchlist {
+    watchlists
+        .iter()
+        .find(|watchlist| Some(watchlist.name.as_str()) == name)
+        .or_else(|| watchlists.first())
+        .cloned()
+        .unwrap_or_else(|| Watchlist {
+            name: DEFAULT_WATCHLIST_NAME.to_string(),
+            path: PathBuf::from(JSON_FILE_PATH),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::testing::temp_config_dir;
+    use super::*;
+
+    #[test]
+    fn test_discover_watchlists() {
+        let config_dir = temp_config_dir("discover");
+        for file_name in ["team favourites.json", "competitors.json", "notes.txt"] {
+            fs::write(config_dir.join(WATCHLISTS_DIR_NAME).join(file_name), "{}").unwrap();
+        }
+        let names: Vec<String> = discover_watchlists(&config_dir)
+            .into_iter()
+            .map(|watchlist| watchlist.name)
+            .collect();
+        // The default list comes first when the working directory has one
+        let default_name = Path::new(JSON_FILE_PATH)
+            .is_file()
+            .then_some(DEFAULT_WATCHLIST_NAME);
+        let expected: Vec<&str> = default_name
+            .into_iter()
+            .chain(["competitors", "team favourites"])
+            .collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn test_find_watchlist() {
+        let watchlists = [
+            Watchlist {
+                name: "competitors".to_string(),
+                path: PathBuf::from("competitors.json"),
+            },
+            Watchlist {
+                name: "friends".to_string(),
+                path: PathBuf::from("friends.json"),
+            },
+        ];
+        assert_eq!(find_watchlist(&watchlists, Some("friends")), watchlists[1]);
+        assert_eq!(find_watchlist(&watchlists, Some("gone")), watchlists[0]);
+        assert_eq!(find_watchlist(&[], None).name, DEFAULT_WATCHLIST_NAME);
+    }
+
+    #[test]
+    fn test_watchlists_state_round_trip() {
+        let config_dir = temp_config_dir("state");
+        assert_eq!(
+            WatchlistsState::load(&config_dir),
+            WatchlistsState::default()
+        );
+
+        let mut state = WatchlistsState {
+            active: Some("competitors".to_string()),
+            ..Default::default()
+        };
+        let by_name = WatchlistSortOption {
+            sort_option: AllowedFieldNamesForSorting::Name,
+            sort_direction: SortDirection::Ascending,
+        };
+        state.set_sort_option("competitors", by_name);
+        state.save(&config_dir).unwrap();
+
+        let loaded_state = WatchlistsState::load(&config_dir);
+        assert_eq!(loaded_state, state);
+        assert_eq!(loaded_state.sort_option_of("competitors"), Some(by_name));
+        assert_eq!(loaded_state.sort_option_of("unknown"), None);
+    }
+}
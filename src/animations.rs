@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+use yt_monitor_core::model::CardAnimation;
+
+// About 30 frames per second, only while something is moving
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+const GO_LIVE_DURATION: Duration = Duration::from_millis(800);
+const HIGHLIGHT_DURATION: Duration = Duration::from_millis(600);
+const PULSE_PERIOD: Duration = Duration::from_millis(1600);
+// The badge never fades out completely, it should stay readable
+const MIN_BADGE_OPACITY: f32 = 0.55;
+
+// Cards are tracked by creator name, their position changes with every sort
+#[derive(Debug)]
+pub struct Animations {
+    started: Instant,
+    now: Instant,
+    went_live: HashMap<String, Instant>,
+    moved: HashMap<String, Instant>,
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        let now = Instant::now();
+        Animations {
+            started: now,
+            now,
+            went_live: HashMap::new(),
+            moved: HashMap::new(),
+        }
+    }
+}
+
+// 0 when the animation starts, 1 once it's over
+fn progress(start: Instant, now: Instant, duration: Duration) -> f32 {
+    (now.saturating_duration_since(start).as_secs_f32() / duration.as_secs_f32()).min(1.0)
+}
+
+// Fast at first, slowing down at the end
+fn ease_out(progress: f32) -> f32 {
+    1.0 - (1.0 - progress).powi(3)
+}
+
+impl Animations {
+    // Compares the visible cards (name, is live) before and after a refresh or a sort. Cards
+    // which weren't shown before (new watchlist, filters) are not animated.
+    pub fn note_changes(
+        &mut self,
+        before: &[(String, bool)],
+        after: &[(String, bool)],
+        now: Instant,
+    ) {
+        self.now = now;
+        for (position, (name, is_live)) in after.iter().enumerate() {
+            let Some(old_position) = before.iter().position(|(old_name, _)| old_name == name)
+            else {
+                continue;
+            };
+            if *is_live && !before[old_position].1 {
+                self.went_live.insert(name.clone(), now);
+            }
+            if position != old_position {
+                self.moved.insert(name.clone(), now);
+            }
+        }
+    }
+
+    // Called on every frame, finished animations are dropped
+    pub fn tick(&mut self, now: Instant) {
+        self.now = now;
+        self.went_live
+            .retain(|_, start| progress(*start, now, GO_LIVE_DURATION) < 1.0);
+        self.moved
+            .retain(|_, start| progress(*start, now, HIGHLIGHT_DURATION) < 1.0);
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.went_live.is_empty() || !self.moved.is_empty()
+    }
+
+    pub fn card_animation(&self, name: &str, is_live: bool) -> CardAnimation {
+        let go_live = self.went_live.get(name).map_or(1.0, |start| {
+            ease_out(progress(*start, self.now, GO_LIVE_DURATION))
+        });
+        let highlight = self.moved.get(name).map_or(0.0, |start| {
+            1.0 - ease_out(progress(*start, self.now, HIGHLIGHT_DURATION))
+        });
+        // Every badge pulses in sync, starting fully visible
+        let pulse_phase = self
+            .now
+            .saturating_duration_since(self.started)
+            .as_secs_f32()
+            / PULSE_PERIOD.as_secs_f32();
+        let pulse = 0.5 + 0.5 * (2.0 * PI * pulse_phase).cos();
+        CardAnimation {
+            go_live,
+            highlight,
+            badge_opacity: match is_live {
+                true => MIN_BADGE_OPACITY + (1.0 - MIN_BADGE_OPACITY) * pulse,
+                false => 1.0,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cards(cards: &[(&str, bool)]) -> Vec<(String, bool)> {
+        cards
+            .iter()
+            .map(|(name, is_live)| (name.to_string(), *is_live))
+            .collect()
+    }
+
+    #[test]
+    fn test_go_live_and_reorder_are_noticed() {
+        let mut animations = Animations::default();
+        let now = animations.started;
+        animations.note_changes(
+            &cards(&[("alice", false), ("Bob", false), ("Zoë", true)]),
+            &cards(&[
+                ("Bob", true),
+                ("alice", false),
+                ("Zoë", true),
+                ("Émile", true),
+            ]),
+            now,
+        );
+        assert!(animations.is_running());
+        // Bob just went live (and moved), Émile wasn't shown before so that card isn't animated
+        let bob = animations.card_animation("Bob", true);
+        assert_eq!((bob.go_live, bob.highlight), (0.0, 1.0));
+        let alice = animations.card_animation("alice", false);
+        assert_eq!((alice.go_live, alice.highlight), (1.0, 1.0));
+        let zoe = animations.card_animation("Zoë", true);
+        assert_eq!((zoe.go_live, zoe.highlight), (1.0, 0.0));
+        assert_eq!(animations.card_animation("Émile", true).go_live, 1.0);
+    }
+
+    #[test]
+    fn test_animations_finish() {
+        let mut animations = Animations::default();
+        let now = animations.started;
+        animations.note_changes(&cards(&[("Bob", false)]), &cards(&[("Bob", true)]), now);
+        animations.tick(now + GO_LIVE_DURATION / 2);
+        let halfway = animations.card_animation("Bob", true).go_live;
+        assert!(0.0 < halfway && halfway < 1.0);
+        animations.tick(now + GO_LIVE_DURATION);
+        assert!(!animations.is_running());
+        assert_eq!(
+            animations.card_animation("Bob", true),
+            CardAnimation {
+                badge_opacity: animations.card_animation("Bob", true).badge_opacity,
+                ..CardAnimation::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_only_live_badges_pulse() {
+        let mut animations = Animations::default();
+        let start = animations.started;
+        assert_eq!(animations.card_animation("Bob", true).badge_opacity, 1.0);
+        animations.tick(start + PULSE_PERIOD / 2);
+        let faded = animations.card_animation("Bob", true).badge_opacity;
+        assert!((faded - MIN_BADGE_OPACITY).abs() < 1e-3);
+        assert_eq!(animations.card_animation("alice", false).badge_opacity, 1.0);
+    }
+}
@@ -0,0 +1,595 @@
+use itertools::izip;
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use super::server;
+use super::tui;
+use yt_monitor_core::config;
+use yt_monitor_core::export::{self, ExportColumn, ExportFormat};
+use yt_monitor_core::i18n;
+use yt_monitor_core::model::{self, AllowedFieldNamesForSorting, YTCreator};
+use yt_monitor_core::monitor::Monitor;
+use yt_monitor_core::records;
+use yt_monitor_core::watchlists::WatchlistSortOption;
+
+pub const USAGE: &str = "\
+Usage: yt-monitor [COMMAND]
+
+Opens the window when no command is given.
+
+Commands:
+  status    Print the creators of a watchlist and whether they are live
+  tui       Show the creators in the terminal, refreshed like in the window
+  serve     Serve the creators over HTTP as JSON, refreshed like in the window
+  export    Write the creators of a watchlist to CSV, JSON or a Markdown table
+  help      Print this help
+
+Options of status:
+  --watchlist <NAME>   Watchlist to print, the one shown last in the window by default
+  --sort <FIELD>       subscribers, live, name, recent-growth, last-live or custom-order
+                       (the watchlist's sort option in the window by default)
+  --live-only          Only print the creators who are live
+  --format <FORMAT>    table (default), json or csv
+
+Options of tui:
+  --watchlist <NAME>   Watchlist to show, the one shown last in the window by default
+
+Options of serve:
+  --bind <ADDRESS>     Address to listen on, 127.0.0.1:8080 by default
+  --watchlist <NAME>   Watchlist to serve, the one shown last in the window by default
+  --metrics            Also serve Prometheus metrics at /metrics
+
+Options of export:
+  --watchlist <NAME>   Watchlist to export, the one shown last in the window by default
+  --sort <FIELD>       Same as for status
+  --live-only          Only export the creators who are live
+  --format <FORMAT>    csv (default), json or markdown
+  --columns <LIST>     Comma-separated, out of name, channel-url, subscribers, change, live,
+                       viewers, last-live and tags (name,subscribers,change,live,last-live
+                       by default)
+  --output <FILE>      File to write, the standard output by default
+";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatusOptions {
+    pub watchlist: Option<String>,
+    pub sort_option: Option<AllowedFieldNamesForSorting>,
+    pub live_only: bool,
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub watchlist: Option<String>,
+    pub sort_option: Option<AllowedFieldNamesForSorting>,
+    pub live_only: bool,
+    pub format: ExportFormat,
+    pub columns: Vec<ExportColumn>,
+    // None for the standard output
+    pub output: Option<PathBuf>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            watchlist: None,
+            sort_option: None,
+            live_only: false,
+            format: ExportFormat::default(),
+            columns: ExportColumn::DEFAULT.to_vec(),
+            output: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    // No command, the usual window
+    Gui,
+    Help,
+    Status(StatusOptions),
+    Tui {
+        watchlist: Option<String>,
+    },
+    Serve {
+        bind: String,
+        watchlist: Option<String>,
+        metrics: bool,
+    },
+    Export(ExportOptions),
+}
+
+// The arguments without the program name
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliCommand, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None => Ok(CliCommand::Gui),
+        Some("help" | "--help" | "-h") => Ok(CliCommand::Help),
+        Some("status") => parse_status_args(args).map(CliCommand::Status),
+        Some("tui") => parse_tui_args(args),
+        Some("serve") => parse_serve_args(args),
+        Some("export") => parse_export_args(args).map(CliCommand::Export),
+        Some(command) => Err(format!("Unknown command: {command}")),
+    }
+}
+
+// Values can be given as "--sort name" or "--sort=name"
+fn split_flag(arg: String) -> (String, Option<String>) {
+    match arg.split_once('=') {
+        Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+        None => (arg, None),
+    }
+}
+
+fn flag_value(
+    flag: &str,
+    inline_value: Option<String>,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<String, String> {
+    inline_value
+        .or_else(|| args.next())
+        .ok_or_else(|| format!("Missing value for {flag}"))
+}
+
+fn parse_status_args(mut args: impl Iterator<Item = String>) -> Result<StatusOptions, String> {
+    let mut options = StatusOptions::default();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = split_flag(arg);
+        let mut value = || flag_value(&flag, inline_value.clone(), &mut args);
+        match flag.as_str() {
+            "--watchlist" => options.watchlist = Some(value()?),
+            "--sort" => options.sort_option = Some(parse_sort_option(&value()?)?),
+            "--live-only" => options.live_only = true,
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "table" => OutputFormat::Table,
+                    "json" => OutputFormat::Json,
+                    "csv" => OutputFormat::Csv,
+                    format => return Err(format!("Unknown format: {format}")),
+                }
+            }
+            _ => return Err(format!("Unknown option: {flag}")),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_sort_option(key: &str) -> Result<AllowedFieldNamesForSorting, String> {
+    AllowedFieldNamesForSorting::from_key(key).ok_or_else(|| format!("Unknown sort option: {key}"))
+}
+
+fn parse_export_args(mut args: impl Iterator<Item = String>) -> Result<ExportOptions, String> {
+    let mut options = ExportOptions::default();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = split_flag(arg);
+        let mut value = || flag_value(&flag, inline_value.clone(), &mut args);
+        match flag.as_str() {
+            "--watchlist" => options.watchlist = Some(value()?),
+            "--sort" => options.sort_option = Some(parse_sort_option(&value()?)?),
+            "--live-only" => options.live_only = true,
+            "--format" => {
+                let key = value()?;
+                options.format =
+                    ExportFormat::from_key(&key).ok_or_else(|| format!("Unknown format: {key}"))?;
+            }
+            "--columns" => {
+                options.columns = value()?
+                    .split(',')
+                    .map(|key| {
+                        ExportColumn::from_key(key.trim())
+                            .ok_or_else(|| format!("Unknown column: {}", key.trim()))
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            "--output" => options.output = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("Unknown option: {flag}")),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_tui_args(mut args: impl Iterator<Item = String>) -> Result<CliCommand, String> {
+    let mut watchlist = None;
+    while let Some(arg) = args.next() {
+        match split_flag(arg) {
+            (flag, inline_value) if flag == "--watchlist" => {
+                watchlist = Some(flag_value(&flag, inline_value, &mut args)?);
+            }
+            (flag, _) => return Err(format!("Unknown option: {flag}")),
+        }
+    }
+    Ok(CliCommand::Tui { watchlist })
+}
+
+fn parse_serve_args(mut args: impl Iterator<Item = String>) -> Result<CliCommand, String> {
+    let mut bind = server::DEFAULT_BIND.to_string();
+    let mut watchlist = None;
+    let mut metrics = false;
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = split_flag(arg);
+        match flag.as_str() {
+            "--bind" => bind = flag_value(&flag, inline_value, &mut args)?,
+            "--watchlist" => watchlist = Some(flag_value(&flag, inline_value, &mut args)?),
+            "--metrics" => metrics = true,
+            _ => return Err(format!("Unknown option: {flag}")),
+        }
+    }
+    Ok(CliCommand::Serve {
+        bind,
+        watchlist,
+        metrics,
+    })
+}
+
+// Runs any command but Gui, returns the exit code
+pub fn run(command: CliCommand) -> i32 {
+    let exit_code = run_command(command);
+    // Messages which couldn't be translated, the core library leaves the printing to us
+    for err in i18n::take_errors() {
+        eprintln!("{err}");
+    }
+    exit_code
+}
+
+fn run_command(command: CliCommand) -> i32 {
+    match command {
+        CliCommand::Gui | CliCommand::Help => {
+            print!("{USAGE}");
+            0
+        }
+        CliCommand::Status(options) => match status(&options) {
+            Ok(output) => {
+                print!("{output}");
+                0
+            }
+            Err(err) => {
+                eprintln!("Couldn't get the status of the creators: {err}");
+                1
+            }
+        },
+        CliCommand::Tui { watchlist } => match tui::run(watchlist.as_deref()) {
+            Ok(()) => 0,
+            Err(err) => {
+                eprintln!("Couldn't run the terminal UI: {err}");
+                1
+            }
+        },
+        CliCommand::Serve {
+            bind,
+            watchlist,
+            metrics,
+        } => match server::serve(&bind, watchlist.as_deref(), metrics) {
+            Ok(()) => 0,
+            Err(err) => {
+                eprintln!("Couldn't serve the creators: {err}");
+                1
+            }
+        },
+        CliCommand::Export(options) => match export_creators(&options) {
+            Ok(output) => match &options.output {
+                Some(path) => match fs::write(path, output) {
+                    Ok(()) => 0,
+                    Err(err) => {
+                        eprintln!("Couldn't write the export to {}: {err}", path.display());
+                        1
+                    }
+                },
+                None => {
+                    print!("{output}");
+                    0
+                }
+            },
+            Err(err) => {
+                eprintln!("Couldn't export the creators: {err}");
+                1
+            }
+        },
+    }
+}
+
+// The same data as the window: the watchlist file, brought up to date from the YouTube
+// Data API if a key is set
+pub fn status(options: &StatusOptions) -> Result<String, Box<dyn Error>> {
+    let monitor = refreshed_monitor(options.watchlist.as_deref(), options.sort_option)?;
+    format_statuses(
+        &creator_statuses(&monitor.json_obj, options.live_only),
+        options.format,
+    )
+}
+
+// Sorted the same way as status
+pub fn export_creators(options: &ExportOptions) -> Result<String, Box<dyn Error>> {
+    let monitor = refreshed_monitor(options.watchlist.as_deref(), options.sort_option)?;
+    let creators: Vec<records::CreatorRecord> = records::creator_records(&monitor.json_obj)
+        .into_iter()
+        .filter(|record| record.is_live || !options.live_only)
+        .collect();
+    Ok(export::export(&creators, &options.columns, options.format))
+}
+
+// With the problems that didn't keep it from opening printed
+pub fn open_monitor(watchlist_name: Option<&str>) -> Result<Monitor, Box<dyn Error>> {
+    let monitor = Monitor::open(config::config_dir(), watchlist_name)?;
+    for warning in &monitor.warnings {
+        eprintln!("{warning}");
+    }
+    Ok(monitor)
+}
+
+fn refreshed_monitor(
+    watchlist_name: Option<&str>,
+    sort_option: Option<AllowedFieldNamesForSorting>,
+) -> Result<Monitor, Box<dyn Error>> {
+    let mut monitor = open_monitor(watchlist_name)?;
+    if let Some(sort_option) = sort_option {
+        monitor.set_sort_option(WatchlistSortOption {
+            sort_option,
+            sort_direction: sort_option.default_direction(),
+        });
+    }
+    monitor.refresh()?;
+    if let Some(err) = &monitor.refresh_status.last_error {
+        eprintln!("Couldn't fetch from the YouTube API, using the watchlist file: {err}");
+    }
+    Ok(monitor)
+}
+
+// A line of the output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CreatorStatus {
+    pub name: String,
+    // None if the watchlist doesn't have a number
+    pub subscribers: Option<u64>,
+    pub is_live: bool,
+}
+
+pub fn creator_statuses(json_obj: &YTCreator, live_only: bool) -> Vec<CreatorStatus> {
+    let live_status = model::get_live_status(json_obj.get_field("is_live_status"));
+    izip!(
+        json_obj.get_field("names").into_iter().flatten(),
+        json_obj.get_field("subscribers").into_iter().flatten(),
+        live_status,
+    )
+    .filter(|(_, _, is_live)| *is_live || !live_only)
+    .map(|(name, subscribers, is_live)| CreatorStatus {
+        name: name.clone(),
+        subscribers: subscribers.trim().parse().ok(),
+        is_live,
+    })
+    .collect()
+}
+
+pub fn format_statuses(
+    statuses: &[CreatorStatus],
+    format: OutputFormat,
+) -> Result<String, Box<dyn Error>> {
+    match format {
+        OutputFormat::Table => Ok(format_table(statuses)),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(statuses)? + "\n"),
+        OutputFormat::Csv => Ok(format_csv(statuses)),
+    }
+}
+
+// Aligned columns with the window's headers, subscriber counts right-aligned
+fn format_table(statuses: &[CreatorStatus]) -> String {
+    let header = [
+        i18n::tr("column-name"),
+        i18n::tr("column-subscribers"),
+        i18n::tr("column-live"),
+    ];
+    let rows: Vec<[String; 3]> = statuses
+        .iter()
+        .map(|status| {
+            [
+                status.name.clone(),
+                status
+                    .subscribers
+                    .map(|count| i18n::format_count(count as i64))
+                    .unwrap_or_default(),
+                match status.is_live {
+                    true => i18n::tr("live-badge"),
+                    false => String::new(),
+                },
+            ]
+        })
+        .collect();
+    let width = |column: usize| {
+        rows.iter()
+            .chain([&header])
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or_default()
+    };
+    let (name_width, subscribers_width) = (width(0), width(1));
+    [header]
+        .iter()
+        .chain(&rows)
+        .map(|[name, subscribers, live]| {
+            let line = format!("{name:<name_width$}  {subscribers:>subscribers_width$}  {live}");
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+fn format_csv(statuses: &[CreatorStatus]) -> String {
+    let lines = statuses.iter().map(|status| {
+        format!(
+            "{},{},{}\n",
+            export::csv_field(&status.name),
+            status
+                .subscribers
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+            status.is_live
+        )
+    });
+    std::iter::once("name,subscribers,is_live\n".to_string())
+        .chain(lines)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<CliCommand, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn statuses() -> Vec<CreatorStatus> {
+        let json_obj: YTCreator = serde_json::from_str(
+            r#"{
+                "names": ["Kush", "Doe, Jane", "Tom"],
+                "subscribers": ["1234567", "980", "unknown"],
+                "is_live_status": ["false", "true", "true"]
+            }"#,
+        )
+        .unwrap();
+        creator_statuses(&json_obj, false)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(args(&[]), Ok(CliCommand::Gui));
+        assert_eq!(args(&["--help"]), Ok(CliCommand::Help));
+        assert_eq!(
+            args(&["status"]),
+            Ok(CliCommand::Status(StatusOptions::default()))
+        );
+        assert_eq!(
+            args(&[
+                "status",
+                "--sort",
+                "last-live",
+                "--live-only",
+                "--format=csv",
+                "--watchlist",
+                "team favourites"
+            ]),
+            Ok(CliCommand::Status(StatusOptions {
+                watchlist: Some("team favourites".to_string()),
+                sort_option: Some(AllowedFieldNamesForSorting::LastLive),
+                live_only: true,
+                format: OutputFormat::Csv,
+            }))
+        );
+        assert_eq!(
+            args(&["tui", "--watchlist=friends"]),
+            Ok(CliCommand::Tui {
+                watchlist: Some("friends".to_string())
+            })
+        );
+        assert_eq!(
+            args(&["serve", "--bind", "0.0.0.0:9000", "--metrics"]),
+            Ok(CliCommand::Serve {
+                bind: "0.0.0.0:9000".to_string(),
+                watchlist: None,
+                metrics: true
+            })
+        );
+        assert_eq!(
+            args(&[
+                "export",
+                "--format",
+                "markdown",
+                "--columns=name, viewers,tags",
+                "--output",
+                "report.md"
+            ]),
+            Ok(CliCommand::Export(ExportOptions {
+                format: ExportFormat::Markdown,
+                columns: vec![
+                    ExportColumn::Name,
+                    ExportColumn::Viewers,
+                    ExportColumn::Tags
+                ],
+                output: Some(PathBuf::from("report.md")),
+                ..Default::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert_eq!(args(&["stats"]), Err("Unknown command: stats".to_string()));
+        assert_eq!(
+            args(&["status", "--sort", "views"]),
+            Err("Unknown sort option: views".to_string())
+        );
+        assert_eq!(
+            args(&["status", "--format"]),
+            Err("Missing value for --format".to_string())
+        );
+        assert_eq!(
+            args(&["status", "--format", "xml"]),
+            Err("Unknown format: xml".to_string())
+        );
+        assert_eq!(
+            args(&["status", "--all"]),
+            Err("Unknown option: --all".to_string())
+        );
+        assert_eq!(
+            args(&["export", "--columns", "name,views"]),
+            Err("Unknown column: views".to_string())
+        );
+        assert_eq!(
+            args(&["tui", "--sort", "name"]),
+            Err("Unknown option: --sort".to_string())
+        );
+    }
+
+    #[test]
+    fn test_creator_statuses() {
+        let json_obj: YTCreator = serde_json::from_str(
+            r#"{"names": ["Kush", "Tom"], "subscribers": ["10", "20"], "is_live_status": ["false", "true"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            creator_statuses(&json_obj, true),
+            [CreatorStatus {
+                name: "Tom".to_string(),
+                subscribers: Some(20),
+                is_live: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_format_table() {
+        assert_eq!(
+            format_statuses(&statuses(), OutputFormat::Table).unwrap(),
+            "\
+Name       Subscribers  Live
+Kush         1,234,567
+Doe, Jane          980  LIVE
+Tom                     LIVE
+"
+        );
+    }
+
+    #[test]
+    fn test_format_csv_and_json() {
+        assert_eq!(
+            format_statuses(&statuses(), OutputFormat::Csv).unwrap(),
+            "name,subscribers,is_live\nKush,1234567,false\n\"Doe, Jane\",980,true\nTom,,true\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_statuses(&statuses(), OutputFormat::Json).unwrap())
+                .unwrap();
+        assert_eq!(
+            json[1],
+            serde_json::json!({"name": "Doe, Jane", "subscribers": 980, "is_live": true})
+        );
+        assert_eq!(json[2]["subscribers"], serde_json::Value::Null);
+    }
+}
@@ -1,9 +1,41 @@
-use iced::{Sandbox, Settings};
+use iced::{Application, Settings};
 
+use self::cli::CliCommand;
+use yt_monitor_core::config;
+
+mod animations;
+mod cli;
+mod notifications;
+mod oauth;
+mod render_cards;
+mod server;
+mod shortcuts;
+mod tray;
+mod tui;
 mod yt_monitor;
 
 pub fn main() -> iced::Result {
-    let mut settings = Settings::default();
-    settings.window.size = (1600, 800);
+    // Commands only print, they don't need a display
+    match cli::parse_args(std::env::args().skip(1)) {
+        Ok(CliCommand::Gui) => {}
+        Ok(command) => std::process::exit(cli::run(command)),
+        Err(err) => {
+            eprintln!("{err}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+    let (user_settings, settings_error) = config::UserSettings::load(&config::config_dir());
+    if let Some(err) = settings_error {
+        eprintln!("{err}");
+    }
+    let mut settings = Settings::with_flags(user_settings.clone());
+    settings.window.size = user_settings.window_size;
+    if let Some((x, y)) = user_settings.window_position {
+        settings.window.position = iced::window::Position::Specific(x, y);
+    }
+    // YTMonitor shows it again if there turns out to be no tray to bring it back from
+    settings.window.visible = !user_settings.start_hidden;
+    // Lets YTMonitor save the window geometry before closing
+    settings.exit_on_close_request = false;
     yt_monitor::YTMonitor::run(settings)
 }
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use zbus::blocking::Connection;
+use zbus::zvariant::Value;
+
+use yt_monitor_core::model::{YTCreator, UPCOMING_GRACE_SECS};
+
+const NOTIFICATIONS_DESTINATION: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+const NOTIFICATIONS_INTERFACE: &str = "org.freedesktop.Notifications";
+const APP_NAME: &str = "yt-monitor";
+// Same standard icon as the tray shows while someone is live
+const ICON: &str = "media-record";
+// Let the notification server decide how long it stays
+const DEFAULT_TIMEOUT: i32 = -1;
+
+// Through the desktop's notification server on the session bus
+pub fn notify(summary: &str, body: &str) -> Result<(), Box<dyn Error>> {
+    let connection = Connection::session()?;
+    connection.call_method(
+        Some(NOTIFICATIONS_DESTINATION),
+        NOTIFICATIONS_PATH,
+        Some(NOTIFICATIONS_INTERFACE),
+        "Notify",
+        &(
+            APP_NAME,
+            // Not replacing an earlier notification
+            0u32,
+            ICON,
+            summary,
+            body,
+            Vec::<&str>::new(),
+            HashMap::<&str, Value>::new(),
+            DEFAULT_TIMEOUT,
+        ),
+    )?;
+    Ok(())
+}
+
+// The scheduled streams already announced, by channel and start, so every stream is announced
+// once however often it's checked. A stream moved to another time is announced again. Streams
+// are forgotten once they're no longer upcoming (see model::upcoming_start).
+#[derive(Debug, Default)]
+pub struct UpcomingNotices {
+    announced: Vec<(String, u64)>,
+}
+
+impl UpcomingNotices {
+    // The creators of obj whose stream starts within lead_secs and wasn't announced yet, with
+    // its start. They count as announced from now on.
+    pub fn due(&mut self, obj: &YTCreator, now: u64, lead_secs: u64) -> Vec<(usize, u64)> {
+        self.announced
+            .retain(|(_, scheduled_start)| scheduled_start + UPCOMING_GRACE_SECS > now);
+        let count = obj.get_field("names").map_or(0, Vec::len);
+        let due: Vec<(usize, u64)> = (0..count)
+            .filter_map(|idx| {
+                let scheduled_start = obj.scheduled_start_of(idx, now)?;
+                let announced = (obj.channel_url(idx), scheduled_start);
+                (scheduled_start <= now + lead_secs && !self.announced.contains(&announced))
+                    .then_some((idx, scheduled_start))
+            })
+            .collect();
+        self.announced.extend(
+            due.iter()
+                .map(|(idx, start)| (obj.channel_url(*idx), *start)),
+        );
+        due
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 00:00 in the comments below
+    const NOW: u64 = 1_700_000_000;
+
+    // Kush and Tom with their streams starting at the given times, Ana live
+    fn creators(kush_start: u64, tom_start: u64) -> YTCreator {
+        serde_json::from_value(serde_json::json!({
+            "names": ["Kush", "Tom", "Ana"],
+            "is_live_status": ["false", "false", "true"],
+            "channel_ids": ["UC123", "", "UCana"],
+            "scheduled_start": [kush_start.to_string(), tom_start.to_string(), ""]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_upcoming_streams_are_announced_once() {
+        let obj = creators(NOW + 600, NOW + 7_200);
+        let mut notices = UpcomingNotices::default();
+        assert_eq!(notices.due(&obj, NOW, 900), [(0, NOW + 600)]);
+        // Kush's at 00:10, only once
+        assert!(notices.due(&obj, NOW + 60, 900).is_empty());
+        // Tom's is 10 minutes away at 01:50
+        assert_eq!(notices.due(&obj, NOW + 6_600, 900), [(1, NOW + 7_200)]);
+
+        // Kush's postponed to 02:05
+        let obj = creators(NOW + 7_500, NOW + 7_200);
+        assert_eq!(notices.due(&obj, NOW + 6_900, 900), [(0, NOW + 7_500)]);
+        // The one at 00:10 is long over
+        assert_eq!(notices.announced.len(), 2);
+
+        // Only the postponed one is still upcoming at 03:01
+        assert!(notices.due(&obj, NOW + 10_860, 900).is_empty());
+        assert_eq!(
+            notices.announced,
+            [(
+                "https://www.youtube.com/channel/UC123".to_string(),
+                NOW + 7_500
+            )]
+        );
+    }
+}
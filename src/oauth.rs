@@ -0,0 +1,133 @@
+use std::future::Future;
+use std::pin::Pin;
+use yup_oauth2::authenticator_delegate::InstalledFlowDelegate;
+use yup_oauth2::{InstalledFlowAuthenticator, InstalledFlowReturnMethod};
+
+use yt_monitor_core::config;
+use yt_monitor_core::import::{self, ImportProgress, Subscription};
+use yt_monitor_core::model::sources::OAuthClient;
+
+// The OAuth client ("Desktop app") of a Google Cloud project, as yt_api_sample.rs reads it
+const CREDENTIALS_ENV_VAR: &str = "GOOGLE_APPLICATION_CREDENTIALS";
+// In the config directory, so the browser only opens the first time
+const TOKENS_FILE_NAME: &str = "oauth_tokens.json";
+const SCOPES: [&str; 1] = ["https://www.googleapis.com/auth/youtube.readonly"];
+
+// The consent page opens in the browser instead of its URL being printed to stdout, which
+// nobody sees behind a window
+struct BrowserDelegate;
+
+impl InstalledFlowDelegate for BrowserDelegate {
+    fn present_user_url<'a>(
+        &'a self,
+        url: &'a str,
+        _need_code: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>> {
+        Box::pin(async move {
+            open::that(url).map_err(|err| format!("couldn't open {url}: {err}"))?;
+            // The redirect to the local server brings the code
+            Ok(String::new())
+        })
+    }
+}
+
+// An access token of the signed in account, refreshed from the saved tokens when they're
+// still valid
+async fn access_token() -> Result<String, String> {
+    let credentials = std::env::var(CREDENTIALS_ENV_VAR)
+        .map_err(|_| format!("{CREDENTIALS_ENV_VAR} isn't set"))?;
+    let secret = yup_oauth2::read_application_secret(&credentials)
+        .await
+        .map_err(|err| format!("couldn't read {credentials}: {err}"))?;
+    let config_dir = config::config_dir();
+    std::fs::create_dir_all(&config_dir).map_err(|err| err.to_string())?;
+    let auth = InstalledFlowAuthenticator::builder(secret, InstalledFlowReturnMethod::HTTPRedirect)
+        .persist_tokens_to_disk(config_dir.join(TOKENS_FILE_NAME))
+        .flow_delegate(Box::new(BrowserDelegate))
+        .build()
+        .await
+        .map_err(|err| err.to_string())?;
+    let token = auth.token(&SCOPES).await.map_err(|err| err.to_string())?;
+    token
+        .token()
+        .map(str::to_string)
+        .ok_or_else(|| "no access token".to_string())
+}
+
+#[derive(Debug, Clone)]
+pub enum AccountImportUpdate {
+    SignedIn,
+    Progress(ImportProgress),
+    Finished(Result<Vec<Subscription>, String>),
+}
+
+// Where the import of the account's subscriptions is, handed on from one update to the next
+#[derive(Debug, Default)]
+pub enum AccountImport {
+    #[default]
+    SigningIn,
+    Fetching {
+        access_token: String,
+        page_token: Option<String>,
+        subscriptions: Vec<Subscription>,
+    },
+    Done,
+}
+
+// One page at a time, so the window shows the progress. None once done.
+pub async fn next_update(state: AccountImport) -> Option<(AccountImportUpdate, AccountImport)> {
+    match state {
+        AccountImport::SigningIn => Some(match access_token().await {
+            Ok(access_token) => (
+                AccountImportUpdate::SignedIn,
+                AccountImport::Fetching {
+                    access_token,
+                    page_token: None,
+                    subscriptions: Vec::new(),
+                },
+            ),
+            Err(err) => (AccountImportUpdate::Finished(Err(err)), AccountImport::Done),
+        }),
+        AccountImport::Fetching {
+            access_token,
+            page_token,
+            mut subscriptions,
+        } => {
+            // reqwest's blocking client can't run on the window's runtime
+            let fetched = tokio::task::spawn_blocking(move || {
+                let client = OAuthClient::new(access_token.clone());
+                import::fetch_subscriptions_page(&client, page_token.as_deref())
+                    .map(|page| (access_token, page))
+                    .map_err(|err| err.to_string())
+            })
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|fetched| fetched);
+            let (access_token, page) = match fetched {
+                Ok(fetched) => fetched,
+                Err(err) => {
+                    return Some((AccountImportUpdate::Finished(Err(err)), AccountImport::Done))
+                }
+            };
+            subscriptions.extend(page.subscriptions);
+            Some(match page.next_page_token {
+                Some(page_token) => (
+                    AccountImportUpdate::Progress(ImportProgress {
+                        fetched: subscriptions.len(),
+                        total: page.total,
+                    }),
+                    AccountImport::Fetching {
+                        access_token,
+                        page_token: Some(page_token),
+                        subscriptions,
+                    },
+                ),
+                None => (
+                    AccountImportUpdate::Finished(Ok(subscriptions)),
+                    AccountImport::Done,
+                ),
+            })
+        }
+        AccountImport::Done => None,
+    }
+}
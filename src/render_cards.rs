@@ -1,209 +1,21 @@
 use iced::theme;
 // use iced::widget::container::Appearance;
-use iced::widget::{column, container, image, row, text, Column, Container, Row};
+use iced::widget::{button, column, container, image, row, text, tooltip, Column, Container, Row};
 use iced::{Length, Renderer};
 use iced_core::Color;
-use serde::Deserialize;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-
 use std::error::Error;
 
-pub const MAX_EXPECTED_ITEMS: usize = 12;
-const JSON_FILE_PATH: &str = "list_users.json";
-
-// This also adds an impl: get_field to get the corresponding field from the field name (&str)
-macro_rules! get_struct_names {
-    (
-        #[derive($($derive_name:ident),*)]
-        pub struct $name:ident {
-            $(pub $fname:ident : $ftype:ty), *
-        }
-    ) => {
-        #[derive($($derive_name),*)]
-        pub struct $name {
-            $($fname : $ftype),*
-        }
-
-        impl $name {
-            fn field_names() -> &'static [&'static str] {
-                static NAMES: &'static [&'static str] = &[$(stringify!($fname)), *];
-                NAMES
-            }
-
-            pub fn get_field(&self, field_name: &str) -> Option<&Vec<String>> {
-                match field_name {
-                    $(stringify!($fname) => Some(&self.$fname)),
-                    *,
-                    &_ => None
-                }
-            }
-
-            fn slice_to(&self, count_items: usize) -> YTCreator {
-                YTCreator {
-                    $($fname: self.$fname.get(0..count_items).expect(&format!("Not enough elements to be sliced into, maybe check the input {count_items} again.")).to_vec()),
-                    *
-                }
-            }
-
-            fn set_field(&mut self, field_name: &str, field_data: Vec<String>) -> &mut YTCreator {
-                match field_name {
-                    $(stringify!($fname) => {
-                        self.$fname = field_data
-                    }),
-                    *,
-                    &_ => ()
-                };
-                self
-            }
-        }
-    }
-}
-
-get_struct_names! {
-    #[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
-    pub struct YTCreator {
-        pub names: Vec<String>,
-        pub avatar_links: Vec<String>,
-        pub descriptions: Vec<String>,
-        pub is_live_status: Vec<String>,
-        pub subscribers: Vec<String>
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum AllowedFieldNamesForSorting {
-    Subscribers,
-    IsLiveStatus,
-}
-
-impl Default for AllowedFieldNamesForSorting {
-    fn default() -> Self {
-        AllowedFieldNamesForSorting::Subscribers
-    }
-}
-
-impl YTCreator {
-    fn size(&self) -> usize {
-        let mut lengths_all: Vec<usize> = vec![];
-        let mut msges: String = "".to_string();
-        for field_name in YTCreator::field_names().iter() {
-            let len_field: usize = self.get_field(field_name).unwrap().len();
-            lengths_all.push(len_field);
-            if len_field > MAX_EXPECTED_ITEMS {
-                msges += format!(
-                    "Found: {len_field} but got {MAX_EXPECTED_ITEMS} for the given field_name: {field_name}\n"
-                ).as_str();
-            };
-        }
-
-        if !msges.is_empty() {
-            panic!("Found more items than expected. {msges}");
-        }
-        assert!(
-            lengths_all
-                .windows(2)
-                .all(|single_len| single_len[0] == single_len[1]),
-            "Not all fields have equal length. Check the input data again."
-        );
-        self.names.len()
-    }
+use yt_monitor_core::themes::{HexColor, ThemeColorField};
+use yt_monitor_core::{appearance, client, config, export, i18n, watchlists};
 
-    // Implement sort_by(field_name="")
-    // For example, sort_by(field_name="subscriber_count")
-    // Inplace operation
-
-    fn helper_sort(&self, field: &[String], is_bool: bool) -> (YTCreator, Vec<usize>) {
-        let mut new_yt_creator: YTCreator = YTCreator::default();
-        // let's create a mapping =>
-        // {0 : field[...], 1: field[...], ...}
-        let mut hash_map: HashMap<i32, String> = HashMap::new();
-        for (idx, val) in field.iter().enumerate() {
-            hash_map.insert(idx as i32, val.to_string());
-        }
-        let mut count_vec: Vec<(&i32, &String)> = hash_map.iter().collect();
-        count_vec.sort_by(|a, b| {
-            if b.1 == a.1 {
-                let b_32: i32 = self
-                    .subscribers
-                    .get(*b.0 as usize)
-                    .unwrap()
-                    .parse()
-                    .ok()
-                    .unwrap();
-                let a_32: i32 = self
-                    .subscribers
-                    .get(*a.0 as usize)
-                    .unwrap()
-                    .parse()
-                    .ok()
-                    .unwrap();
-                b_32.cmp(&a_32)
-            } else {
-                match is_bool {
-                    true => {
-                        let b_bool: bool = b.1.parse().ok().unwrap();
-                        let a_bool: bool = a.1.parse().ok().unwrap();
-                        b_bool.cmp(&a_bool)
-                    }
-                    false => {
-                        let b_32: i32 = b.1.parse().ok().unwrap();
-                        let a_32: i32 = a.1.parse().ok().unwrap();
-                        b_32.cmp(&a_32)
-                    }
-                }
-            }
-        });
-        for field_name in YTCreator::field_names() {
-            let data_field = self.get_field(field_name).unwrap();
-            let mut new_data_field = Vec::<String>::new();
-            for tuple_ in count_vec.iter() {
-                let idx = tuple_.0;
-                new_data_field.push(data_field.get(*idx as usize).unwrap().to_string());
-            }
-            new_yt_creator.set_field(field_name, new_data_field);
-        }
-        let mut new_indices = Vec::<usize>::new();
-        for tuple_ in count_vec.iter() {
-            let idx = tuple_.0;
-            new_indices.push(*idx as usize);
-        }
-        (new_yt_creator, new_indices)
-    }
+// The widgets of the window, on top of the creators model of the core library
+pub use yt_monitor_core::model::*;
 
-    pub fn sort_by(
-        &self,
-        field_name: AllowedFieldNamesForSorting,
-    ) -> Option<(YTCreator, Vec<usize>)> {
-        match field_name {
-            AllowedFieldNamesForSorting::Subscribers => {
-                let field = self.get_field("subscribers").unwrap();
-                let sort_output = self.helper_sort(field, false);
-                Some(sort_output)
-            }
-            AllowedFieldNamesForSorting::IsLiveStatus => {
-                let field = self.get_field("is_live_status").unwrap();
-                let sort_output = self.helper_sort(field, true);
-                Some(sort_output)
-            }
-        }
-    }
-}
-
-// Straight from the documentation
-pub fn read_json(file_path: &str) -> Result<YTCreator, Box<dyn Error>> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
-    // Read the JSON contents of the file as an instance of `YTCreator`.
-    let u: YTCreator = serde_json::from_reader(reader)?;
-    if u.size() > MAX_EXPECTED_ITEMS {
-        Ok(u.slice_to(MAX_EXPECTED_ITEMS))
-    } else {
-        Ok(u)
-    }
-}
+// Same as iced's default text size
+const DEFAULT_FONT_SIZE: u16 = 20;
+// Shown for the avatars which couldn't be downloaded, e.g. while offline
+const PLACEHOLDER_AVATAR: &[u8] = include_bytes!("../assets/placeholder_avatar.png");
 
 #[derive(Debug, Default, Clone)]
 pub struct Card {
@@ -212,6 +24,8 @@ pub struct Card {
     description: String,
     is_live_status: String,
     subscribers: String,
+    last_live: String,
+    scheduled_start: String,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -219,39 +33,101 @@ pub struct ListOfCards {
     cards: Vec<Card>,
 }
 
-#[derive(Debug, PartialEq, Clone, Eq, Copy)]
-pub enum ThemeType {
-    Light,
-    Dark,
-    Custom,
-}
-
 #[derive(Debug, Clone)]
 pub enum Message {
     ThemeChanged(ThemeType),
     SortOptionChanged(AllowedFieldNamesForSorting),
+    SortDirectionChanged(SortDirection),
+    SearchChanged(String),
+    LiveOnlyToggled(bool),
+    SubscriberRangeChanged(SubscriberRange),
+    TagFilterToggled(String, bool),
+    ClearFilters,
+    GroupByTagToggled(bool),
+    TagSectionToggled(String),
+    WatchlistChanged(watchlists::Watchlist),
+    CardsPerRowChanged(usize),
+    PollingIntervalChanged(u64),
+    UpcomingNoticeChanged(config::UpcomingNotice),
+    AnimationsToggled(bool),
+    AnimationFrame(std::time::Instant),
+    ViewModeChanged(ViewMode),
+    DisplayFormatChanged(DisplayFormat),
+    LanguageChanged(i18n::Language),
+    StaticTextEdited(TextType, String),
+    StaticTextToggled(TextType, bool),
+    ClockTicked,
+    StartHiddenToggled(bool),
+    Tray(super::tray::TrayEvent),
+    ListHeaderClicked(AllowedFieldNamesForSorting),
+    Shortcut(super::shortcuts::Shortcut),
+    Refresh,
+    Daemon(client::DaemonUpdate),
+    DaemonUrlEdited(String),
+    DaemonUrlSubmitted,
+    ExportOpened,
+    ExportFormatChanged(export::ExportFormat),
+    ExportColumnToggled(export::ExportColumn, bool),
+    ExportPathEdited(String),
+    ExportSaved,
+    ExportCopied,
+    ExportClosed,
+    ImportOpened,
+    ImportPathEdited(String),
+    ImportFileLoaded,
+    AccountImportStarted,
+    AccountImport(super::oauth::AccountImportUpdate),
+    SubscriptionsLoaded(Result<super::yt_monitor::LoadedSubscriptions, String>),
+    ImportChannelToggled(usize, bool),
+    ImportAllToggled(bool),
+    ImportConfirmed,
+    SubscriptionsImported(Result<usize, String>),
+    ImportClosed,
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    CloseRequested,
+    ThemeEditorOpened,
+    ThemeNameEdited(String),
+    ThemeColorEdited(ThemeColorField, String),
+    ThemeFontSizeChanged(u16),
+    ThemeBorderWidthChanged(f32),
+    ThemeEditorSaved,
+    ThemeEditorCancelled,
+    SystemColorSchemeChanged(appearance::ColorScheme),
+    WatchlistLoaded(Box<super::yt_monitor::FetchedWatchlist>),
+    WatchlistRefreshed(Box<super::yt_monitor::FetchedWatchlist>),
+    AvatarsDownloaded(HashMap<String, image::Handle>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextType {
     Header,
     Footer,
     Normal,
 }
 
+// The values of a field of obj, empty for an unknown field name
+fn field<'a>(obj: &'a YTCreator, field_name: &str) -> &'a [String] {
+    obj.get_field(field_name).map_or(&[], Vec::as_slice)
+}
+
 // FIXME: Not taking any arguments intentionally for now, once JSON reading is done
 // add arguments.
-pub fn create_list_of_cards(obj: &YTCreator) -> Vec<ListOfCards> {
+pub fn create_list_of_cards(obj: &YTCreator, cards_per_row: usize) -> Vec<ListOfCards> {
     let mut list_of_cards = vec![ListOfCards::default()];
-    for (count_so_far, (name, description, is_live_status, subscribers, avatar_link)) in
-        itertools::izip!(
-            &obj.names,
-            &obj.descriptions,
-            &obj.is_live_status,
-            &obj.subscribers,
-            &obj.avatar_links
-        )
-        .enumerate()
+    for (
+        count_so_far,
+        (name, description, is_live_status, subscribers, avatar_link, last_live, scheduled_start),
+    ) in itertools::izip!(
+        field(obj, "names"),
+        field(obj, "descriptions"),
+        field(obj, "is_live_status"),
+        field(obj, "subscribers"),
+        field(obj, "avatar_links"),
+        field(obj, "last_live"),
+        field(obj, "scheduled_start")
+    )
+    .enumerate()
     {
         let card = Card {
             name: name.to_string(),
@@ -259,9 +135,11 @@ pub fn create_list_of_cards(obj: &YTCreator) -> Vec<ListOfCards> {
             is_live_status: is_live_status.to_string(),
             subscribers: subscribers.to_string(),
             avatar_link: avatar_link.to_string(),
+            last_live: last_live.to_string(),
+            scheduled_start: scheduled_start.to_string(),
         };
 
-        if count_so_far % 4 != 0 || count_so_far == 0 {
+        if count_so_far % cards_per_row != 0 || count_so_far == 0 {
             list_of_cards.last_mut().unwrap().cards.push(card);
         } else {
             list_of_cards.push(ListOfCards::default());
@@ -272,112 +150,262 @@ pub fn create_list_of_cards(obj: &YTCreator) -> Vec<ListOfCards> {
     list_of_cards
 }
 
-pub fn create_card(card: &Card) -> iced::Element<'static, Message> {
-    let container_text = "Name: ".to_owned()
-        + &card.name
-        + "\nDescription: "
-        + &card.description
-        + "\nSubscriber Count: "
-        + &card.subscribers
-        + "\nIs Live?: "
-        + &card.is_live_status
-        + "\n";
-    container(column![text(container_text)]).into()
+// A line of text, with the exact value on hover if the line only shows an abbreviation
+fn text_with_exact_value(
+    line: String,
+    exact_value: Option<String>,
+    font_size: u16,
+) -> iced::Element<'static, Message> {
+    match exact_value {
+        Some(exact_value) => tooltip(
+            text(line).size(font_size),
+            exact_value,
+            tooltip::Position::Bottom,
+        )
+        .style(theme::Container::Box)
+        .into(),
+        None => text(line).size(font_size).into(),
+    }
+}
+
+pub fn create_card(
+    card: &Card,
+    font_size: u16,
+    format: &CardFormat,
+) -> iced::Element<'static, Message> {
+    let is_live = sorting::parse_bool(&card.is_live_status);
+    let subscribers = views::format_subscribers(&card.subscribers, format.display_format);
+    let mut lines = vec![
+        text(i18n::tr_args(
+            "card-name",
+            &[("name", card.name.as_str().into())],
+        ))
+        .size(font_size)
+        .into(),
+        text(i18n::tr_args(
+            "card-description",
+            &[("description", card.description.as_str().into())],
+        ))
+        .size(font_size)
+        .into(),
+        text_with_exact_value(
+            i18n::tr_args("card-subscribers", &[("subscribers", subscribers.into())]),
+            views::exact_subscribers(&card.subscribers, format.display_format),
+            font_size,
+        ),
+        text(i18n::tr_args(
+            "card-is-live",
+            &[("is_live", is_live.to_string().into())],
+        ))
+        .size(font_size)
+        .into(),
+    ];
+    if let Some(last_live) = views::format_last_live(&card.last_live, is_live, format) {
+        lines.push(text_with_exact_value(
+            last_live,
+            views::exact_last_live(&card.last_live, format.display_format),
+            font_size,
+        ));
+    }
+    if let Some(scheduled_start) =
+        upcoming_start(&card.is_live_status, &card.scheduled_start, format.now)
+    {
+        lines.push(
+            text(views::format_scheduled_start(scheduled_start, format))
+                .size(font_size)
+                .into(),
+        );
+    }
+    container(Column::with_children(lines)).into()
 }
 
-struct ContainerCustomStyle {
-    curr_theme: theme::Theme,
-    curr_live_status: bool,
+pub fn to_color(hex_color: HexColor) -> Color {
+    Color::from_rgb8(hex_color.r, hex_color.g, hex_color.b)
 }
 
-const DARK_BACKGROUND_LIVE: Option<iced_core::Background> =
-    Some(iced_core::Background::Color(Color {
-        r: 255.0,
-        g: 255.0,
-        b: 255.0,
-        a: 0.7,
-    }));
-const LIGHT_BACKGROUND_LIVE: Option<iced_core::Background> =
-    Some(iced_core::Background::Color(Color {
-        r: 0.0,
-        g: 0.0,
-        b: 0.0,
-        a: 0.9,
-    }));
-
-impl container::StyleSheet for ContainerCustomStyle {
+struct CardStyleSheet {
+    colors: CardColors,
+}
+
+impl container::StyleSheet for CardStyleSheet {
+    type Style = theme::Theme;
+    fn appearance(&self, _: &iced::Theme) -> container::Appearance {
+        container::Appearance {
+            text_color: Some(to_color(self.colors.text)),
+            background: Some(iced_core::Background::Color(to_color(
+                self.colors.background,
+            ))),
+            border_radius: self.colors.border_radius,
+            border_width: self.colors.border_width,
+            border_color: to_color(self.colors.border),
+        }
+    }
+}
+
+struct LiveBadgeStyleSheet {
+    colors: CardColors,
+    opacity: f32,
+}
+
+impl container::StyleSheet for LiveBadgeStyleSheet {
     type Style = theme::Theme;
     fn appearance(&self, _: &iced::Theme) -> container::Appearance {
-        let (text_color, bg) = match &self.curr_live_status {
-            true => match &self.curr_theme {
-                iced::Theme::Light => (Color::WHITE, LIGHT_BACKGROUND_LIVE),
-                iced::Theme::Dark => (Color::BLACK, DARK_BACKGROUND_LIVE),
-                iced::Theme::Custom(_) => (
-                    Color::BLACK,
-                    Some(iced_core::Background::Color(Color::TRANSPARENT)),
-                ),
-            },
-            false => match &self.curr_theme {
-                iced::Theme::Light => (Color::BLACK, None),
-                iced::Theme::Dark => (Color::WHITE, None),
-                iced::Theme::Custom(_) => (
-                    Color::BLACK,
-                    Some(iced_core::Background::Color(Color::TRANSPARENT)),
-                ),
-            },
-        };
         container::Appearance {
-            text_color: Some(text_color),
-            background: bg,
-            border_radius: 2.0,
-            border_width: 2.0,
+            text_color: Some(to_color(self.colors.badge_text)),
+            background: Some(iced_core::Background::Color(Color {
+                a: self.opacity,
+                ..to_color(self.colors.badge_background)
+            })),
+            border_radius: 4.0,
+            border_width: 0.0,
             border_color: Color::TRANSPARENT,
         }
     }
 }
 
+// Shown on live cards so that the status never depends on telling colors apart
+fn live_badge<'a>(colors: CardColors, opacity: f32, font_size: u16) -> Container<'a, Message> {
+    container(text(i18n::tr("live-badge")).size(font_size))
+        .padding([2, 8])
+        .style(iced::theme::Container::Custom(Box::new(
+            LiveBadgeStyleSheet { colors, opacity },
+        )))
+}
+
 pub fn create_row(
     cards: &ListOfCards,
     img_handles_row: &[image::Handle],
     offset: usize,
-    theme: &theme::Theme,
+    scheme: &CardScheme,
     status: &[bool],
+    animations: &[CardAnimation],
+    format: &CardFormat,
 ) -> Row<'static, Message> {
+    let view_mode = format.view_mode;
+    let font_size = view_mode.font_size(scheme_font_size(scheme));
+    let padding = view_mode.card_padding();
     Row::with_children(
         cards
             .cards
             .iter()
             .enumerate()
             .map(|(idx, each_card)| {
+                let is_live = *status.get(idx + offset).unwrap();
+                let animation = animations.get(idx + offset).copied().unwrap_or_default();
+                let colors = scheme.animated_card_colors(is_live, &animation);
+                let card_text = match is_live {
+                    true => column![
+                        live_badge(colors, animation.badge_opacity, font_size),
+                        create_card(each_card, font_size, format)
+                    ]
+                    .spacing(padding / 2),
+                    false => column![create_card(each_card, font_size, format)],
+                };
                 container(
                     row![
-                        column![create_card(each_card)]
-                            .padding(20)
-                            .width(Length::FillPortion(2)),
+                        card_text.padding(padding).width(Length::FillPortion(2)),
                         column![profile_pic(
-                            130,
+                            view_mode.avatar_size(),
                             img_handles_row.get(offset + idx).unwrap().to_owned()
                         )]
                         .width(Length::FillPortion(1))
-                        .padding(20)
+                        .padding(padding)
                     ]
                     .align_items(iced::Alignment::Center)
                     .height(Length::Fill),
                 )
                 .width(Length::Fill)
                 .center_y()
-                .style(iced::theme::Container::Custom(Box::new(
-                    ContainerCustomStyle {
-                        curr_theme: theme.clone(),
-                        curr_live_status: *status.get(idx + offset).unwrap(),
-                    },
-                )))
+                .style(iced::theme::Container::Custom(Box::new(CardStyleSheet {
+                    colors,
+                })))
                 .into()
             })
             .collect(),
     )
 }
 
+fn scheme_font_size(scheme: &CardScheme) -> u16 {
+    scheme
+        .custom_theme()
+        .map_or(DEFAULT_FONT_SIZE, |custom_theme| custom_theme.font_size)
+}
+
+// One line per creator of obj, under a header whose sortable columns are buttons
+pub fn create_list_view(
+    obj: &YTCreator,
+    img_handles: &[image::Handle],
+    scheme: &CardScheme,
+    animations: &[CardAnimation],
+    sort_option: AllowedFieldNamesForSorting,
+    sort_direction: SortDirection,
+    format: &CardFormat,
+) -> Column<'static, Message> {
+    let font_size = ViewMode::List.font_size(scheme_font_size(scheme));
+    let header = Row::with_children(
+        ListColumn::ALL
+            .iter()
+            .map(|column| {
+                let title = text(column.header(sort_option, sort_direction)).size(font_size);
+                let cell: iced::Element<'static, Message> = match column.sort_key() {
+                    Some(sort_key) => button(title)
+                        .style(theme::Button::Text)
+                        .on_press(Message::ListHeaderClicked(sort_key))
+                        .into(),
+                    None => title.into(),
+                };
+                container(cell)
+                    .width(Length::FillPortion(column.width_portion()))
+                    .into()
+            })
+            .collect(),
+    )
+    .padding(ViewMode::List.card_padding())
+    .align_items(iced::Alignment::Center);
+
+    let rows = (0..field(obj, "names").len()).map(|idx| {
+        let is_live = sorting::parse_bool(&field(obj, "is_live_status")[idx]);
+        let animation = animations.get(idx).copied().unwrap_or_default();
+        let colors = CardColors {
+            border_radius: 0.0,
+            ..scheme.animated_card_colors(is_live, &animation)
+        };
+        let cells = ListColumn::ALL
+            .iter()
+            .map(|column| {
+                let cell: iced::Element<'static, Message> = match column {
+                    ListColumn::Avatar => {
+                        profile_pic(ViewMode::List.avatar_size(), img_handles[idx].clone()).into()
+                    }
+                    ListColumn::Live if is_live => {
+                        live_badge(colors, animation.badge_opacity, font_size).into()
+                    }
+                    _ => text_with_exact_value(
+                        column.cell(obj, idx, format),
+                        column.tooltip(obj, idx, format),
+                        font_size,
+                    ),
+                };
+                container(cell)
+                    .width(Length::FillPortion(column.width_portion()))
+                    .into()
+            })
+            .collect();
+        container(
+            Row::with_children(cells)
+                .padding(ViewMode::List.card_padding())
+                .align_items(iced::Alignment::Center),
+        )
+        .width(Length::Fill)
+        .style(iced::theme::Container::Custom(Box::new(CardStyleSheet {
+            colors,
+        })))
+        .into()
+    });
+
+    Column::with_children(std::iter::once(header.into()).chain(rows).collect()).spacing(4)
+}
+
 pub fn profile_pic<'a>(width: u16, img_handle: image::Handle) -> Container<'a, Message> {
     container(
         image(img_handle)
@@ -405,238 +433,81 @@ pub fn create_text<'a>(
         .center_x()
 }
 
-pub fn get_json_data(json_path: Option<&str>) -> YTCreator {
-    let json_file_path = json_path.unwrap_or(JSON_FILE_PATH);
-    let obj = read_json(json_file_path).unwrap();
-    obj
+pub fn placeholder_avatar() -> image::Handle {
+    image::Handle::from_memory(PLACEHOLDER_AVATAR.to_vec())
 }
 
-pub fn get_all_avatars(json_obj: &YTCreator) -> Vec<image::Handle> {
-    let mut out_handles: Vec<image::Handle> = Vec::new();
-    for link in &json_obj.avatar_links {
-        let img_obj = reqwest::blocking::get(link).ok();
-        let img_bytes = match img_obj {
-            Some(bytes) => bytes.bytes().ok(),
-            None => None,
-        };
-        let out_img: image::Handle = image::Handle::from_memory(img_bytes.expect("Probably the image wasn't read successfully. Please check the avatar link again").to_vec());
-        out_handles.push(out_img);
-    }
+fn get_avatar(link: &str) -> Result<image::Handle, Box<dyn Error>> {
+    let img_bytes = reqwest::blocking::get(link)?.error_for_status()?.bytes()?;
+    Ok(image::Handle::from_memory(img_bytes.to_vec()))
+}
 
-    out_handles
+// By link. The placeholder stands in for every avatar that can't be downloaded, it isn't
+// tried again until the next start.
+pub fn download_avatars(links: &[String]) -> HashMap<String, image::Handle> {
+    links
+        .iter()
+        .filter(|link| !link.is_empty())
+        .map(|link| {
+            let avatar = get_avatar(link).unwrap_or_else(|err| {
+                eprintln!("Couldn't download the avatar {link}: {err}");
+                placeholder_avatar()
+            });
+            (link.clone(), avatar)
+        })
+        .collect()
 }
 
-pub fn get_live_status(live_status: Option<&Vec<String>>) -> Vec<bool> {
-    let mut out_status: Vec<bool> = Vec::new();
-    let status_as_strings = live_status.expect("Didn't find any data in is_live_status field.");
-    for status in status_as_strings {
-        let _true_str = String::from("true");
-        let _false_str = String::from("false");
-        let bool_output: bool = status.trim().parse().unwrap();
-        out_status.push(bool_output);
-    }
-    out_status
+// In the order of json_obj, the placeholder for the ones not downloaded (yet)
+pub fn get_all_avatars(
+    json_obj: &YTCreator,
+    avatars: &HashMap<String, image::Handle>,
+) -> Vec<image::Handle> {
+    field(json_obj, "avatar_links")
+        .iter()
+        .map(|link| {
+            avatars
+                .get(link)
+                .cloned()
+                .unwrap_or_else(placeholder_avatar)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    // Testing YTCreator struct methods
-    #[test]
-    fn test_yt_creator_size_empty() {
-        let mock_yt_creator = YTCreator::default();
-        assert_eq!(mock_yt_creator.size(), 0);
-    }
-
-    #[test]
-    fn test_yt_creator_size_non_empty() {
-        let mock_yt_creator = YTCreator {
-            names: vec!["Kush".to_string()],
-            avatar_links: vec!["sample".to_string()],
-            descriptions: vec!["Dev".to_string()],
-            is_live_status: vec!["true".to_string()],
-            subscribers: vec!["200".to_string()],
-        };
-        assert_eq!(mock_yt_creator.size(), 1);
-    }
-
-    #[test]
-    #[should_panic(expected = "Not all fields have equal length")]
-    fn test_yt_creator_size_invalid() {
-        YTCreator {
-            names: vec!["Kush".to_string(), "Another".to_string()],
-            avatar_links: vec!["sample".to_string()],
-            descriptions: vec!["Dev".to_string(), "Another".to_string()],
-            is_live_status: vec!["true".to_string()],
-            subscribers: vec!["200".to_string()],
-        }
-        .size();
-    }
-
-    #[test]
-    fn test_yt_creator_get_field_names() {
-        assert_eq!(
-            YTCreator::field_names(),
-            vec![
-                "names",
-                "avatar_links",
-                "descriptions",
-                "is_live_status",
-                "subscribers"
-            ]
-        );
-    }
-
-    #[test]
-    fn test_yt_creator_get_field() {
-        let mock_yt_creator = get_json_data(Some("test_assets/sample_data.json"));
-        let output_names = mock_yt_creator.get_field("names");
-        assert!(output_names.is_some());
-        // Testing for just one field is enough IMO
-        assert_eq!(output_names.unwrap(), &vec!["Kush", "Kushashwa"]);
-    }
-
-    #[test]
-    fn test_yt_creator_get_field_invalid() {
-        let mock_yt_creator = get_json_data(Some("test_assets/sample_data.json"));
-        assert!(mock_yt_creator.get_field("doesn't_exist").is_none());
-    }
-
-    #[test]
-    fn test_get_json_data_valid_file() {
-        let expected_output: YTCreator = YTCreator {
-            names: vec!["Kush", "Kushashwa"].iter().map(|&s|s.into()).collect(),
-            avatar_links: vec!["https://avatars.githubusercontent.com/u/19997320?v=4", "https://media-exp1.licdn.com/dms/image/C4D03AQGiAbH1TT3fNA/profile-displayphoto-shrink_800_800/0/1642226109876?e=2147483647&v=beta&t=fcJojobq-NZv0oNX_WW9RrCsYsoTqz0TSYMcC6zOGco"].iter().map(|&s|s.into()).collect(),
-            descriptions: vec!["Developer", "Developer"].iter().map(|&s|s.into()).collect(),
-            is_live_status: vec!["true", "false"].iter().map(|&s|s.into()).collect(),
-            subscribers: vec!["100", "200"].iter().map(|&s|s.into()).collect()
-        };
-        assert_eq!(
-            get_json_data(Some("test_assets/sample_data.json")),
-            expected_output
-        );
-    }
-
-    #[test]
-    #[should_panic(expected = "No such file or directory")]
-    fn test_get_json_data_invalid_file() {
-        get_json_data(Some("invalid_files.json"));
-    }
-
     #[test]
     fn test_get_all_avatars_valid() {
-        let sample_data_yt_creator: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
-        assert!(!get_all_avatars(&sample_data_yt_creator).is_empty());
+        let sample_data_yt_creator: YTCreator =
+            get_json_data(Some("core/test_assets/sample_data.json"));
+        let avatars = download_avatars(field(&sample_data_yt_creator, "avatar_links"));
+        assert!(!get_all_avatars(&sample_data_yt_creator, &avatars).is_empty());
     }
 
     #[test]
     fn test_get_all_avatars_empty_data() {
-        let sample_data_yt_creator: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
-        assert!(get_all_avatars(&sample_data_yt_creator).is_empty());
+        let sample_data_yt_creator: YTCreator =
+            get_json_data(Some("core/test_assets/empty_data.json"));
+        let avatars = download_avatars(field(&sample_data_yt_creator, "avatar_links"));
+        assert!(avatars.is_empty());
+        assert!(get_all_avatars(&sample_data_yt_creator, &avatars).is_empty());
     }
 
     #[test]
-    #[should_panic(expected = "wasn't read successfully")]
     fn test_get_all_avatars_invalid_data() {
-        let mut sample_data_yt_creator: YTCreator =
-            get_json_data(Some("test_assets/sample_data.json"));
-        sample_data_yt_creator.avatar_links.pop();
-        sample_data_yt_creator
-            .avatar_links
-            .push("wrong_link".to_string());
-        get_all_avatars(&sample_data_yt_creator);
-    }
-
-    #[test]
-    fn test_get_live_status_valid() {
-        let sample_data_yt_creator: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
-        assert_eq!(
-            get_live_status(Some(&sample_data_yt_creator.is_live_status)),
-            vec![true, false]
-        );
-    }
-
-    #[test]
-    fn test_get_live_status_empty() {
-        let sample_data_yt_creator: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
-        assert_eq!(
-            get_live_status(Some(&sample_data_yt_creator.is_live_status)).len(),
-            0
-        );
-    }
-
-    #[test]
-    #[should_panic(expected = "Didn't find any data in is_live_status field.")]
-    fn test_get_live_status_invalid() {
-        get_live_status(None);
-    }
-
-    #[test]
-    fn test_yt_creator_slice_to() {
-        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
-        assert_eq!(yt_creator_mock.slice_to(1).size(), 1);
-    }
-
-    #[test]
-    #[should_panic(expected = "Not enough elements to be sliced into")]
-    fn test_yt_creator_slice_to_more_than_existing() {
-        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/sample_data.json"));
-        yt_creator_mock.slice_to(3);
-    }
-
-    #[test]
-    fn test_yt_creator_slice_to_on_empty_valid() {
-        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
-        assert_eq!(yt_creator_mock.slice_to(0).size(), 0);
-    }
-
-    #[test]
-    #[should_panic(expected = "Not enough elements to be sliced into")]
-    fn test_yt_creator_slice_to_on_empty_invalid() {
-        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/empty_data.json"));
-        assert_eq!(yt_creator_mock.slice_to(2).size(), 0);
-    }
-
-    #[test]
-    fn test_yt_creator_set_field() {
-        let mut yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
-        let new_data: Vec<String> = vec![
-            "0".to_string(),
-            "0".to_string(),
-            "0".to_string(),
-            "0".to_string(),
-        ];
-        yt_creator_mock.set_field("subscribers", new_data);
-        assert_eq!(yt_creator_mock.subscribers, vec!["0", "0", "0", "0"]);
-    }
-
-    #[test]
-    fn test_yt_creator_sort_by_is_live_status() {
-        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
-        let sorted_tuple_with_indices =
-            yt_creator_mock.sort_by(AllowedFieldNamesForSorting::IsLiveStatus);
-        let new_yt_creator_mock = sorted_tuple_with_indices.unwrap().0;
-        assert_eq!(
-            new_yt_creator_mock.subscribers,
-            ["300", "100", "400", "200"]
-        );
-    }
-
-    #[test]
-    fn test_yt_creator_sort_by_subscribers() {
-        let yt_creator_mock: YTCreator = get_json_data(Some("test_assets/more_data.json"));
-        let new_yt_creator_mock = yt_creator_mock
-            .sort_by(AllowedFieldNamesForSorting::Subscribers)
-            .unwrap()
-            .0;
-        assert_ne!(
-            new_yt_creator_mock.avatar_links,
-            yt_creator_mock.avatar_links
-        );
-        assert_eq!(
-            new_yt_creator_mock.subscribers,
-            ["400", "300", "200", "100"]
-        );
+        let sample_data_yt_creator: YTCreator = serde_json::from_str(
+            r#"{"names": ["Kush"], "avatar_links": ["wrong_link"], "descriptions": [""], "subscribers": ["1"], "is_live_status": ["false"]}"#,
+        )
+        .unwrap();
+        let downloaded = download_avatars(field(&sample_data_yt_creator, "avatar_links"));
+        assert_eq!(downloaded["wrong_link"].id(), placeholder_avatar().id());
+        let avatars = get_all_avatars(&sample_data_yt_creator, &downloaded);
+        assert_eq!(avatars.len(), 1);
+        assert_eq!(avatars[0].id(), placeholder_avatar().id());
+        // Shown before the download too
+        let avatars = get_all_avatars(&sample_data_yt_creator, &HashMap::new());
+        assert_eq!(avatars[0].id(), placeholder_avatar().id());
     }
 }
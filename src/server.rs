@@ -0,0 +1,425 @@
+use serde::Serialize;
+use std::error::Error;
+use std::io::Write;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::cli;
+use yt_monitor_core::metrics;
+use yt_monitor_core::model::views;
+use yt_monitor_core::monitor::Monitor;
+use yt_monitor_core::records::{self, CreatorRecord, MonitorStatus};
+
+pub const DEFAULT_BIND: &str = "127.0.0.1:8080";
+// A comment sent on quiet event streams, so proxies keep them open and closed ones are noticed
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+// Written by hand, tiny_http would buffer the events of a chunked body
+const EVENT_STREAM_HEAD: &str = "HTTP/1.1 200 OK\r\n\
+Content-Type: text/event-stream\r\n\
+Cache-Control: no-cache\r\n\
+Connection: close\r\n\r\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Route {
+    Creators,
+    Creator(String),
+    Live,
+    Status,
+    Refresh,
+    Events,
+    Metrics,
+    NotFound,
+    MethodNotAllowed,
+}
+
+fn route(method: &Method, url: &str) -> Route {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let route = match segments.as_slice() {
+        ["creators"] => Route::Creators,
+        ["creators", id] => Route::Creator(percent_decode(id)),
+        ["live"] => Route::Live,
+        ["status"] => Route::Status,
+        ["refresh"] => Route::Refresh,
+        ["events"] => Route::Events,
+        ["metrics"] => Route::Metrics,
+        _ => return Route::NotFound,
+    };
+    let expected_method = match route {
+        Route::Refresh => Method::Post,
+        _ => Method::Get,
+    };
+    match *method == expected_method {
+        true => route,
+        false => Route::MethodNotAllowed,
+    }
+}
+
+// The ids of creators without a channel id are their names, e.g. "Jane%20Doe"
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = bytes
+            .get(idx + 1..idx + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[idx], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// "event: live\ndata: {...}\n\n"
+fn sse_message(event: &str, data: &impl Serialize) -> String {
+    let data = serde_json::to_string(data).unwrap_or_default();
+    format!("event: {event}\ndata: {data}\n\n")
+}
+
+fn json_body(value: &impl Serialize) -> (u16, String) {
+    (200, serde_json::to_string(value).unwrap_or_default())
+}
+
+fn error_body(status_code: u16, error: &str) -> (u16, String) {
+    (
+        status_code,
+        serde_json::json!({ "error": error }).to_string(),
+    )
+}
+
+// The monitor shared by the poller and the requests, and the open event streams
+#[derive(Debug)]
+struct Daemon {
+    monitor: Mutex<Monitor>,
+    // Held during a whole refresh, so that the quota isn't counted twice
+    refreshing: Mutex<()>,
+    streams: Mutex<Vec<Sender<String>>>,
+    // Whether /metrics is served
+    metrics: bool,
+}
+
+impl Daemon {
+    fn new(monitor: Monitor, metrics: bool) -> Daemon {
+        Daemon {
+            monitor: Mutex::new(monitor),
+            refreshing: Mutex::new(()),
+            streams: Mutex::new(Vec::new()),
+            metrics,
+        }
+    }
+
+    // A panic of another request while it was locked leaves the monitor with its last data,
+    // which is still worth serving
+    fn monitor(&self) -> MutexGuard<'_, Monitor> {
+        self.monitor.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn streams(&self) -> MutexGuard<'_, Vec<Sender<String>>> {
+        self.streams.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn status(&self) -> MonitorStatus {
+        MonitorStatus::of(&self.monitor(), views::unix_now())
+    }
+
+    fn records(&self) -> Vec<CreatorRecord> {
+        records::creator_records(&self.monitor().json_obj)
+    }
+
+    // Tells the event streams about the creators who went live or offline, then about the
+    // refresh itself. The monitor is only locked once the creators are fetched, requests are
+    // answered with the last data meanwhile.
+    fn refresh(&self) -> MonitorStatus {
+        let _refreshing = self
+            .refreshing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let fetch = self.monitor().fetch();
+        let fetched = fetch.run();
+        let mut monitor = self.monitor();
+        let before = records::creator_records(&monitor.json_obj);
+        if let Err(err) = monitor.apply_fetched(fetched) {
+            eprintln!("Couldn't read the watchlist, serving the last data: {err}");
+        }
+        let after = records::creator_records(&monitor.json_obj);
+        let status = MonitorStatus::of(&monitor, views::unix_now());
+        drop(monitor);
+
+        let messages: Vec<String> = records::live_changes(&before, &after)
+            .iter()
+            .map(|record| sse_message("live", record))
+            .chain([sse_message("refreshed", &status)])
+            .collect();
+        // The streams of clients that went away have dropped their receiver
+        self.streams().retain(|stream| {
+            messages
+                .iter()
+                .all(|message| stream.send(message.clone()).is_ok())
+        });
+        status
+    }
+
+    fn subscribe(&self) -> mpsc::Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        self.streams().push(sender);
+        receiver
+    }
+
+    // Status code and body of every route but Events, JSON but for Metrics
+    fn respond(&self, route: &Route) -> (u16, String) {
+        match route {
+            Route::Creators => json_body(&self.records()),
+            Route::Creator(id) => {
+                match self.records().into_iter().find(|record| record.id == *id) {
+                    Some(record) => json_body(&record),
+                    None => error_body(404, &format!("no creator with the id {id}")),
+                }
+            }
+            Route::Live => {
+                let live: Vec<CreatorRecord> = self
+                    .records()
+                    .into_iter()
+                    .filter(|record| record.is_live)
+                    .collect();
+                json_body(&live)
+            }
+            Route::Status => json_body(&self.status()),
+            Route::Refresh => json_body(&self.refresh()),
+            Route::Metrics if self.metrics => (
+                200,
+                metrics::format_metrics(&self.monitor(), views::unix_now()),
+            ),
+            Route::Events | Route::Metrics | Route::NotFound => error_body(404, "not found"),
+            Route::MethodNotAllowed => error_body(405, "method not allowed"),
+        }
+    }
+
+    fn poll(&self) {
+        loop {
+            let polling_interval = self.monitor().polling_interval();
+            thread::sleep(polling_interval);
+            self.refresh();
+        }
+    }
+}
+
+// Serves the watchlist until the process is stopped, refreshing it like the window does
+pub fn serve(
+    bind: &str,
+    watchlist_name: Option<&str>,
+    metrics: bool,
+) -> Result<(), Box<dyn Error>> {
+    let monitor = cli::open_monitor(watchlist_name)?;
+    let daemon = Arc::new(Daemon::new(monitor, metrics));
+    let status = daemon.refresh();
+    let server = Server::http(bind).map_err(|err| format!("couldn't listen on {bind}: {err}"))?;
+    eprintln!(
+        "Serving the watchlist {} on http://{bind}",
+        status.watchlist
+    );
+
+    let poller = Arc::clone(&daemon);
+    thread::spawn(move || poller.poll());
+    // Event streams stay open, every request gets its own thread
+    for request in server.incoming_requests() {
+        let daemon = Arc::clone(&daemon);
+        thread::spawn(move || handle(&daemon, request));
+    }
+    Ok(())
+}
+
+fn handle(daemon: &Daemon, request: Request) {
+    let route = route(request.method(), request.url());
+    if route == Route::Events {
+        return stream_events(daemon, request);
+    }
+    let (status_code, body) = daemon.respond(&route);
+    let content_type = match route {
+        // The Prometheus text format
+        Route::Metrics if status_code == 200 => "text/plain; version=0.0.4",
+        _ => "application/json",
+    };
+    let content_type = Header::from_bytes("Content-Type", content_type).unwrap();
+    let response = Response::from_string(body)
+        .with_status_code(status_code)
+        .with_header(content_type);
+    if let Err(err) = request.respond(response) {
+        eprintln!("Couldn't answer a request: {err}");
+    }
+}
+
+// Starts with the current status, then sends the messages of every refresh until the client
+// goes away
+fn stream_events(daemon: &Daemon, request: Request) {
+    let receiver = daemon.subscribe();
+    let mut writer = request.into_writer();
+    let mut message = EVENT_STREAM_HEAD.to_string() + &sse_message("refreshed", &daemon.status());
+    loop {
+        if writer
+            .write_all(message.as_bytes())
+            .and_then(|()| writer.flush())
+            .is_err()
+        {
+            return;
+        }
+        message = match receiver.recv_timeout(KEEP_ALIVE_INTERVAL) {
+            Ok(message) => message,
+            Err(RecvTimeoutError::Timeout) => ": keep-alive\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => return,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpStream;
+    use std::path::Path;
+    use yt_monitor_core::testing::temp_config_dir;
+
+    fn write_watchlist(config_dir: &Path, is_live_status: [&str; 2]) {
+        let json = serde_json::json!({
+            "names": ["Kush", "Jane Doe"],
+            "avatar_links": ["", ""],
+            "descriptions": ["", ""],
+            "subscribers": ["10", "20"],
+            "is_live_status": is_live_status,
+            "channel_ids": ["UC123", ""]
+        });
+        fs::write(
+            config_dir.join("watchlists").join("friends.json"),
+            json.to_string(),
+        )
+        .unwrap();
+    }
+
+    fn daemon(config_dir: &Path) -> Daemon {
+        let monitor = Monitor::open(config_dir.to_path_buf(), Some("friends")).unwrap();
+        let daemon = Daemon::new(monitor, false);
+        daemon.refresh();
+        daemon
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route(&Method::Get, "/creators"), Route::Creators);
+        assert_eq!(route(&Method::Get, "/live/?pretty"), Route::Live);
+        assert_eq!(
+            route(&Method::Get, "/creators/Jane%20Doe"),
+            Route::Creator("Jane Doe".to_string())
+        );
+        assert_eq!(route(&Method::Post, "/refresh"), Route::Refresh);
+        assert_eq!(route(&Method::Get, "/refresh"), Route::MethodNotAllowed);
+        assert_eq!(route(&Method::Delete, "/creators"), Route::MethodNotAllowed);
+        assert_eq!(
+            route(&Method::Get, "/creators/UC123/videos"),
+            Route::NotFound
+        );
+        assert_eq!(route(&Method::Get, "/"), Route::NotFound);
+    }
+
+    #[test]
+    fn test_respond() {
+        let config_dir = temp_config_dir("respond");
+        write_watchlist(&config_dir, ["false", "true"]);
+        let daemon = daemon(&config_dir);
+
+        let (status_code, body) = daemon.respond(&Route::Live);
+        assert_eq!(status_code, 200);
+        let live: Vec<CreatorRecord> = serde_json::from_str(&body).unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].id, "Jane Doe");
+
+        let (_, body) = daemon.respond(&Route::Creator("UC123".to_string()));
+        let record: CreatorRecord = serde_json::from_str(&body).unwrap();
+        assert_eq!(record.name, "Kush");
+        assert_eq!(record.subscribers, Some(10));
+        assert_eq!(daemon.respond(&Route::Creator("UC999".to_string())).0, 404);
+
+        let (_, body) = daemon.respond(&Route::Status);
+        let status: MonitorStatus = serde_json::from_str(&body).unwrap();
+        assert_eq!((status.total, status.live), (2, 1));
+        assert_eq!(status.watchlist, "friends");
+
+        // Only served with --metrics
+        assert_eq!(daemon.respond(&Route::Metrics).0, 404);
+        let daemon = Daemon {
+            metrics: true,
+            ..daemon
+        };
+        let (status_code, body) = daemon.respond(&Route::Metrics);
+        assert_eq!(status_code, 200);
+        assert!(body.contains("yt_creator_live{channel=\"UC123\",name=\"Kush\"} 0\n"));
+    }
+
+    #[test]
+    fn test_events_of_a_refresh() {
+        let config_dir = temp_config_dir("events");
+        write_watchlist(&config_dir, ["false", "false"]);
+        let daemon = Arc::new(daemon(&config_dir));
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let streamer = Arc::clone(&daemon);
+        thread::spawn(move || {
+            let request = server.recv().unwrap();
+            handle(&streamer, request);
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET /events HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut lines = BufReader::new(stream).lines().map(Result::unwrap);
+        assert_eq!(lines.next().unwrap(), "HTTP/1.1 200 OK");
+        let mut next_event = || {
+            let event = lines.find(|line| line.starts_with("event: ")).unwrap();
+            (event, lines.next().unwrap())
+        };
+        assert_eq!(next_event().0, "event: refreshed");
+
+        // Wait for the stream to be subscribed before refreshing
+        while daemon.streams().is_empty() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        write_watchlist(&config_dir, ["true", "false"]);
+        daemon.refresh();
+        let (event, data) = next_event();
+        assert_eq!(event, "event: live");
+        let record: CreatorRecord =
+            serde_json::from_str(data.strip_prefix("data: ").unwrap()).unwrap();
+        assert_eq!((record.id.as_str(), record.is_live), ("UC123", true));
+        assert_eq!(next_event().0, "event: refreshed");
+    }
+
+    #[test]
+    fn test_refresh_of_uneven_fields() {
+        let config_dir = temp_config_dir("uneven");
+        write_watchlist(&config_dir, ["false", "true"]);
+        let daemon = daemon(&config_dir);
+        fs::write(
+            config_dir.join("watchlists").join("friends.json"),
+            r#"{"names": ["Kush", "Tom"], "channel_ids": ["UC1"]}"#,
+        )
+        .unwrap();
+
+        // The last data is still served
+        let status = daemon.refresh();
+        assert_eq!((status.total, status.live), (2, 1));
+        assert!(status.last_error.is_some());
+        assert_eq!(daemon.respond(&Route::Creators).0, 200);
+    }
+}
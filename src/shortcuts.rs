@@ -0,0 +1,145 @@
+// Keys the app reacts to, translated from the window's keyboard events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyInput {
+    Char(char),
+    Left,
+    Right,
+    Up,
+    Down,
+    Enter,
+    Escape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusMove {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    MoveFocus(FocusMove),
+    OpenFocused,
+    FocusSearch,
+    CycleTheme,
+    CycleSort,
+    Refresh,
+    ToggleHelp,
+    CloseHelp,
+}
+
+// Shown in the "?" overlay, with the message id of the description
+pub const SHORTCUTS: [(&str, &str); 8] = [
+    ("← → ↑ ↓", "shortcut-move-focus"),
+    ("Enter", "shortcut-open"),
+    ("/", "shortcut-search"),
+    ("t", "shortcut-next-theme"),
+    ("s", "shortcut-next-sort"),
+    ("r", "shortcut-refresh"),
+    ("?", "shortcut-toggle-help"),
+    ("Esc", "shortcut-close-help"),
+];
+
+pub fn shortcut_for(key: KeyInput) -> Option<Shortcut> {
+    match key {
+        KeyInput::Left => Some(Shortcut::MoveFocus(FocusMove::Left)),
+        KeyInput::Right => Some(Shortcut::MoveFocus(FocusMove::Right)),
+        KeyInput::Up => Some(Shortcut::MoveFocus(FocusMove::Up)),
+        KeyInput::Down => Some(Shortcut::MoveFocus(FocusMove::Down)),
+        KeyInput::Enter => Some(Shortcut::OpenFocused),
+        KeyInput::Escape => Some(Shortcut::CloseHelp),
+        KeyInput::Char('/') => Some(Shortcut::FocusSearch),
+        KeyInput::Char('t') => Some(Shortcut::CycleTheme),
+        KeyInput::Char('s') => Some(Shortcut::CycleSort),
+        KeyInput::Char('r') => Some(Shortcut::Refresh),
+        KeyInput::Char('?') => Some(Shortcut::ToggleHelp),
+        KeyInput::Char(_) => None,
+    }
+}
+
+// Position of the focused card among count cards laid out columns per row. The first key
+// press focuses the first card, moving past an edge stays on the edge.
+pub fn move_focus(
+    focused: Option<usize>,
+    focus_move: FocusMove,
+    count: usize,
+    columns: usize,
+) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    let Some(focused) = focused else {
+        return Some(0);
+    };
+    // The cards might have been filtered out since
+    let focused = focused.min(count - 1);
+    let columns = columns.max(1);
+    Some(match focus_move {
+        FocusMove::Left => focused.saturating_sub(1),
+        FocusMove::Right => (focused + 1).min(count - 1),
+        FocusMove::Up if focused >= columns => focused - columns,
+        FocusMove::Down if focused + columns < count => focused + columns,
+        FocusMove::Up | FocusMove::Down => focused,
+    })
+}
+
+// The choice after current, wrapping around. The first one if current isn't a choice.
+pub fn next_choice<T: PartialEq + Clone>(choices: &[T], current: &T) -> Option<T> {
+    let next_idx = choices
+        .iter()
+        .position(|choice| choice == current)
+        .map_or(0, |idx| (idx + 1) % choices.len());
+    choices.get(next_idx).cloned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shortcut_for() {
+        assert_eq!(
+            shortcut_for(KeyInput::Down),
+            Some(Shortcut::MoveFocus(FocusMove::Down))
+        );
+        assert_eq!(
+            shortcut_for(KeyInput::Char('/')),
+            Some(Shortcut::FocusSearch)
+        );
+        assert_eq!(
+            shortcut_for(KeyInput::Char('?')),
+            Some(Shortcut::ToggleHelp)
+        );
+        assert_eq!(shortcut_for(KeyInput::Char('x')), None);
+    }
+
+    #[test]
+    fn test_move_focus_in_a_grid() {
+        // 7 cards, 3 per row
+        assert_eq!(move_focus(None, FocusMove::Right, 7, 3), Some(0));
+        assert_eq!(move_focus(Some(0), FocusMove::Left, 7, 3), Some(0));
+        assert_eq!(move_focus(Some(2), FocusMove::Right, 7, 3), Some(3));
+        assert_eq!(move_focus(Some(6), FocusMove::Right, 7, 3), Some(6));
+        assert_eq!(move_focus(Some(4), FocusMove::Up, 7, 3), Some(1));
+        assert_eq!(move_focus(Some(1), FocusMove::Up, 7, 3), Some(1));
+        assert_eq!(move_focus(Some(3), FocusMove::Down, 7, 3), Some(6));
+        assert_eq!(move_focus(Some(5), FocusMove::Down, 7, 3), Some(5));
+    }
+
+    #[test]
+    fn test_move_focus_after_cards_disappeared() {
+        assert_eq!(move_focus(Some(5), FocusMove::Left, 0, 3), None);
+        assert_eq!(move_focus(Some(9), FocusMove::Left, 4, 1), Some(2));
+    }
+
+    #[test]
+    fn test_next_choice_wraps_around() {
+        let choices = ["a", "b", "c"];
+        assert_eq!(next_choice(&choices, &"a"), Some("b"));
+        assert_eq!(next_choice(&choices, &"c"), Some("a"));
+        assert_eq!(next_choice(&choices, &"z"), Some("a"));
+        assert_eq!(next_choice(&[] as &[&str], &"a"), None);
+    }
+}
@@ -0,0 +1,629 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+use zbus::blocking::Connection;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use yt_monitor_core::i18n;
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const MENU_PATH: &str = "/MenuBar";
+const ITEM_INTERFACE: &str = "org.kde.StatusNotifierItem";
+const MENU_INTERFACE: &str = "com.canonical.dbusmenu";
+const WATCHER_DESTINATION: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+const WATCHER_INTERFACE: &str = "org.kde.StatusNotifierWatcher";
+// Standard freedesktop icon names, every icon theme has them
+const LIVE_ICON: &str = "media-record";
+const IDLE_ICON: &str = "video-display";
+
+// What a click in the tray asks the app to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayEvent {
+    ShowWindow,
+    Quit,
+    OpenChannel(String),
+}
+
+// The creators currently live (name, channel URL) and how many are watched
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrayModel {
+    pub live: Vec<(String, String)>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEntry {
+    Item {
+        label: String,
+        // None for the entries which can't be clicked
+        event: Option<TrayEvent>,
+    },
+    Separator,
+}
+
+impl TrayModel {
+    // "3 live", shown next to the icon by the hosts which support labels
+    pub fn title(&self) -> String {
+        i18n::tr_args("tray-title", &[("live", self.live.len().into())])
+    }
+
+    pub fn tooltip(&self) -> String {
+        i18n::tr_args(
+            "tray-tooltip",
+            &[
+                ("live", self.live.len().into()),
+                ("total", self.total.into()),
+            ],
+        )
+    }
+
+    pub fn icon_name(&self) -> &'static str {
+        if self.live.is_empty() {
+            IDLE_ICON
+        } else {
+            LIVE_ICON
+        }
+    }
+
+    // The live creators, then the window actions. Menu item ids are the positions + 1, 0 is
+    // the root of the menu.
+    pub fn menu(&self) -> Vec<MenuEntry> {
+        let creators = match self.live.is_empty() {
+            true => vec![MenuEntry::Item {
+                label: i18n::tr("tray-nobody-live"),
+                event: None,
+            }],
+            false => self
+                .live
+                .iter()
+                .map(|(name, channel_url)| MenuEntry::Item {
+                    label: name.clone(),
+                    event: Some(TrayEvent::OpenChannel(channel_url.clone())),
+                })
+                .collect(),
+        };
+        creators
+            .into_iter()
+            .chain([
+                MenuEntry::Separator,
+                MenuEntry::Item {
+                    label: i18n::tr("tray-show-window"),
+                    event: Some(TrayEvent::ShowWindow),
+                },
+                MenuEntry::Item {
+                    label: i18n::tr("tray-quit"),
+                    event: Some(TrayEvent::Quit),
+                },
+            ])
+            .collect()
+    }
+
+    pub fn menu_event(&self, id: i32) -> Option<TrayEvent> {
+        let position = usize::try_from(id).ok()?.checked_sub(1)?;
+        match self.menu().into_iter().nth(position)? {
+            MenuEntry::Item { event, .. } => event,
+            MenuEntry::Separator => None,
+        }
+    }
+}
+
+fn owned(value: Value<'_>) -> OwnedValue {
+    // Only file descriptors can't be owned, the menu has none
+    OwnedValue::try_from(value).unwrap_or_else(|_| OwnedValue::from(0u8))
+}
+
+// dbusmenu properties of an entry, underscores would be taken for keyboard accelerators
+fn menu_properties(entry: &MenuEntry) -> HashMap<String, OwnedValue> {
+    match entry {
+        MenuEntry::Item { label, event } => HashMap::from([
+            (
+                "label".to_string(),
+                owned(Value::from(label.replace('_', "__"))),
+            ),
+            ("enabled".to_string(), owned(Value::from(event.is_some()))),
+        ]),
+        MenuEntry::Separator => {
+            HashMap::from([("type".to_string(), owned(Value::from("separator")))])
+        }
+    }
+}
+
+type MenuLayout = (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>);
+
+// Icon name, icon pixmaps, title, description
+type ToolTip = (String, Vec<(i32, i32, Vec<u8>)>, String, String);
+
+fn menu_layout(model: &TrayModel) -> MenuLayout {
+    let children = model
+        .menu()
+        .iter()
+        .zip(1..)
+        .map(|(entry, id)| {
+            let child: MenuLayout = (id, menu_properties(entry), Vec::new());
+            owned(Value::from(child))
+        })
+        .collect();
+    let root_properties = HashMap::from([(
+        "children-display".to_string(),
+        owned(Value::from("submenu")),
+    )]);
+    (0, root_properties, children)
+}
+
+struct StatusNotifierItem {
+    model: Arc<Mutex<TrayModel>>,
+    events: Sender<TrayEvent>,
+}
+
+impl StatusNotifierItem {
+    fn model(&self) -> TrayModel {
+        self.model
+            .lock()
+            .map(|model| model.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[zbus::interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    // A left click brings the window back
+    fn activate(&self, _x: i32, _y: i32) {
+        let _ = self.events.send(TrayEvent::ShowWindow);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {}
+
+    // Hosts show the Menu themselves
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: &str) {}
+
+    #[zbus(property)]
+    fn category(&self) -> String {
+        "ApplicationStatus".to_string()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        "yt-monitor".to_string()
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        self.model().title()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "Active".to_string()
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        self.model().icon_name().to_string()
+    }
+
+    #[zbus(property)]
+    fn tool_tip(&self) -> ToolTip {
+        let model = self.model();
+        (
+            model.icon_name().to_string(),
+            Vec::new(),
+            model.title(),
+            model.tooltip(),
+        )
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(MENU_PATH).unwrap_or_default()
+    }
+
+    // Text next to the icon on the hosts of the Ayatana extension (Ubuntu's among others)
+    #[zbus(property, name = "XAyatanaLabel")]
+    fn x_ayatana_label(&self) -> String {
+        self.model().live.len().to_string()
+    }
+}
+
+struct DBusMenu {
+    model: Arc<Mutex<TrayModel>>,
+    events: Sender<TrayEvent>,
+    revision: Arc<Mutex<u32>>,
+}
+
+impl DBusMenu {
+    fn model(&self) -> TrayModel {
+        self.model
+            .lock()
+            .map(|model| model.clone())
+            .unwrap_or_default()
+    }
+
+    fn clicked(&self, id: i32) {
+        if let Some(event) = self.model().menu_event(id) {
+            let _ = self.events.send(event);
+        }
+    }
+}
+
+// The whole menu is always sent, it's a handful of items without submenus
+#[zbus::interface(name = "com.canonical.dbusmenu")]
+impl DBusMenu {
+    fn get_layout(
+        &self,
+        _parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> (u32, MenuLayout) {
+        let revision = self.revision.lock().map_or(0, |revision| *revision);
+        (revision, menu_layout(&self.model()))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+        let menu = self.model().menu();
+        ids.into_iter()
+            .filter_map(|id| {
+                let entry = menu.get(usize::try_from(id).ok()?.checked_sub(1)?)?;
+                Some((id, menu_properties(entry)))
+            })
+            .collect()
+    }
+
+    fn event(&self, id: i32, event_id: &str, _data: Value<'_>, _timestamp: u32) {
+        if event_id == "clicked" {
+            self.clicked(id);
+        }
+    }
+
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        for (id, event_id, _, _) in &events {
+            if event_id == "clicked" {
+                self.clicked(*id);
+            }
+        }
+        // No id errors
+        Vec::new()
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> String {
+        "ltr".to_string()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "normal".to_string()
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+// A StatusNotifierItem registered with the tray host, updated with set_model
+pub struct Tray {
+    connection: Connection,
+    model: Arc<Mutex<TrayModel>>,
+    menu_revision: Arc<Mutex<u32>>,
+}
+
+impl fmt::Debug for Tray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tray").field("model", &self.model).finish()
+    }
+}
+
+impl Tray {
+    // On the session bus, under the name the specification asks for
+    pub fn session(events: Sender<TrayEvent>) -> Result<Tray, Box<dyn Error>> {
+        let connection = Connection::session()?;
+        let service = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        connection.request_name(service.as_str())?;
+        Tray::start(connection, Some(WATCHER_DESTINATION), &service, events)
+    }
+
+    // Serves the item and its menu on connection, then asks the watcher to show it. Fails if
+    // there is no watcher, i.e. nothing to show tray icons.
+    pub fn start(
+        connection: Connection,
+        watcher_destination: Option<&str>,
+        service: &str,
+        events: Sender<TrayEvent>,
+    ) -> Result<Tray, Box<dyn Error>> {
+        let model = Arc::new(Mutex::new(TrayModel::default()));
+        let menu_revision = Arc::new(Mutex::new(0));
+        connection.object_server().at(
+            ITEM_PATH,
+            StatusNotifierItem {
+                model: Arc::clone(&model),
+                events: events.clone(),
+            },
+        )?;
+        connection.object_server().at(
+            MENU_PATH,
+            DBusMenu {
+                model: Arc::clone(&model),
+                events,
+                revision: Arc::clone(&menu_revision),
+            },
+        )?;
+        connection.call_method(
+            watcher_destination,
+            WATCHER_PATH,
+            Some(WATCHER_INTERFACE),
+            "RegisterStatusNotifierItem",
+            &(service,),
+        )?;
+        Ok(Tray {
+            connection,
+            model,
+            menu_revision,
+        })
+    }
+
+    // Tells the host to read the item and the menu again if anything changed
+    pub fn set_model(&self, model: TrayModel) -> Result<(), Box<dyn Error>> {
+        {
+            let mut current = self.model.lock().map_err(|err| err.to_string())?;
+            if *current == model {
+                return Ok(());
+            }
+            *current = model.clone();
+        }
+        let revision = {
+            let mut revision = self.menu_revision.lock().map_err(|err| err.to_string())?;
+            *revision += 1;
+            *revision
+        };
+        for signal in ["NewTitle", "NewIcon", "NewToolTip"] {
+            self.connection
+                .emit_signal(None::<()>, ITEM_PATH, ITEM_INTERFACE, signal, &())?;
+        }
+        self.connection.emit_signal(
+            None::<()>,
+            ITEM_PATH,
+            ITEM_INTERFACE,
+            "XAyatanaNewLabel",
+            &(model.live.len().to_string(), ""),
+        )?;
+        self.connection.emit_signal(
+            None::<()>,
+            MENU_PATH,
+            MENU_INTERFACE,
+            "LayoutUpdated",
+            &(revision, 0i32),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::time::Duration;
+
+    fn model() -> TrayModel {
+        TrayModel {
+            live: vec![(
+                "the_streamer".to_string(),
+                "https://www.youtube.com/channel/UC123".to_string(),
+            )],
+            total: 12,
+        }
+    }
+
+    #[test]
+    fn test_menu_lists_the_live_creators() {
+        let model = model();
+        assert_eq!(model.title(), "1 live");
+        assert_eq!(model.tooltip(), "1 of 12 creators live");
+        let menu = model.menu();
+        assert_eq!(menu.len(), 4);
+        assert_eq!(menu[1], MenuEntry::Separator);
+        assert_eq!(
+            model.menu_event(1),
+            Some(TrayEvent::OpenChannel(
+                "https://www.youtube.com/channel/UC123".to_string()
+            ))
+        );
+        assert_eq!(model.menu_event(2), None);
+        assert_eq!(model.menu_event(4), Some(TrayEvent::Quit));
+        assert_eq!(model.menu_event(0), None);
+        assert_eq!(model.menu_event(9), None);
+        // Underscores are doubled so they aren't taken for accelerators
+        assert_eq!(
+            menu_properties(&menu[0])["label"],
+            owned(Value::from("the__streamer"))
+        );
+    }
+
+    #[test]
+    fn test_nobody_live() {
+        let model = TrayModel {
+            live: Vec::new(),
+            total: 3,
+        };
+        assert_eq!(model.title(), "Nobody live");
+        assert_eq!(model.icon_name(), IDLE_ICON);
+        assert_eq!(
+            model.menu()[0],
+            MenuEntry::Item {
+                label: "Nobody is live".to_string(),
+                event: None
+            }
+        );
+        assert_eq!(model.menu_event(1), None);
+    }
+
+    // Stands in for the tray host, remembers the items registered
+    struct MockWatcher {
+        registered: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[zbus::interface(name = "org.kde.StatusNotifierWatcher")]
+    impl MockWatcher {
+        fn register_status_notifier_item(&self, service: &str) {
+            self.registered.lock().unwrap().push(service.to_string());
+        }
+    }
+
+    // A tray talking to a mock watcher over a private connection, no bus daemon needed. The
+    // watcher's end of the connection is returned to call the tray with.
+    fn start_tray() -> (
+        Tray,
+        Connection,
+        Arc<Mutex<Vec<String>>>,
+        Receiver<TrayEvent>,
+    ) {
+        let (watcher_stream, tray_stream) = UnixStream::pair().unwrap();
+        let registered = Arc::new(Mutex::new(Vec::new()));
+        let watcher = MockWatcher {
+            registered: Arc::clone(&registered),
+        };
+        let watcher_connection = std::thread::spawn(move || {
+            zbus::blocking::connection::Builder::async_io_unix_stream(watcher_stream)
+                .server(zbus::Guid::generate())
+                .unwrap()
+                .p2p()
+                .serve_at(WATCHER_PATH, watcher)
+                .unwrap()
+                .build()
+                .unwrap()
+        });
+        let tray_connection =
+            zbus::blocking::connection::Builder::async_io_unix_stream(tray_stream)
+                .p2p()
+                .build()
+                .unwrap();
+        let watcher_connection = watcher_connection.join().unwrap();
+        let (events, received) = channel();
+        let tray = Tray::start(tray_connection, None, ITEM_PATH, events).unwrap();
+        (tray, watcher_connection, registered, received)
+    }
+
+    fn get_property(
+        connection: &Connection,
+        path: &str,
+        interface: &str,
+        name: &str,
+    ) -> OwnedValue {
+        connection
+            .call_method(
+                None::<()>,
+                path,
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &(interface, name),
+            )
+            .unwrap()
+            .body()
+            .deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_registers_with_the_watcher() {
+        let (tray, watcher_connection, registered, _) = start_tray();
+        assert_eq!(registered.lock().unwrap().as_slice(), [ITEM_PATH]);
+
+        let title = get_property(&watcher_connection, ITEM_PATH, ITEM_INTERFACE, "Title");
+        assert_eq!(title, owned(Value::from("Nobody live")));
+        tray.set_model(model()).unwrap();
+        let title = get_property(&watcher_connection, ITEM_PATH, ITEM_INTERFACE, "Title");
+        assert_eq!(title, owned(Value::from("1 live")));
+        let label = get_property(
+            &watcher_connection,
+            ITEM_PATH,
+            ITEM_INTERFACE,
+            "XAyatanaLabel",
+        );
+        assert_eq!(label, owned(Value::from("1")));
+    }
+
+    #[test]
+    fn test_clicks_are_sent_to_the_app() {
+        let (tray, watcher_connection, _, received) = start_tray();
+        tray.set_model(model()).unwrap();
+        watcher_connection
+            .call_method(
+                None::<()>,
+                ITEM_PATH,
+                Some(ITEM_INTERFACE),
+                "Activate",
+                &(0i32, 0i32),
+            )
+            .unwrap();
+        let click = |id: i32| {
+            watcher_connection
+                .call_method(
+                    None::<()>,
+                    MENU_PATH,
+                    Some(MENU_INTERFACE),
+                    "Event",
+                    &(id, "clicked", Value::from(0i32), 0u32),
+                )
+                .unwrap();
+        };
+        click(1);
+        // The separator does nothing
+        click(2);
+        click(4);
+        let timeout = Duration::from_secs(5);
+        assert_eq!(received.recv_timeout(timeout), Ok(TrayEvent::ShowWindow));
+        assert_eq!(
+            received.recv_timeout(timeout),
+            Ok(TrayEvent::OpenChannel(
+                "https://www.youtube.com/channel/UC123".to_string()
+            ))
+        );
+        assert_eq!(received.recv_timeout(timeout), Ok(TrayEvent::Quit));
+    }
+
+    #[test]
+    fn test_menu_layout_over_dbus() {
+        let (tray, watcher_connection, _, _) = start_tray();
+        tray.set_model(model()).unwrap();
+        let reply = watcher_connection
+            .call_method(
+                None::<()>,
+                MENU_PATH,
+                Some(MENU_INTERFACE),
+                "GetLayout",
+                &(0i32, -1i32, Vec::<String>::new()),
+            )
+            .unwrap();
+        let (revision, (root_id, _, children)): (u32, MenuLayout) =
+            reply.body().deserialize().unwrap();
+        assert_eq!((revision, root_id, children.len()), (1, 0, 4));
+    }
+}
@@ -0,0 +1,512 @@
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use super::cli;
+use super::shortcuts::{self, KeyInput, Shortcut};
+use yt_monitor_core::i18n;
+use yt_monitor_core::model::{
+    views, AllowedFieldNamesForSorting, CardColors, CardFormat, CardScheme, ListColumn, ViewMode,
+};
+use yt_monitor_core::monitor::Monitor;
+use yt_monitor_core::themes::HexColor;
+use yt_monitor_core::watchlists::WatchlistSortOption;
+
+// Keys of the terminal only, shown after the window's in the help
+const TUI_SHORTCUTS: [(&str, &str); 3] = [
+    ("v", "tui-next-view"),
+    ("o", "tui-flip-order"),
+    ("q", "tui-quit"),
+];
+// Also how often the relative times are updated
+const TICK: Duration = Duration::from_secs(1);
+
+// The creators of the watchlist in the terminal, refreshed every polling interval like the
+// window. Takes over the terminal until "q" is pressed.
+pub fn run(watchlist_name: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut monitor = cli::open_monitor(watchlist_name)?;
+    monitor.refresh()?;
+    let mut terminal = ratatui::init();
+    let result = Tui::new(monitor).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+struct Tui {
+    monitor: Monitor,
+    focused: Option<usize>,
+    show_help: bool,
+    last_refresh: Instant,
+    // The last thing which went wrong outside of a refresh, e.g. opening a channel
+    notice: Option<String>,
+    quit: bool,
+}
+
+impl Tui {
+    fn new(monitor: Monitor) -> Tui {
+        Tui {
+            monitor,
+            focused: None,
+            show_help: false,
+            last_refresh: Instant::now(),
+            notice: None,
+            quit: false,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.handle_key(key.code, key.modifiers);
+                    }
+                }
+            }
+            if self.last_refresh.elapsed() >= self.monitor.polling_interval() {
+                self.refresh();
+            }
+        }
+        Ok(())
+    }
+
+    // Failures show up in the footer, with the last data still on screen
+    fn refresh(&mut self) {
+        let _ = self.monitor.refresh();
+        self.focused = clamp_focus(self.focused, self.monitor.live_status.len());
+        self.last_refresh = Instant::now();
+    }
+
+    fn view_mode(&self) -> ViewMode {
+        self.monitor.settings.view_mode
+    }
+
+    fn columns(&self) -> usize {
+        match self.view_mode() {
+            ViewMode::List => 1,
+            view_mode => view_mode.columns(self.monitor.settings.cards_per_row),
+        }
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(err) = self.monitor.settings.save(&self.monitor.config_dir) {
+            self.notice = Some(format!("Couldn't save the settings: {err}"));
+        }
+    }
+
+    fn set_sort_option(&mut self, sort_option: AllowedFieldNamesForSorting) {
+        let WatchlistSortOption {
+            sort_option: old_option,
+            sort_direction: old_direction,
+        } = self.monitor.sort_option;
+        let (sort_option, sort_direction) =
+            views::sort_after_header_click(old_option, old_direction, sort_option);
+        self.monitor.set_sort_option(WatchlistSortOption {
+            sort_option,
+            sort_direction,
+        });
+        if let Err(err) = self.monitor.save_sort_option() {
+            self.notice = Some(format!("Couldn't save the watchlists state: {err}"));
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        // Shown until the next key
+        self.notice = None;
+        match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('v') => {
+                let next_view_mode = shortcuts::next_choice(&ViewMode::ALL, &self.view_mode());
+                if let Some(view_mode) = next_view_mode {
+                    self.monitor.settings.view_mode = view_mode;
+                    self.save_settings();
+                }
+            }
+            // Sorting again by the current option flips it, like a click on a list header
+            KeyCode::Char('o') => self.set_sort_option(self.monitor.sort_option.sort_option),
+            _ => {
+                if let Some(shortcut) = key_input(code).and_then(shortcuts::shortcut_for) {
+                    self.apply(shortcut);
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, shortcut: Shortcut) {
+        match shortcut {
+            Shortcut::MoveFocus(focus_move) => {
+                self.focused = shortcuts::move_focus(
+                    self.focused,
+                    focus_move,
+                    self.monitor.live_status.len(),
+                    self.columns(),
+                );
+            }
+            Shortcut::OpenFocused => {
+                if let Some(idx) = self.focused {
+                    let channel_url = self.monitor.json_obj.channel_url(idx);
+                    if let Err(err) = open::that(&channel_url) {
+                        self.notice = Some(format!("Couldn't open {channel_url}: {err}"));
+                    }
+                }
+            }
+            // There's no search box in the terminal
+            Shortcut::FocusSearch => {}
+            Shortcut::CycleTheme => {
+                let theme_choices = self.monitor.theme_choices();
+                let next_theme =
+                    shortcuts::next_choice(&theme_choices, &self.monitor.settings.theme);
+                if let Some(theme) = next_theme {
+                    self.monitor.set_theme(theme);
+                    self.save_settings();
+                }
+            }
+            Shortcut::CycleSort => {
+                let next_sort_option = shortcuts::next_choice(
+                    &AllowedFieldNamesForSorting::ALL,
+                    &self.monitor.sort_option.sort_option,
+                );
+                if let Some(sort_option) = next_sort_option {
+                    self.set_sort_option(sort_option);
+                }
+            }
+            Shortcut::Refresh => self.refresh(),
+            Shortcut::ToggleHelp => self.show_help = !self.show_help,
+            Shortcut::CloseHelp => self.show_help = false,
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let format = CardFormat {
+            view_mode: self.view_mode(),
+            display_format: self.monitor.settings.display_format,
+            now: views::unix_now(),
+        };
+
+        let summary = self.monitor.refresh_status.header_summary(
+            self.monitor.live_count(),
+            self.monitor.live_status.len(),
+            format.now,
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    i18n::tr("app-title"),
+                    Style::new().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" · {summary}")),
+            ])),
+            header,
+        );
+
+        let scheme = self.monitor.card_scheme();
+        match format.view_mode {
+            ViewMode::List => self.draw_list(frame, body, &scheme, &format),
+            _ => self.draw_grid(frame, body, &scheme, &format),
+        }
+
+        let sort_option = self.monitor.sort_option;
+        let mut settings_line = format!(
+            "{} · {} ({}) · {} · ? {}",
+            self.monitor.settings.theme,
+            sort_option.sort_option,
+            sort_option.sort_direction,
+            format.view_mode,
+            i18n::tr("shortcut-toggle-help"),
+        );
+        // Over SSH the channel can't be opened here, the link can be copied instead
+        if let Some(idx) = self.focused {
+            settings_line += &format!(" · {}", self.monitor.json_obj.channel_url(idx));
+        }
+        let status_line = match &self.notice {
+            Some(notice) => notice.clone(),
+            None => self.monitor.refresh_status.footer_status(format.now),
+        };
+        frame.render_widget(
+            Paragraph::new(vec![Line::raw(status_line), Line::raw(settings_line)]),
+            footer,
+        );
+
+        if self.show_help {
+            draw_help(frame);
+        }
+    }
+
+    // Rows of cards, scrolled so that the focused one is visible
+    fn draw_grid(&self, frame: &mut Frame, area: Rect, scheme: &CardScheme, format: &CardFormat) {
+        let columns = self.columns().max(1);
+        let height = card_height(format.view_mode);
+        let visible_rows = usize::from((area.height / height).max(1));
+        let focused_row = self.focused.map_or(0, |idx| idx / columns);
+        let first_row = first_visible_row(focused_row, visible_rows);
+        let count = self.monitor.live_status.len();
+        let row_areas =
+            Layout::vertical(vec![Constraint::Length(height); visible_rows]).split(area);
+        for (row_area, row) in row_areas.iter().zip(first_row..) {
+            let card_areas =
+                Layout::horizontal(vec![Constraint::Ratio(1, columns as u32); columns])
+                    .split(*row_area);
+            for (card_area, idx) in card_areas.iter().zip(row * columns..count) {
+                frame.render_widget(self.card(idx, scheme, format), *card_area);
+            }
+        }
+    }
+
+    fn card(&self, idx: usize, scheme: &CardScheme, format: &CardFormat) -> Paragraph<'static> {
+        let field = |field_name: &str| {
+            self.monitor
+                .json_obj
+                .get_field(field_name)
+                .and_then(|values| values.get(idx))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let is_live = self.monitor.live_status[idx];
+        let colors = scheme.card_colors(is_live);
+        let mut block = Block::bordered()
+            .border_type(border_type(&colors, self.focused == Some(idx)))
+            .border_style(Style::new().fg(to_color(colors.border)))
+            .style(
+                Style::new()
+                    .fg(to_color(colors.text))
+                    .bg(to_color(colors.background)),
+            );
+        if is_live {
+            block = block.title(Span::styled(
+                format!(" {} ", i18n::tr("live-badge")),
+                Style::new()
+                    .fg(to_color(colors.badge_text))
+                    .bg(to_color(colors.badge_background))
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        let subscribers = views::format_subscribers(&field("subscribers"), format.display_format);
+        let mut lines = vec![Line::styled(
+            field("names"),
+            Style::new().add_modifier(Modifier::BOLD),
+        )];
+        if format.view_mode == ViewMode::Grid {
+            lines.push(Line::raw(field("descriptions")));
+        }
+        lines.push(Line::raw(i18n::tr_args(
+            "card-subscribers",
+            &[("subscribers", subscribers.into())],
+        )));
+        if format.view_mode == ViewMode::Grid {
+            if let Some(last_live) = views::format_last_live(&field("last_live"), is_live, format) {
+                lines.push(Line::raw(last_live));
+            }
+        }
+        Paragraph::new(lines).block(block)
+    }
+
+    // The columns of the window's list but the avatar
+    fn draw_list(&self, frame: &mut Frame, area: Rect, scheme: &CardScheme, format: &CardFormat) {
+        let columns: Vec<ListColumn> = ListColumn::ALL
+            .into_iter()
+            .filter(|column| *column != ListColumn::Avatar)
+            .collect();
+        let sort_option = self.monitor.sort_option;
+        let header = Row::new(
+            columns
+                .iter()
+                .map(|column| column.header(sort_option.sort_option, sort_option.sort_direction)),
+        )
+        .style(Style::new().add_modifier(Modifier::BOLD));
+        let json_obj = &self.monitor.json_obj;
+        let rows = self
+            .monitor
+            .live_status
+            .iter()
+            .enumerate()
+            .map(|(idx, is_live)| {
+                let colors = scheme.card_colors(*is_live);
+                Row::new(
+                    columns
+                        .iter()
+                        .map(|column| column.cell(json_obj, idx, format)),
+                )
+                .style(
+                    Style::new()
+                        .fg(to_color(colors.text))
+                        .bg(to_color(colors.background)),
+                )
+            });
+        let widths = columns
+            .iter()
+            .map(|column| Constraint::Fill(column.width_portion()));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut table_state = TableState::default().with_selected(self.focused);
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+}
+
+fn draw_help(frame: &mut Frame) {
+    let lines: Vec<Line> = shortcuts::SHORTCUTS
+        .into_iter()
+        .filter(|(key, _)| *key != "/")
+        .chain(TUI_SHORTCUTS)
+        .map(|(key, message_id)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{key:>8}  "),
+                    Style::new().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(i18n::tr(message_id)),
+            ])
+        })
+        .collect();
+    let area = centered(frame.area(), 60, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(i18n::tr("shortcuts-title"))),
+        area,
+    );
+}
+
+fn key_input(code: KeyCode) -> Option<KeyInput> {
+    match code {
+        KeyCode::Char(character) => Some(KeyInput::Char(character)),
+        KeyCode::Left => Some(KeyInput::Left),
+        KeyCode::Right => Some(KeyInput::Right),
+        KeyCode::Up => Some(KeyInput::Up),
+        KeyCode::Down => Some(KeyInput::Down),
+        KeyCode::Enter => Some(KeyInput::Enter),
+        KeyCode::Esc => Some(KeyInput::Escape),
+        _ => None,
+    }
+}
+
+fn to_color(color: HexColor) -> Color {
+    Color::Rgb(color.r, color.g, color.b)
+}
+
+// Lines of a card, borders included
+fn card_height(view_mode: ViewMode) -> u16 {
+    match view_mode {
+        ViewMode::CompactGrid => 4,
+        _ => 6,
+    }
+}
+
+// Live cards get the thick border the window gives them, the focused one a double one
+fn border_type(colors: &CardColors, is_focused: bool) -> BorderType {
+    match (
+        is_focused,
+        colors.border_width >= 3.0,
+        colors.border_radius > 0.0,
+    ) {
+        (true, _, _) => BorderType::Double,
+        (false, true, _) => BorderType::Thick,
+        (false, false, true) => BorderType::Rounded,
+        (false, false, false) => BorderType::Plain,
+    }
+}
+
+// The focus moves to the last card when the watchlist got shorter, and goes away with the
+// last card
+fn clamp_focus(focused: Option<usize>, card_count: usize) -> Option<usize> {
+    focused
+        .zip(card_count.checked_sub(1))
+        .map(|(idx, last_idx)| idx.min(last_idx))
+}
+
+// The grid scrolls just enough for the focused row to be the last one shown
+fn first_visible_row(focused_row: usize, visible_rows: usize) -> usize {
+    (focused_row + 1).saturating_sub(visible_rows.max(1))
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let (width, height) = (width.min(area.width), height.min(area.height));
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use yt_monitor_core::testing::temp_config_dir;
+
+    fn write_watchlist(config_dir: &Path, names: &[&str]) {
+        let json = serde_json::json!({
+            "names": names,
+            "avatar_links": vec![""; names.len()],
+            "descriptions": vec![""; names.len()],
+            "subscribers": vec!["10"; names.len()],
+            "is_live_status": vec!["false"; names.len()]
+        });
+        fs::write(
+            config_dir.join("watchlists").join("friends.json"),
+            json.to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_keys_are_the_window_shortcuts() {
+        assert_eq!(
+            key_input(KeyCode::Char('s')).and_then(shortcuts::shortcut_for),
+            Some(Shortcut::CycleSort)
+        );
+        assert_eq!(key_input(KeyCode::Esc), Some(KeyInput::Escape));
+        assert_eq!(key_input(KeyCode::Tab), None);
+    }
+
+    #[test]
+    fn test_first_visible_row() {
+        assert_eq!(first_visible_row(0, 3), 0);
+        assert_eq!(first_visible_row(2, 3), 0);
+        assert_eq!(first_visible_row(5, 3), 3);
+        // A terminal too small for a whole card still shows the focused row
+        assert_eq!(first_visible_row(5, 0), 5);
+    }
+
+    #[test]
+    fn test_focus_after_the_watchlist_shrank() {
+        let config_dir = temp_config_dir("tui-focus");
+        write_watchlist(&config_dir, &["Kush", "Tom", "Ana"]);
+        let mut tui = Tui::new(Monitor::open(config_dir.clone(), Some("friends")).unwrap());
+        tui.refresh();
+        tui.focused = Some(2);
+
+        write_watchlist(&config_dir, &["Kush"]);
+        tui.refresh();
+        assert_eq!(tui.focused, Some(0));
+
+        write_watchlist(&config_dir, &[]);
+        tui.refresh();
+        assert_eq!(tui.focused, None);
+    }
+
+    #[test]
+    fn test_centered() {
+        let area = Rect::new(0, 0, 100, 30);
+        assert_eq!(centered(area, 60, 10), Rect::new(20, 10, 60, 10));
+        assert_eq!(
+            centered(Rect::new(0, 0, 40, 5), 60, 10),
+            Rect::new(0, 0, 40, 5)
+        );
+    }
+}
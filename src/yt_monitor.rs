@@ -1,40 +1,139 @@
 use iced::theme::{self, Theme};
-use iced::widget::{column, container, horizontal_rule, image, radio, row, text};
-use iced::{Color, Length, Renderer, Sandbox};
+use iced::widget::{
+    button, checkbox, column, container, horizontal_rule, image, pick_list, radio, row, scrollable,
+    slider, text, text_input, Column, Row,
+};
+use iced::{executor, Application, Command, Length, Renderer, Subscription};
+use iced_native::keyboard;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use self::render_cards::AllowedFieldNamesForSorting;
-#[path = "render_cards.rs"]
-mod render_cards;
+use super::animations::Animations;
+use super::notifications::UpcomingNotices;
+use super::oauth::{AccountImport, AccountImportUpdate};
+use super::render_cards::{
+    sources, views, AllowedFieldNamesForSorting, CardFormat, CardScheme, DisplayFormat,
+    SortDirection, ThemeType, ViewMode,
+};
+use super::shortcuts::{KeyInput, Shortcut};
+use super::tray::{Tray, TrayEvent, TrayModel};
+use super::{animations, notifications, oauth, render_cards, shortcuts, tray};
+use yt_monitor_core::appearance::ColorScheme;
+use yt_monitor_core::client::{Connection, ConnectionStatus, DaemonClient, DaemonUpdate};
+use yt_monitor_core::config::{StaticText, UserSettings};
+use yt_monitor_core::export::{ExportColumn, ExportFormat};
+use yt_monitor_core::i18n::Language;
+use yt_monitor_core::records::{CreatorRecord, MonitorStatus};
+use yt_monitor_core::status::RefreshStatus;
+use yt_monitor_core::themes::{CustomTheme, ThemeColorField, ThemePalette};
+use yt_monitor_core::watchlists::{Watchlist, WatchlistSortOption, WatchlistsState};
+use yt_monitor_core::{
+    appearance, client, config, export, i18n, import, records, themes, watchlists,
+};
 
 #[derive(Default, Debug)]
 pub struct YTMonitor {
     theme: Theme,
     json_obj: render_cards::YTCreator,
+    // The avatars of json_obj, in its order
     loaded_photos: Vec<image::Handle>,
+    // Every avatar downloaded so far, by link
+    avatars: HashMap<String, image::Handle>,
     live_status: Vec<bool>,
     sort_option: AllowedFieldNamesForSorting,
+    sort_direction: SortDirection,
+    filter: render_cards::CreatorFilter,
+    // Indices into json_obj of the creators passing the filter, in json_obj's (sorted) order
+    visible_indices: Vec<usize>,
+    // Show one (collapsible) section per tag instead of a single grid
+    group_by_tag: bool,
+    collapsed_tags: Vec<String>,
+    watchlists: Vec<Watchlist>,
+    active_watchlist: Watchlist,
+    watchlists_state: WatchlistsState,
+    settings: UserSettings,
+    custom_themes: Vec<CustomTheme>,
+    theme_editor: Option<ThemeEditor>,
+    // Last preference read from the desktop, only used by the Auto theme
+    color_scheme: ColorScheme,
+    animations: Animations,
+    // Position (in visible_indices) of the card selected with the arrow keys
+    focused: Option<usize>,
+    show_shortcuts: bool,
+    refresh_status: RefreshStatus,
+    // A watchlist is being read and fetched from the API. One fetch at a time, so that the
+    // quota isn't counted twice.
+    is_fetching: bool,
+    // Chosen during a fetch, loaded once it's done
+    next_watchlist: Option<Watchlist>,
+    // None if there is nothing to show tray icons, closing the window quits then
+    tray: Option<Tray>,
+    tray_events: Option<Arc<Mutex<Receiver<TrayEvent>>>>,
+    // What is typed in the daemon URL input, only used once submitted
+    daemon_url_input: String,
+    daemon_status: ConnectionStatus,
+    export_panel: Option<ExportPanel>,
+    import_panel: Option<ImportPanel>,
+    upcoming_notices: UpcomingNotices,
 }
 
-// TODO: Make two separable users for female and males
-// Not possible for YouTube but worth having for the library (later)
-const DEFAULT_IMAGE_URL: &str = "https://www.w3schools.com/howto/img_avatar.png";
+// Channels of a subscriptions export, picked one by one to go into the active watchlist
+#[derive(Debug, Default, Clone)]
+pub struct ImportPanel {
+    path: String,
+    // The channels of the file which aren't in the watchlist yet, and whether they're ticked
+    channels: Vec<(import::Subscription, bool)>,
+    // Channels of the file the watchlist already has
+    known: usize,
+    // How many more creators the watchlist file can hold
+    free_slots: usize,
+    // While signing in and paging through the account's subscriptions
+    fetching_account: bool,
+    // The ticked channels while they're added to the watchlist file
+    importing: Vec<import::Subscription>,
+    // How the last load or import went
+    outcome: Option<String>,
+}
+
+// The format and the columns are settings, they stay the same from one export to the next
+#[derive(Debug, Clone)]
+pub struct ExportPanel {
+    path: String,
+    // How the last save or copy went
+    outcome: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThemeEditor {
+    theme: CustomTheme,
+    // What is typed in every color input, which might not be a valid color (yet)
+    color_inputs: HashMap<ThemeColorField, String>,
+}
+
+impl ThemeEditor {
+    fn new(theme: CustomTheme) -> ThemeEditor {
+        let color_inputs = ThemeColorField::ALL
+            .iter()
+            .map(|field| {
+                let color = field.get(&theme).map(|color| color.to_string());
+                (*field, color.unwrap_or_default())
+            })
+            .collect();
+        ThemeEditor {
+            theme,
+            color_inputs,
+        }
+    }
+}
 
 // TODO: Unused for now but can come handy later
 fn default_img_handle(total_count: usize) -> Vec<image::Handle> {
     // TODO: Later on, accept gender as well
-    let mut default_handles: Vec<image::Handle> = Vec::new();
-    for _ in 0..total_count {
-        let img_handle_link = DEFAULT_IMAGE_URL;
-        let img_obj = reqwest::blocking::get(img_handle_link).ok();
-        let img_bytes = match img_obj {
-            Some(bytes) => bytes.bytes().ok(),
-            None => None,
-        }
-        .unwrap();
-        let out_img: image::Handle = image::Handle::from_memory(img_bytes.to_vec());
-        default_handles.push(out_img);
-    }
-    default_handles
+    vec![render_cards::placeholder_avatar(); total_count]
 }
 
 // Reference: https://stackoverflow.com/a/69774341
@@ -55,108 +154,1795 @@ pub fn rearrange_with_indices<T>(field: &mut Vec<T>, mut sorted_indices: Vec<usi
     }
 }
 
-pub fn update_json_obj(obj: &mut YTMonitor, old_option: &AllowedFieldNamesForSorting) {
+pub fn update_json_obj(
+    obj: &mut YTMonitor,
+    old_option: &AllowedFieldNamesForSorting,
+    old_direction: &SortDirection,
+) {
+    let before = visible_cards(obj);
     // Don't do any reordering if the same option is chosen again...
-    if old_option == &obj.sort_option {
+    let reordered = old_option != &obj.sort_option || old_direction != &obj.sort_direction;
+    if reordered {
+        let sorted_json_obj_with_indices = obj
+            .json_obj
+            .sort_by_direction(obj.sort_option, obj.sort_direction)
+            .unwrap();
+        let sorted_json_obj = sorted_json_obj_with_indices.0;
+        obj.json_obj = sorted_json_obj;
+        let sorted_indices = sorted_json_obj_with_indices.1;
+        rearrange_with_indices::<bool>(&mut obj.live_status, sorted_indices.clone());
+        rearrange_with_indices::<iced_native::image::Handle>(
+            &mut obj.loaded_photos,
+            sorted_indices,
+        );
+    }
+    // Filters only decide what is visible, the (sorted) data itself is never dropped
+    obj.visible_indices = obj.filter.visible_indices(&obj.json_obj);
+    // Cards moving because of a filter aren't highlighted, only a new order is
+    if reordered {
+        animate_changes(obj, &before);
+    }
+}
+
+// Name and live status of the cards on screen, in order
+pub fn visible_cards(obj: &YTMonitor) -> Vec<(String, bool)> {
+    obj.visible_indices
+        .iter()
+        .map(|idx| (creator_name(obj, *idx).to_string(), obj.live_status[*idx]))
+        .collect()
+}
+
+fn creator_name(obj: &YTMonitor, idx: usize) -> &str {
+    obj.json_obj
+        .get_field("names")
+        .and_then(|names| names.get(idx))
+        .map_or("", String::as_str)
+}
+
+pub fn animate_changes(obj: &mut YTMonitor, before: &[(String, bool)]) {
+    if obj.settings.animations_enabled {
+        let after = visible_cards(obj);
+        obj.animations.note_changes(before, &after, Instant::now());
+    }
+}
+
+// The avatars of the creators at the given indices into obj.json_obj
+fn select_photos(obj: &YTMonitor, indices: &[usize]) -> Vec<image::Handle> {
+    indices
+        .iter()
+        .map(|idx| obj.loaded_photos[*idx].clone())
+        .collect()
+}
+
+// The focused card is outlined the same way as a card which just moved
+fn card_animations(obj: &YTMonitor, indices: &[usize]) -> Vec<render_cards::CardAnimation> {
+    let focused_creator = focused_creator(obj);
+    indices
+        .iter()
+        .map(|idx| {
+            let animation = match obj.settings.animations_enabled {
+                true => obj
+                    .animations
+                    .card_animation(creator_name(obj, *idx), obj.live_status[*idx]),
+                false => render_cards::CardAnimation::default(),
+            };
+            match focused_creator == Some(*idx) {
+                true => render_cards::CardAnimation {
+                    highlight: 1.0,
+                    ..animation
+                },
+                false => animation,
+            }
+        })
+        .collect()
+}
+
+// Indices (in json_obj) of the cards in the order they're on screen
+fn focus_order(obj: &YTMonitor) -> Vec<usize> {
+    match obj.group_by_tag {
+        true => obj
+            .json_obj
+            .tag_sections_order(&obj.visible_indices, &obj.collapsed_tags),
+        false => obj.visible_indices.clone(),
+    }
+}
+
+// Index (in json_obj) of the creator selected with the arrow keys
+pub fn focused_creator(obj: &YTMonitor) -> Option<usize> {
+    obj.focused
+        .and_then(|position| focus_order(obj).get(position).copied())
+}
+
+// Cards per row as far as the arrow keys are concerned, tag sections and the list view
+// are navigated as one long list
+fn focus_columns(obj: &YTMonitor) -> usize {
+    match obj.group_by_tag || obj.settings.view_mode == ViewMode::List {
+        true => 1,
+        false => obj.settings.view_mode.columns(obj.settings.cards_per_row),
+    }
+}
+
+fn search_input_id() -> text_input::Id {
+    text_input::Id::new("search")
+}
+
+fn key_input(event: keyboard::Event) -> Option<KeyInput> {
+    match event {
+        keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        } if modifiers.is_empty() => match key_code {
+            keyboard::KeyCode::Left => Some(KeyInput::Left),
+            keyboard::KeyCode::Right => Some(KeyInput::Right),
+            keyboard::KeyCode::Up => Some(KeyInput::Up),
+            keyboard::KeyCode::Down => Some(KeyInput::Down),
+            keyboard::KeyCode::Enter => Some(KeyInput::Enter),
+            keyboard::KeyCode::Escape => Some(KeyInput::Escape),
+            _ => None,
+        },
+        keyboard::Event::CharacterReceived(character) => Some(KeyInput::Char(character)),
+        _ => None,
+    }
+}
+
+// Dark, Light, ... first, then the ones loaded from the themes directory
+pub fn theme_choices(obj: &YTMonitor) -> Vec<ThemeType> {
+    themes::theme_choices(&obj.custom_themes)
+}
+
+pub fn create_shortcuts_help() -> Column<'static, render_cards::Message, Renderer> {
+    shortcuts::SHORTCUTS.iter().fold(
+        column![text(i18n::tr("shortcuts-title")).size(28)]
+            .spacing(10)
+            .padding(20),
+        |column, (keys, action)| {
+            column.push(
+                row![
+                    text(*keys).width(Length::Fixed(120.0)),
+                    text(i18n::tr(action))
+                ]
+                .spacing(20),
+            )
+        },
+    )
+}
+
+pub fn create_list(
+    obj: &YTMonitor,
+    indices: &[usize],
+) -> Column<'static, render_cards::Message, Renderer> {
+    render_cards::create_list_view(
+        &obj.json_obj.select(indices),
+        &select_photos(obj, indices),
+        &card_scheme(obj),
+        &card_animations(obj, indices),
+        obj.sort_option,
+        obj.sort_direction,
+        &card_format(obj),
+    )
+}
+
+// Rows of cards (cards_per_row per row) for the creators at the given indices into obj.json_obj.
+// Empty rows are added to reach min_rows, so that a few cards don't stretch over the whole window.
+pub fn create_grid(
+    obj: &YTMonitor,
+    indices: &[usize],
+    row_height: Length,
+    min_rows: usize,
+) -> Column<'static, render_cards::Message, Renderer> {
+    let json_obj = obj.json_obj.select(indices);
+    let format = card_format(obj);
+    let cards_per_row = format.view_mode.columns(obj.settings.cards_per_row);
+    let mut all_cards = render_cards::create_list_of_cards(&json_obj, cards_per_row);
+    while all_cards.len() < min_rows {
+        all_cards.push(render_cards::ListOfCards::default());
+    }
+    let all_photos = select_photos(obj, indices);
+    let all_status: Vec<bool> = indices.iter().map(|idx| obj.live_status[*idx]).collect();
+    let scheme = card_scheme(obj);
+    let all_animations = card_animations(obj, indices);
+
+    Column::with_children(
+        all_cards
+            .iter()
+            .enumerate()
+            .map(|(row_idx, cards)| {
+                render_cards::create_row(
+                    cards,
+                    &all_photos,
+                    row_idx * cards_per_row,
+                    &scheme,
+                    &all_status,
+                    &all_animations,
+                    &format,
+                )
+                .height(row_height)
+                .into()
+            })
+            .collect(),
+    )
+}
+
+// One section per tag with a header to collapse/expand it, see YTCreator::group_by_tag
+pub fn create_tag_sections(obj: &YTMonitor) -> Column<'static, render_cards::Message, Renderer> {
+    Column::with_children(
+        obj.json_obj
+            .group_by_tag(&obj.visible_indices)
+            .into_iter()
+            .map(|(tag, indices)| {
+                let is_collapsed = obj.collapsed_tags.contains(&tag);
+                let tag_name = if tag.is_empty() {
+                    i18n::tr("untagged")
+                } else {
+                    tag.clone()
+                };
+                let section_title = format!(
+                    "{} {}",
+                    if is_collapsed { "▶" } else { "▼" },
+                    i18n::tr_args(
+                        "tag-section",
+                        &[("tag", tag_name.into()), ("count", indices.len().into())]
+                    )
+                );
+                let header = button(text(section_title))
+                    .style(theme::Button::Text)
+                    .on_press(render_cards::Message::TagSectionToggled(tag));
+                let view_mode = obj.settings.view_mode;
+                if is_collapsed {
+                    column![header].into()
+                } else if view_mode == ViewMode::List {
+                    column![header, create_list(obj, &indices)].into()
+                } else {
+                    let row_height = Length::Fixed(view_mode.section_row_height());
+                    column![header, create_grid(obj, &indices, row_height, 0)].into()
+                }
+            })
+            .collect(),
+    )
+    .spacing(10)
+}
+
+// The creators passing the filter with a stream coming up, the soonest first. Empty when
+// nobody has one.
+pub fn create_upcoming_section(
+    obj: &YTMonitor,
+) -> Column<'static, render_cards::Message, Renderer> {
+    let format = card_format(obj);
+    let upcoming = obj.json_obj.upcoming(&obj.visible_indices, format.now);
+    if upcoming.is_empty() {
+        return column![];
+    }
+    let section_title = i18n::tr_args("upcoming-section", &[("count", upcoming.len().into())]);
+    upcoming.into_iter().fold(
+        column![text(section_title)].spacing(5).padding(10),
+        |section, idx| match obj.json_obj.scheduled_start_of(idx, format.now) {
+            Some(scheduled_start) => section.push(text(format!(
+                "{}: {}",
+                creator_name(obj, idx),
+                views::format_scheduled_start(scheduled_start, &format)
+            ))),
+            None => section,
+        },
+    )
+}
+
+// A desktop notification for every stream starting within the chosen time, sent from
+// another thread so that a slow notification server doesn't hold the window up
+fn announce_upcoming(obj: &mut YTMonitor) {
+    let Some(lead_secs) = obj.settings.upcoming_notice.lead_secs() else {
         return;
+    };
+    let format = card_format(obj);
+    let due = obj
+        .upcoming_notices
+        .due(&obj.json_obj, format.now, lead_secs);
+    for (idx, scheduled_start) in due {
+        let summary = i18n::tr_args(
+            "notification-upcoming",
+            &[("name", creator_name(obj, idx).into())],
+        );
+        let body = views::format_scheduled_start(scheduled_start, &format);
+        std::thread::spawn(move || {
+            if let Err(err) = notifications::notify(&summary, &body) {
+                eprintln!("Couldn't show the notification: {err}");
+            }
+        });
+    }
+}
+
+// Replaces the shown creators with the ones of the given watchlist once it's read, the
+// current ones stay on screen until then
+pub fn load_watchlist(obj: &mut YTMonitor, watchlist: Watchlist) -> Command<render_cards::Message> {
+    match obj.settings.daemon_url {
+        // The creators arrive with the daemon's first event
+        Some(_) => show_watchlist(obj, watchlist, render_cards::YTCreator::default()),
+        None if obj.is_fetching => {
+            obj.next_watchlist = Some(watchlist);
+            Command::none()
+        }
+        None => fetch_watchlist(obj, watchlist, render_cards::Message::WatchlistLoaded),
+    }
+}
+
+// Re-reads the active watchlist (live statuses, subscribers, ... might have changed since).
+// A fetch already running brings fresh data too.
+pub fn refresh_watchlist(obj: &mut YTMonitor) -> Command<render_cards::Message> {
+    if obj.is_fetching {
+        return Command::none();
+    }
+    let watchlist = obj.active_watchlist.clone();
+    fetch_watchlist(obj, watchlist, render_cards::Message::WatchlistRefreshed)
+}
+
+// A watchlist file read and brought up to date from the YouTube Data API
+#[derive(Debug, Clone)]
+pub struct FetchedWatchlist {
+    watchlist: Watchlist,
+    // Err if the file couldn't be read
+    json_obj: Result<render_cards::YTCreator, String>,
+    quota: sources::QuotaUsage,
+    // None without an API key
+    api_result: Option<Result<(), String>>,
+}
+
+// The file and the requests block, so they're kept off the UI thread
+fn fetch_watchlist(
+    obj: &mut YTMonitor,
+    watchlist: Watchlist,
+    on_fetched: fn(Box<FetchedWatchlist>) -> render_cards::Message,
+) -> Command<render_cards::Message> {
+    obj.is_fetching = true;
+    let quota = obj.refresh_status.quota;
+    Command::perform(
+        async move {
+            let fetched_watchlist = watchlist.clone();
+            tokio::task::spawn_blocking(move || read_watchlist(fetched_watchlist, quota))
+                .await
+                .unwrap_or_else(|err| FetchedWatchlist {
+                    watchlist,
+                    json_obj: Err(err.to_string()),
+                    quota,
+                    api_result: None,
+                })
+        },
+        move |fetched| on_fetched(Box::new(fetched)),
+    )
+}
+
+// What couldn't be fetched from the API keeps the file's values
+fn read_watchlist(watchlist: Watchlist, mut quota: sources::QuotaUsage) -> FetchedWatchlist {
+    let (json_obj, api_result) = match render_cards::read_json(&watchlist.path.to_string_lossy()) {
+        Ok(mut json_obj) => {
+            let now = views::unix_now();
+            let api_result =
+                sources::update_from_api(&mut json_obj, &mut quota, &config::config_dir(), now);
+            let api_result = api_result.map(|result| result.map_err(|err| err.to_string()));
+            (Ok(json_obj), api_result)
+        }
+        Err(err) => (Err(err.to_string()), None),
+    };
+    FetchedWatchlist {
+        watchlist,
+        json_obj,
+        quota,
+        api_result,
+    }
+}
+
+// Takes the outcome of a fetch into the footer. None if the file couldn't be read, the
+// current creators stay on screen then.
+fn fetched_creators(
+    obj: &mut YTMonitor,
+    fetched: FetchedWatchlist,
+) -> Option<render_cards::YTCreator> {
+    obj.is_fetching = false;
+    obj.refresh_status.quota = fetched.quota;
+    let json_obj = match fetched.json_obj {
+        Ok(json_obj) => json_obj,
+        Err(err) => {
+            eprintln!(
+                "Couldn't read the watchlist {}: {err}",
+                fetched.watchlist.name
+            );
+            obj.refresh_status.failed(err);
+            return None;
+        }
+    };
+    match fetched.api_result {
+        None | Some(Ok(())) => obj.refresh_status.succeeded(views::unix_now()),
+        Some(Err(err)) => {
+            eprintln!("Couldn't fetch from the YouTube API: {err}");
+            obj.refresh_status.failed(err);
+        }
+    }
+    Some(json_obj)
+}
+
+// The watchlist chosen during the last fetch
+fn fetch_next_watchlist(obj: &mut YTMonitor) -> Command<render_cards::Message> {
+    match obj.next_watchlist.take() {
+        Some(watchlist) => load_watchlist(obj, watchlist),
+        None => Command::none(),
+    }
+}
+
+// Replaces the shown creators with the ones of the given watchlist, sorted the way this
+// list was sorted last time.
+fn show_watchlist(
+    obj: &mut YTMonitor,
+    watchlist: Watchlist,
+    json_obj: render_cards::YTCreator,
+) -> Command<render_cards::Message> {
+    let sort_option = obj
+        .watchlists_state
+        .sort_option_of(&watchlist.name)
+        .unwrap_or(obj.settings.default_sort);
+    let sorted_json_obj = json_obj
+        .sort_by_direction(sort_option.sort_option, sort_option.sort_direction)
+        .unwrap()
+        .0;
+    obj.live_status = render_cards::get_live_status(sorted_json_obj.get_field("is_live_status"));
+    obj.visible_indices = obj.filter.visible_indices(&sorted_json_obj);
+    obj.json_obj = sorted_json_obj;
+    obj.sort_option = sort_option.sort_option;
+    obj.sort_direction = sort_option.sort_direction;
+    // Reopening the list shown last time changes nothing to remember
+    let is_switch = obj.watchlists_state.active.as_ref() != Some(&watchlist.name);
+    obj.watchlists_state.active = Some(watchlist.name.clone());
+    obj.active_watchlist = watchlist;
+    if is_switch {
+        save_watchlists_state(obj);
     }
-    let sorted_json_obj_with_indices = obj.json_obj.sort_by(obj.sort_option).unwrap();
-    let sorted_json_obj = sorted_json_obj_with_indices.0;
+    update_tray(obj);
+    show_avatars(obj)
+}
+
+// Replaces the shown creators with json_obj, keeping the current sort option and filters.
+// Avatars are only downloaded for new links.
+fn show_creators(
+    obj: &mut YTMonitor,
+    json_obj: render_cards::YTCreator,
+) -> Command<render_cards::Message> {
+    let before = visible_cards(obj);
+    let sorted_json_obj = json_obj
+        .sort_by_direction(obj.sort_option, obj.sort_direction)
+        .unwrap()
+        .0;
+    obj.live_status = render_cards::get_live_status(sorted_json_obj.get_field("is_live_status"));
+    obj.visible_indices = obj.filter.visible_indices(&sorted_json_obj);
     obj.json_obj = sorted_json_obj;
-    let sorted_indices = sorted_json_obj_with_indices.1;
-    rearrange_with_indices::<bool>(&mut obj.live_status, sorted_indices.clone());
-    rearrange_with_indices::<iced_native::image::Handle>(&mut obj.loaded_photos, sorted_indices);
+    animate_changes(obj, &before);
+    update_tray(obj);
+    show_avatars(obj)
 }
 
-impl Sandbox for YTMonitor {
-    type Message = render_cards::Message;
+// The placeholder stands in for the avatars being downloaded on a blocking thread
+fn show_avatars(obj: &mut YTMonitor) -> Command<render_cards::Message> {
+    obj.loaded_photos = render_cards::get_all_avatars(&obj.json_obj, &obj.avatars);
+    let new_links: Vec<String> = obj
+        .json_obj
+        .get_field("avatar_links")
+        .into_iter()
+        .flatten()
+        .filter(|link| !link.is_empty() && !obj.avatars.contains_key(*link))
+        .cloned()
+        .collect();
+    if new_links.is_empty() {
+        return Command::none();
+    }
+    Command::perform(
+        async move {
+            tokio::task::spawn_blocking(move || render_cards::download_avatars(&new_links))
+                .await
+                .unwrap_or_default()
+        },
+        render_cards::Message::AvatarsDownloaded,
+    )
+}
+
+// The daemon refreshes on its own, its status stands for the refreshes of the window
+fn show_daemon_creators(
+    obj: &mut YTMonitor,
+    creators: &[CreatorRecord],
+    status: MonitorStatus,
+) -> Command<render_cards::Message> {
+    obj.refresh_status.last_refreshed = status.last_refreshed;
+    obj.refresh_status.last_error = status.last_error;
+    obj.refresh_status.uses_api = false;
+    show_creators(obj, records::creators_from_records(creators))
+}
+
+// The new creators come through the event stream, a daemon busy refreshing mustn't block
+// the window
+fn refresh_daemon(daemon_url: &str) {
+    let daemon_url = daemon_url.to_string();
+    std::thread::spawn(move || {
+        if let Err(err) = DaemonClient::new(&daemon_url).and_then(|client| client.refresh()) {
+            eprintln!("Couldn't ask the daemon to refresh: {err}");
+        }
+    });
+}
+
+// Waits for the daemon on a blocking thread, the connection is handed on from one update
+// to the next. A new URL is a new subscription, which drops the old connection.
+fn daemon_updates(daemon_url: String) -> Subscription<render_cards::Message> {
+    iced::subscription::unfold(
+        ("daemon-updates", daemon_url.clone()),
+        Connection::default(),
+        move |connection| {
+            let daemon_url = daemon_url.clone();
+            async move {
+                let (update, connection) = tokio::task::spawn_blocking(move || {
+                    client::next_update(&daemon_url, connection)
+                })
+                .await
+                .unwrap_or_else(|err| {
+                    (
+                        DaemonUpdate::Disconnected {
+                            error: err.to_string(),
+                            retry_in: client::reconnect_delay(1),
+                        },
+                        Connection::Connecting { attempt: 1 },
+                    )
+                });
+                (Some(render_cards::Message::Daemon(update)), connection)
+            }
+        },
+    )
+}
+
+// Needs a StatusNotifierWatcher on the session bus: KDE, GNOME with the AppIndicator
+// extension, most other desktops' panels
+fn start_tray() -> Option<(Tray, Receiver<TrayEvent>)> {
+    let (events, received) = mpsc::channel();
+    match Tray::session(events) {
+        Ok(tray) => Some((tray, received)),
+        Err(err) => {
+            eprintln!("Couldn't show the tray icon: {err}");
+            None
+        }
+    }
+}
+
+// Every live creator of the watchlist, whatever the filters
+pub fn tray_model(obj: &YTMonitor) -> TrayModel {
+    let names = obj.json_obj.get_field("names").cloned().unwrap_or_default();
+    let live = names
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| obj.live_status.get(*idx) == Some(&true))
+        .map(|(idx, name)| (name.clone(), obj.json_obj.channel_url(idx)))
+        .collect();
+    TrayModel {
+        live,
+        total: names.len(),
+    }
+}
+
+fn update_tray(obj: &YTMonitor) {
+    if let Some(tray) = &obj.tray {
+        if let Err(err) = tray.set_model(tray_model(obj)) {
+            eprintln!("Couldn't update the tray icon: {err}");
+        }
+    }
+}
+
+fn static_text_mut(
+    obj: &mut YTMonitor,
+    text_type: render_cards::TextType,
+) -> Option<&mut StaticText> {
+    match text_type {
+        render_cards::TextType::Header => Some(&mut obj.settings.header_text),
+        render_cards::TextType::Footer => Some(&mut obj.settings.footer_text),
+        render_cards::TextType::Normal => None,
+    }
+}
+
+// The configured text or the built-in one, None if it's hidden
+fn static_text(static_text: &StaticText, default_message_id: &str) -> Option<String> {
+    static_text.shown.then(|| {
+        static_text
+            .text
+            .clone()
+            .unwrap_or_else(|| i18n::tr(default_message_id))
+    })
+}
+
+fn create_static_text_input<'a>(
+    static_text: &StaticText,
+    text_type: render_cards::TextType,
+    label_message_id: &str,
+    default_message_id: &str,
+) -> Row<'a, render_cards::Message, Renderer> {
+    row![
+        text(i18n::tr(label_message_id)),
+        text_input(
+            &i18n::tr(default_message_id),
+            static_text.text.as_deref().unwrap_or_default(),
+            move |input| render_cards::Message::StaticTextEdited(text_type, input),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0)),
+        checkbox(i18n::tr("show-text"), static_text.shown, move |shown| {
+            render_cards::Message::StaticTextToggled(text_type, shown)
+        }),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center)
+}
+
+// Remembers the active watchlist and its sort option for the next session
+pub fn save_watchlists_state(obj: &mut YTMonitor) {
+    let sort_option = WatchlistSortOption {
+        sort_option: obj.sort_option,
+        sort_direction: obj.sort_direction,
+    };
+    obj.watchlists_state
+        .set_sort_option(&obj.active_watchlist.name, sort_option);
+    if let Err(err) = obj.watchlists_state.save(&config::config_dir()) {
+        eprintln!("Couldn't save the watchlists state: {err}");
+    }
+    // The last sort option used also becomes the one for new watchlists
+    obj.settings.default_sort = sort_option;
+    save_settings(obj);
+}
+
+// The core library leaves the printing to the binaries
+fn print_warnings(warnings: impl IntoIterator<Item = String>) {
+    for warning in warnings {
+        eprintln!("{warning}");
+    }
+}
+
+fn load_themes(config_dir: &Path) -> Vec<CustomTheme> {
+    let (custom_themes, skipped) = themes::load_themes(config_dir);
+    print_warnings(skipped);
+    custom_themes
+}
 
-    fn new() -> YTMonitor {
-        let json_obj = render_cards::get_json_data(None);
-        let sorted_json_obj = json_obj
-            .sort_by(render_cards::AllowedFieldNamesForSorting::default())
-            .unwrap()
-            .0;
-        let image_handles = render_cards::get_all_avatars(&sorted_json_obj);
-        let statuses = render_cards::get_live_status(sorted_json_obj.get_field("is_live_status"));
-        // Because dark as default is cool :D
-        YTMonitor {
-            theme: Theme::Dark,
-            json_obj: sorted_json_obj,
-            loaded_photos: image_handles,
-            live_status: statuses,
-            sort_option: AllowedFieldNamesForSorting::Subscribers,
+pub fn save_settings(obj: &YTMonitor) {
+    if let Err(err) = obj.settings.save(&config::config_dir()) {
+        eprintln!("Couldn't save the settings: {err}");
+    }
+}
+
+pub fn palette_to_theme(palette: &ThemePalette) -> Theme {
+    Theme::custom(theme::Palette {
+        background: render_cards::to_color(palette.background),
+        text: render_cards::to_color(palette.text),
+        primary: render_cards::to_color(palette.primary),
+        success: render_cards::to_color(palette.success),
+        danger: render_cards::to_color(palette.danger),
+    })
+}
+
+pub fn theme_from_type(
+    theme_type: &ThemeType,
+    custom_themes: &[CustomTheme],
+    color_scheme: ColorScheme,
+) -> Theme {
+    match theme_type {
+        ThemeType::Auto if color_scheme.is_dark() => Theme::Dark,
+        ThemeType::Auto => Theme::Light,
+        ThemeType::Dark => Theme::Dark,
+        ThemeType::Light => Theme::Light,
+        ThemeType::HighContrast => palette_to_theme(&render_cards::styling::HIGH_CONTRAST_PALETTE),
+        // The theme file might have been removed since, dark is a safe bet
+        ThemeType::Custom(name) => custom_themes
+            .iter()
+            .find(|custom_theme| &custom_theme.name == name)
+            .map_or(Theme::Dark, |custom_theme| {
+                palette_to_theme(&custom_theme.palette)
+            }),
+    }
+}
+
+// The theme being edited wins, so that every change is previewed right away
+pub fn active_custom_theme(obj: &YTMonitor) -> Option<&CustomTheme> {
+    match (&obj.theme_editor, &obj.settings.theme) {
+        (Some(theme_editor), _) => Some(&theme_editor.theme),
+        (None, ThemeType::Custom(name)) => obj
+            .custom_themes
+            .iter()
+            .find(|custom_theme| &custom_theme.name == name),
+        _ => None,
+    }
+}
+
+// D-Bus calls block, so they're kept off the UI thread
+fn read_color_scheme() -> Command<render_cards::Message> {
+    Command::perform(
+        async {
+            // Without a bus it was said at startup
+            tokio::task::spawn_blocking(|| appearance::system_color_scheme().unwrap_or_default())
+                .await
+                .unwrap_or_default()
+        },
+        render_cards::Message::SystemColorSchemeChanged,
+    )
+}
+
+// The portal signals every change of the preference, waited for on a blocking thread
+fn color_scheme_changes() -> Subscription<render_cards::Message> {
+    iced::subscription::unfold(
+        "color-scheme-changes",
+        None,
+        |changes: Option<appearance::ColorSchemeChanges>| async move {
+            let next = tokio::task::spawn_blocking(move || {
+                // Without a bus or a portal there's nothing to follow
+                let mut changes = match changes {
+                    Some(changes) => changes,
+                    None => appearance::system_color_scheme_changes().ok()?,
+                };
+                let color_scheme = changes.next()?;
+                Some((color_scheme, changes))
+            })
+            .await
+            .ok()
+            .flatten();
+            match next {
+                Some((color_scheme, changes)) => (
+                    Some(render_cards::Message::SystemColorSchemeChanged(
+                        color_scheme,
+                    )),
+                    Some(changes),
+                ),
+                None => iced::futures::future::pending().await,
+            }
+        },
+    )
+}
+
+// Relative times are counted from the moment the view is built
+pub fn card_format(obj: &YTMonitor) -> CardFormat {
+    CardFormat {
+        view_mode: obj.settings.view_mode,
+        display_format: obj.settings.display_format,
+        now: views::unix_now(),
+    }
+}
+
+pub fn card_scheme(obj: &YTMonitor) -> CardScheme {
+    match &obj.theme_editor {
+        Some(theme_editor) => CardScheme::Custom(theme_editor.theme.clone()),
+        None => CardScheme::for_theme(&obj.settings.theme, &obj.custom_themes, obj.color_scheme),
+    }
+}
+
+pub fn refresh_theme(obj: &mut YTMonitor) {
+    obj.theme = match &obj.theme_editor {
+        Some(theme_editor) => palette_to_theme(&theme_editor.theme.palette),
+        None => theme_from_type(&obj.settings.theme, &obj.custom_themes, obj.color_scheme),
+    };
+}
+
+fn create_color_input(
+    theme_editor: &ThemeEditor,
+    field: ThemeColorField,
+) -> Column<'_, render_cards::Message, Renderer> {
+    let placeholder = if field.is_optional() {
+        "none"
+    } else {
+        "#rrggbb"
+    };
+    let input = theme_editor.color_inputs.get(&field);
+    column![
+        text(field.label()),
+        text_input(
+            placeholder,
+            input.map_or("", String::as_str),
+            move |input| render_cards::Message::ThemeColorEdited(field, input)
+        )
+        .padding(5)
+        .width(Length::Fixed(100.0))
+    ]
+}
+
+// The creators on screen, in the order they're shown
+fn export_contents(obj: &YTMonitor) -> (String, usize) {
+    let creators = records::creator_records(&obj.json_obj);
+    let shown: Vec<CreatorRecord> = obj
+        .visible_indices
+        .iter()
+        .filter_map(|idx| creators.get(*idx).cloned())
+        .collect();
+    let contents = export::export(
+        &shown,
+        &obj.settings.export_columns,
+        obj.settings.export_format,
+    );
+    (contents, shown.len())
+}
+
+fn create_export_panel<'a>(
+    export_panel: &ExportPanel,
+    settings: &UserSettings,
+) -> Column<'a, render_cards::Message, Renderer> {
+    let choose_format = ExportFormat::ALL.iter().fold(
+        row![text(i18n::tr("export-format-label"))]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        |format_row, format| {
+            format_row.push(radio(
+                format.to_string(),
+                *format,
+                Some(settings.export_format),
+                render_cards::Message::ExportFormatChanged,
+            ))
+        },
+    );
+    let choose_columns = ExportColumn::ALL.iter().fold(
+        row![text(i18n::tr("export-columns-label"))]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        |column_row, column| {
+            let column = *column;
+            column_row.push(checkbox(
+                column.title(),
+                settings.export_columns.contains(&column),
+                move |selected| render_cards::Message::ExportColumnToggled(column, selected),
+            ))
+        },
+    );
+    column![
+        choose_format,
+        choose_columns,
+        row![
+            text(i18n::tr("export-path-label")),
+            text_input(
+                "",
+                &export_panel.path,
+                render_cards::Message::ExportPathEdited
+            )
+            .on_submit(render_cards::Message::ExportSaved)
+            .padding(5)
+            .width(Length::Fixed(400.0)),
+            button(text(i18n::tr("export-save"))).on_press(render_cards::Message::ExportSaved),
+            button(text(i18n::tr("export-copy"))).on_press(render_cards::Message::ExportCopied),
+            button(text(i18n::tr("export-close"))).on_press(render_cards::Message::ExportClosed),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center),
+        text(export_panel.outcome.clone().unwrap_or_default()),
+    ]
+    .spacing(10)
+    .padding(10)
+}
+
+// The channels of a subscriptions export or of the account, compared with the watchlist file
+#[derive(Debug, Clone)]
+pub struct LoadedSubscriptions {
+    new: Vec<import::Subscription>,
+    total: usize,
+    // How many more creators the watchlist file can hold
+    free_slots: usize,
+}
+
+// The files are read on a blocking thread: the subscriptions (if read gets them from a file)
+// and the watchlist file, which is what the channels are added to
+fn load_subscriptions(
+    watchlist_path: PathBuf,
+    read: impl FnOnce() -> Result<Vec<import::Subscription>, Box<dyn std::error::Error>>
+        + Send
+        + 'static,
+) -> Command<render_cards::Message> {
+    Command::perform(
+        async move {
+            tokio::task::spawn_blocking(move || {
+                compare_subscriptions(&watchlist_path, read()).map_err(|err| err.to_string())
+            })
+            .await
+            .unwrap_or_else(|err| Err(err.to_string()))
+        },
+        render_cards::Message::SubscriptionsLoaded,
+    )
+}
+
+fn compare_subscriptions(
+    watchlist_path: &Path,
+    subscriptions: Result<Vec<import::Subscription>, Box<dyn std::error::Error>>,
+) -> Result<LoadedSubscriptions, Box<dyn std::error::Error>> {
+    let subscriptions = subscriptions?;
+    let watchlist = render_cards::read_json(&watchlist_path.to_string_lossy())?;
+    let count = watchlist.get_field("names").map_or(0, Vec::len);
+    Ok(LoadedSubscriptions {
+        new: import::new_subscriptions(&watchlist, &subscriptions),
+        total: subscriptions.len(),
+        free_slots: render_cards::MAX_EXPECTED_ITEMS.saturating_sub(count),
+    })
+}
+
+fn show_subscriptions(import_panel: &mut ImportPanel, loaded: Result<LoadedSubscriptions, String>) {
+    match loaded {
+        Ok(loaded) => {
+            import_panel.known = loaded.total - loaded.new.len();
+            import_panel.free_slots = loaded.free_slots;
+            import_panel.channels = loaded
+                .new
+                .into_iter()
+                .map(|channel| (channel, false))
+                .collect();
+            import_panel.outcome = None;
         }
+        Err(err) => {
+            eprintln!("Couldn't read the subscriptions: {err}");
+            import_panel.channels.clear();
+            import_panel.outcome = Some(i18n::tr_args("import-failed", &[("error", err.into())]));
+        }
+    }
+}
+
+// Signs in on the first update, then one update per page of subscriptions
+fn account_import_updates() -> Subscription<render_cards::Message> {
+    iced::subscription::unfold(
+        "account-import",
+        AccountImport::default(),
+        |account_import| async move {
+            match oauth::next_update(account_import).await {
+                Some((update, account_import)) => (
+                    Some(render_cards::Message::AccountImport(update)),
+                    account_import,
+                ),
+                // The subscription goes away once the panel knows it's finished
+                None => iced::futures::future::pending().await,
+            }
+        },
+    )
+}
+
+fn create_import_panel<'a>(
+    import_panel: &ImportPanel,
+    watchlist_name: &str,
+) -> Column<'a, render_cards::Message, Renderer> {
+    let free_slots = import_panel.free_slots;
+    let choose_file = row![
+        text(i18n::tr("import-path-label")),
+        text_input(
+            &i18n::tr("import-path-placeholder"),
+            &import_panel.path,
+            render_cards::Message::ImportPathEdited,
+        )
+        .on_submit(render_cards::Message::ImportFileLoaded)
+        .padding(5)
+        .width(Length::Fixed(400.0)),
+        button(text(i18n::tr("import-load"))).on_press(render_cards::Message::ImportFileLoaded),
+        button(text(i18n::tr("import-close"))).on_press(render_cards::Message::ImportClosed),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+    // Disabled until the running import finishes
+    let account_button = button(text(i18n::tr("import-account")));
+    let account_button = match import_panel.fetching_account {
+        true => account_button,
+        false => account_button.on_press(render_cards::Message::AccountImportStarted),
+    };
+    let mut import_panel_column = column![choose_file, account_button].spacing(10).padding(10);
+
+    if !import_panel.channels.is_empty() {
+        let ticked = import_panel
+            .channels
+            .iter()
+            .filter(|(_, is_ticked)| *is_ticked)
+            .count();
+        let checklist = import_panel.channels.iter().enumerate().fold(
+            Column::new().spacing(5),
+            |checklist, (idx, (subscription, is_ticked))| {
+                checklist.push(checkbox(
+                    format!("{} ({})", subscription.name, subscription.channel_id),
+                    *is_ticked,
+                    move |is_ticked| render_cards::Message::ImportChannelToggled(idx, is_ticked),
+                ))
+            },
+        );
+        // Nothing to add, or more than fits: the button stays disabled
+        let add_button = button(text(i18n::tr_args(
+            "import-add",
+            &[("watchlist", watchlist_name.into())],
+        )));
+        let add_button =
+            match ticked > 0 && ticked <= free_slots && import_panel.importing.is_empty() {
+                true => add_button.on_press(render_cards::Message::ImportConfirmed),
+                false => add_button,
+            };
+        import_panel_column = import_panel_column
+            .push(text(i18n::tr_args(
+                "import-summary",
+                &[
+                    ("new", import_panel.channels.len().into()),
+                    ("known", import_panel.known.into()),
+                    ("free", free_slots.into()),
+                ],
+            )))
+            .push(checkbox(
+                i18n::tr("import-select-all"),
+                ticked == import_panel.channels.len(),
+                render_cards::Message::ImportAllToggled,
+            ))
+            .push(scrollable(checklist).height(Length::Fixed(200.0)))
+            .push(add_button);
+    }
+    import_panel_column.push(text(import_panel.outcome.clone().unwrap_or_default()))
+}
+
+pub fn create_theme_editor(
+    theme_editor: &ThemeEditor,
+) -> Column<'_, render_cards::Message, Renderer> {
+    // Six color inputs per row, all eleven don't fit next to each other
+    let color_inputs =
+        ThemeColorField::ALL
+            .chunks(6)
+            .fold(Column::new().spacing(10), |color_rows, fields| {
+                color_rows.push(
+                    fields
+                        .iter()
+                        .fold(Row::new().spacing(10), |color_row, field| {
+                            color_row.push(create_color_input(theme_editor, *field))
+                        }),
+                )
+            });
+
+    column![
+        row![
+            text(i18n::tr("theme-editor-name")),
+            text_input(
+                &i18n::tr("theme-editor-name-placeholder"),
+                &theme_editor.theme.name,
+                render_cards::Message::ThemeNameEdited,
+            )
+            .padding(5)
+            .width(Length::Fixed(200.0)),
+            text(i18n::tr_args(
+                "theme-editor-font-size",
+                &[("size", theme_editor.theme.font_size.into())]
+            )),
+            slider(
+                10..=40,
+                theme_editor.theme.font_size,
+                render_cards::Message::ThemeFontSizeChanged,
+            )
+            .width(Length::Fixed(150.0)),
+            text(i18n::tr_args(
+                "theme-editor-border-width",
+                &[("width", theme_editor.theme.border_width.into())]
+            )),
+            slider(
+                0.0..=10.0,
+                theme_editor.theme.border_width,
+                render_cards::Message::ThemeBorderWidthChanged,
+            )
+            .step(0.5)
+            .width(Length::Fixed(150.0)),
+            button(text(i18n::tr("theme-editor-save")))
+                .on_press(render_cards::Message::ThemeEditorSaved),
+            button(text(i18n::tr("theme-editor-cancel")))
+                .on_press(render_cards::Message::ThemeEditorCancelled),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center),
+        color_inputs,
+    ]
+    .spacing(10)
+    .padding(10)
+}
+
+impl Application for YTMonitor {
+    type Executor = executor::Default;
+    type Message = render_cards::Message;
+    type Theme = Theme;
+    type Flags = UserSettings;
+
+    fn new(settings: UserSettings) -> (YTMonitor, Command<Self::Message>) {
+        let config_dir = config::config_dir();
+        let watchlists = watchlists::discover_watchlists(&config_dir);
+        let watchlists_state = WatchlistsState::load(&config_dir);
+        // Reopen the list shown last time
+        let active_watchlist =
+            watchlists::find_watchlist(&watchlists, watchlists_state.active.as_deref());
+        i18n::set_language(settings.language);
+        let custom_themes = load_themes(&config_dir);
+        // Asked right away so the window doesn't open in the wrong theme first
+        let color_scheme = if settings.theme == ThemeType::Auto {
+            appearance::system_color_scheme().unwrap_or_else(|err| {
+                eprintln!("Couldn't read the color scheme: {err}");
+                ColorScheme::default()
+            })
+        } else {
+            ColorScheme::default()
+        };
+        let (tray, tray_events) = match start_tray() {
+            Some((tray, tray_events)) => (Some(tray), Some(Arc::new(Mutex::new(tray_events)))),
+            None => (None, None),
+        };
+        // A daemon uses its own key
+        let uses_api = sources::api_key().is_some() && settings.daemon_url.is_none();
+        let mut yt_monitor = YTMonitor {
+            theme: theme_from_type(&settings.theme, &custom_themes, color_scheme),
+            daemon_url_input: settings.daemon_url.clone().unwrap_or_default(),
+            custom_themes,
+            color_scheme,
+            watchlists,
+            watchlists_state,
+            settings,
+            refresh_status: RefreshStatus {
+                quota: sources::QuotaUsage::load(&config_dir),
+                uses_api,
+                ..Default::default()
+            },
+            tray,
+            tray_events,
+            ..Default::default()
+        };
+        let load = load_watchlist(&mut yt_monitor, active_watchlist);
+        // A hidden window couldn't be brought back without the tray
+        let command = if yt_monitor.settings.start_hidden && yt_monitor.tray.is_none() {
+            iced::window::change_mode(iced::window::Mode::Windowed)
+        } else {
+            Command::none()
+        };
+        (yt_monitor, Command::batch([load, command]))
     }
 
     fn title(&self) -> String {
-        String::from("YT Monitoring App (by KRS)")
+        i18n::tr("app-title")
     }
 
-    fn update(&mut self, message: Self::Message) {
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
         match message {
             render_cards::Message::ThemeChanged(theme) => {
-                self.theme = match theme {
-                    render_cards::ThemeType::Dark => Theme::Dark,
-                    render_cards::ThemeType::Light => Theme::Light,
-                    render_cards::ThemeType::Custom => Theme::custom(theme::Palette {
-                        background: Color::from_rgb(1.0, 0.9, 1.0),
-                        text: Color::BLACK,
-                        primary: Color::from_rgb(0.5, 0.5, 0.0),
-                        success: Color::from_rgb(0.0, 1.0, 0.0),
-                        danger: Color::from_rgb(1.0, 0.0, 0.0),
-                    }),
+                self.settings.theme = theme;
+                self.theme_editor = None;
+                refresh_theme(self);
+                save_settings(self);
+                if self.settings.theme == ThemeType::Auto {
+                    return read_color_scheme();
+                }
+            }
+            render_cards::Message::SystemColorSchemeChanged(color_scheme) => {
+                if color_scheme != self.color_scheme {
+                    self.color_scheme = color_scheme;
+                    refresh_theme(self);
+                }
+            }
+            render_cards::Message::ThemeEditorOpened => {
+                // Edit the theme in use, or start a new one from the bundled theme
+                let custom_theme =
+                    active_custom_theme(self)
+                        .cloned()
+                        .unwrap_or_else(|| CustomTheme {
+                            name: i18n::tr("theme-editor-new-theme"),
+                            ..CustomTheme::bundled()
+                        });
+                self.theme_editor = Some(ThemeEditor::new(custom_theme));
+                refresh_theme(self);
+            }
+            render_cards::Message::ThemeNameEdited(name) => {
+                if let Some(theme_editor) = &mut self.theme_editor {
+                    theme_editor.theme.name = name;
+                }
+            }
+            render_cards::Message::ThemeColorEdited(field, input) => {
+                if let Some(theme_editor) = &mut self.theme_editor {
+                    field.set_from_input(&mut theme_editor.theme, &input);
+                    theme_editor.color_inputs.insert(field, input);
+                }
+                refresh_theme(self);
+            }
+            render_cards::Message::ThemeFontSizeChanged(font_size) => {
+                if let Some(theme_editor) = &mut self.theme_editor {
+                    theme_editor.theme.font_size = font_size;
+                }
+            }
+            render_cards::Message::ThemeBorderWidthChanged(border_width) => {
+                if let Some(theme_editor) = &mut self.theme_editor {
+                    theme_editor.theme.border_width = border_width;
+                }
+            }
+            render_cards::Message::ThemeEditorSaved => {
+                // A theme needs a name, it's also its file name
+                if let Some(theme_editor) = self
+                    .theme_editor
+                    .take_if(|theme_editor| !theme_editor.theme.name.trim().is_empty())
+                {
+                    let config_dir = config::config_dir();
+                    match theme_editor.theme.save(&config_dir) {
+                        Ok(()) => {
+                            self.custom_themes = load_themes(&config_dir);
+                            self.settings.theme = ThemeType::Custom(theme_editor.theme.name);
+                            save_settings(self);
+                        }
+                        Err(err) => eprintln!("Couldn't save the theme: {err}"),
+                    }
+                    refresh_theme(self);
+                }
+            }
+            render_cards::Message::ThemeEditorCancelled => {
+                self.theme_editor = None;
+                refresh_theme(self);
+            }
+            render_cards::Message::ExportOpened => {
+                let path = export::default_path(
+                    &self.active_watchlist.name,
+                    self.settings.export_format,
+                    views::unix_now(),
+                );
+                self.export_panel = Some(ExportPanel {
+                    path: path.display().to_string(),
+                    outcome: None,
+                });
+            }
+            render_cards::Message::ExportFormatChanged(export_format) => {
+                self.settings.export_format = export_format;
+                save_settings(self);
+                if let Some(export_panel) = &mut self.export_panel {
+                    export_panel.path = Path::new(&export_panel.path)
+                        .with_extension(export_format.extension())
+                        .display()
+                        .to_string();
+                }
+            }
+            render_cards::Message::ExportColumnToggled(column, selected) => {
+                // Always in the same order, whatever order they're picked in
+                let columns = &self.settings.export_columns;
+                self.settings.export_columns = ExportColumn::ALL
+                    .into_iter()
+                    .filter(|other| match *other == column {
+                        true => selected,
+                        false => columns.contains(other),
+                    })
+                    .collect();
+                save_settings(self);
+            }
+            render_cards::Message::ExportPathEdited(path) => {
+                if let Some(export_panel) = &mut self.export_panel {
+                    export_panel.path = path;
+                }
+            }
+            render_cards::Message::ExportSaved => {
+                let (contents, count) = export_contents(self);
+                if let Some(export_panel) = &mut self.export_panel {
+                    let outcome = match fs::write(&export_panel.path, contents) {
+                        Ok(()) => i18n::tr_args(
+                            "export-saved",
+                            &[
+                                ("count", count.into()),
+                                ("path", export_panel.path.as_str().into()),
+                            ],
+                        ),
+                        Err(err) => {
+                            eprintln!("Couldn't save the export: {err}");
+                            i18n::tr_args("export-failed", &[("error", err.to_string().into())])
+                        }
+                    };
+                    export_panel.outcome = Some(outcome);
+                }
+            }
+            render_cards::Message::ExportCopied => {
+                let (contents, count) = export_contents(self);
+                if let Some(export_panel) = &mut self.export_panel {
+                    export_panel.outcome =
+                        Some(i18n::tr_args("export-copied", &[("count", count.into())]));
+                }
+                return iced::clipboard::write(contents);
+            }
+            render_cards::Message::ExportClosed => {
+                self.export_panel = None;
+            }
+            render_cards::Message::ImportOpened => {
+                self.import_panel = Some(ImportPanel::default());
+            }
+            render_cards::Message::ImportPathEdited(path) => {
+                if let Some(import_panel) = &mut self.import_panel {
+                    import_panel.path = path;
+                }
+            }
+            render_cards::Message::ImportFileLoaded => {
+                if let Some(import_panel) = &self.import_panel {
+                    let path = PathBuf::from(import_panel.path.trim());
+                    return load_subscriptions(self.active_watchlist.path.clone(), move || {
+                        import::read_subscriptions(&path)
+                    });
+                }
+            }
+            render_cards::Message::SubscriptionsLoaded(loaded) => {
+                if let Some(import_panel) = &mut self.import_panel {
+                    show_subscriptions(import_panel, loaded);
+                }
+            }
+            render_cards::Message::AccountImportStarted => {
+                if let Some(import_panel) = &mut self.import_panel {
+                    import_panel.fetching_account = true;
+                    import_panel.outcome = Some(i18n::tr("import-account-signing-in"));
+                }
+            }
+            render_cards::Message::AccountImport(update) => {
+                let Some(import_panel) = &mut self.import_panel else {
+                    return Command::none();
+                };
+                match update {
+                    AccountImportUpdate::SignedIn => {
+                        import_panel.outcome = Some(i18n::tr("import-account-fetching"));
+                    }
+                    AccountImportUpdate::Progress(progress) => {
+                        import_panel.outcome = Some(i18n::tr_args(
+                            "import-account-progress",
+                            &[
+                                ("fetched", progress.fetched.into()),
+                                ("total", progress.total.unwrap_or(progress.fetched).into()),
+                            ],
+                        ));
+                    }
+                    AccountImportUpdate::Finished(fetched) => {
+                        import_panel.fetching_account = false;
+                        return load_subscriptions(self.active_watchlist.path.clone(), move || {
+                            fetched.map_err(Into::into)
+                        });
+                    }
+                }
+            }
+            render_cards::Message::ImportChannelToggled(idx, is_ticked) => {
+                if let Some((_, ticked)) = self
+                    .import_panel
+                    .as_mut()
+                    .and_then(|import_panel| import_panel.channels.get_mut(idx))
+                {
+                    *ticked = is_ticked;
+                }
+            }
+            render_cards::Message::ImportAllToggled(is_ticked) => {
+                if let Some(import_panel) = &mut self.import_panel {
+                    for (_, ticked) in import_panel.channels.iter_mut() {
+                        *ticked = is_ticked;
+                    }
+                }
+            }
+            render_cards::Message::ImportConfirmed => {
+                let Some(import_panel) = &mut self.import_panel else {
+                    return Command::none();
+                };
+                let (ticked, unticked): (Vec<_>, Vec<_>) = import_panel
+                    .channels
+                    .drain(..)
+                    .partition(|(_, is_ticked)| *is_ticked);
+                import_panel.importing = ticked.into_iter().map(|(channel, _)| channel).collect();
+                import_panel.channels = unticked;
+                // The watchlist file is read and written on a blocking thread
+                let watchlist_path = self.active_watchlist.path.clone();
+                let selected = import_panel.importing.clone();
+                return Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            import::import_into_watchlist(&watchlist_path, &selected)
+                                .map_err(|err| err.to_string())
+                        })
+                        .await
+                        .unwrap_or_else(|err| Err(err.to_string()))
+                    },
+                    render_cards::Message::SubscriptionsImported,
+                );
+            }
+            render_cards::Message::SubscriptionsImported(imported) => {
+                let Some(import_panel) = &mut self.import_panel else {
+                    return Command::none();
+                };
+                let selected = std::mem::take(&mut import_panel.importing);
+                let watchlist = &self.active_watchlist;
+                match imported {
+                    Ok(count) => {
+                        import_panel.known += count;
+                        import_panel.free_slots -= count;
+                        import_panel.outcome = Some(i18n::tr_args(
+                            "import-added",
+                            &[
+                                ("count", count.into()),
+                                ("watchlist", watchlist.name.as_str().into()),
+                            ],
+                        ));
+                        // A daemon only sees the new creators on its next refresh
+                        if self.settings.daemon_url.is_none() {
+                            return refresh_watchlist(self);
+                        }
+                    }
+                    Err(err) => {
+                        eprintln!("Couldn't import the subscriptions: {err}");
+                        let unticked = std::mem::take(&mut import_panel.channels);
+                        import_panel.channels = selected
+                            .into_iter()
+                            .map(|channel| (channel, true))
+                            .chain(unticked)
+                            .collect();
+                        import_panel.outcome =
+                            Some(i18n::tr_args("import-failed", &[("error", err.into())]));
+                    }
                 }
             }
+            render_cards::Message::ImportClosed => {
+                self.import_panel = None;
+            }
             render_cards::Message::SortOptionChanged(sort_option) => {
                 let old_option = self.sort_option;
-                self.sort_option = match sort_option {
-                    AllowedFieldNamesForSorting::IsLiveStatus => {
-                        AllowedFieldNamesForSorting::IsLiveStatus
+                let old_direction = self.sort_direction;
+                self.sort_option = sort_option;
+                // Switching the key starts from its natural direction (A -> Z for names, etc.)
+                if old_option != sort_option {
+                    self.sort_direction = sort_option.default_direction();
+                }
+                update_json_obj(self, &old_option, &old_direction);
+                save_watchlists_state(self);
+            }
+            render_cards::Message::SortDirectionChanged(sort_direction) => {
+                let old_option = self.sort_option;
+                let old_direction = self.sort_direction;
+                self.sort_direction = sort_direction;
+                update_json_obj(self, &old_option, &old_direction);
+                save_watchlists_state(self);
+            }
+            render_cards::Message::WatchlistChanged(watchlist) => {
+                if watchlist != self.active_watchlist {
+                    return load_watchlist(self, watchlist);
+                }
+            }
+            render_cards::Message::SearchChanged(search_query) => {
+                self.filter.search_query = search_query;
+                let (old_option, old_direction) = (self.sort_option, self.sort_direction);
+                update_json_obj(self, &old_option, &old_direction);
+            }
+            render_cards::Message::LiveOnlyToggled(live_only) => {
+                self.filter.live_only = live_only;
+                let (old_option, old_direction) = (self.sort_option, self.sort_direction);
+                update_json_obj(self, &old_option, &old_direction);
+            }
+            render_cards::Message::SubscriberRangeChanged(subscriber_range) => {
+                self.filter.subscriber_range = subscriber_range;
+                let (old_option, old_direction) = (self.sort_option, self.sort_direction);
+                update_json_obj(self, &old_option, &old_direction);
+            }
+            render_cards::Message::TagFilterToggled(tag, is_selected) => {
+                self.filter.toggle_tag(&tag, is_selected);
+                let (old_option, old_direction) = (self.sort_option, self.sort_direction);
+                update_json_obj(self, &old_option, &old_direction);
+            }
+            render_cards::Message::ClearFilters => {
+                self.filter = render_cards::CreatorFilter::default();
+                let (old_option, old_direction) = (self.sort_option, self.sort_direction);
+                update_json_obj(self, &old_option, &old_direction);
+            }
+            render_cards::Message::GroupByTagToggled(group_by_tag) => {
+                self.group_by_tag = group_by_tag;
+            }
+            render_cards::Message::TagSectionToggled(tag) => {
+                if self.collapsed_tags.contains(&tag) {
+                    self.collapsed_tags
+                        .retain(|collapsed_tag| collapsed_tag != &tag);
+                } else {
+                    self.collapsed_tags.push(tag);
+                }
+            }
+            render_cards::Message::CardsPerRowChanged(cards_per_row) => {
+                self.settings.cards_per_row = cards_per_row;
+                save_settings(self);
+            }
+            render_cards::Message::PollingIntervalChanged(polling_interval_secs) => {
+                self.settings.polling_interval_secs = polling_interval_secs;
+                save_settings(self);
+            }
+            render_cards::Message::UpcomingNoticeChanged(upcoming_notice) => {
+                self.settings.upcoming_notice = upcoming_notice;
+                save_settings(self);
+            }
+            render_cards::Message::AnimationsToggled(animations_enabled) => {
+                self.settings.animations_enabled = animations_enabled;
+                self.animations = Animations::default();
+                save_settings(self);
+            }
+            render_cards::Message::AnimationFrame(now) => self.animations.tick(now),
+            render_cards::Message::ViewModeChanged(view_mode) => {
+                self.settings.view_mode = view_mode;
+                save_settings(self);
+            }
+            render_cards::Message::DisplayFormatChanged(display_format) => {
+                self.settings.display_format = display_format;
+                save_settings(self);
+            }
+            render_cards::Message::StaticTextEdited(text_type, input) => {
+                if let Some(static_text) = static_text_mut(self, text_type) {
+                    // An empty text goes back to the built-in one
+                    static_text.text = Some(input).filter(|input| !input.trim().is_empty());
+                    save_settings(self);
+                }
+            }
+            render_cards::Message::StaticTextToggled(text_type, shown) => {
+                if let Some(static_text) = static_text_mut(self, text_type) {
+                    static_text.shown = shown;
+                    save_settings(self);
+                }
+            }
+            // Updates the relative times on screen and notifies of the streams coming up. The
+            // messages which couldn't be translated since are printed too.
+            render_cards::Message::ClockTicked => {
+                print_warnings(i18n::take_errors());
+                announce_upcoming(self);
+            }
+            render_cards::Message::StartHiddenToggled(start_hidden) => {
+                self.settings.start_hidden = start_hidden;
+                save_settings(self);
+            }
+            render_cards::Message::Tray(tray_event) => match tray_event {
+                TrayEvent::ShowWindow => {
+                    return iced::window::change_mode(iced::window::Mode::Windowed)
+                }
+                TrayEvent::OpenChannel(channel_url) => {
+                    if let Err(err) = open::that(&channel_url) {
+                        eprintln!("Couldn't open {channel_url}: {err}");
+                    }
+                }
+                TrayEvent::Quit => {
+                    save_settings(self);
+                    return iced::window::close();
+                }
+            },
+            render_cards::Message::LanguageChanged(language) => {
+                self.settings.language = language;
+                i18n::set_language(language);
+                save_settings(self);
+            }
+            render_cards::Message::Shortcut(shortcut) => match shortcut {
+                Shortcut::MoveFocus(focus_move) => {
+                    self.focused = shortcuts::move_focus(
+                        self.focused,
+                        focus_move,
+                        focus_order(self).len(),
+                        focus_columns(self),
+                    );
+                }
+                Shortcut::OpenFocused => {
+                    if let Some(idx) = focused_creator(self) {
+                        let channel_url = self.json_obj.channel_url(idx);
+                        if let Err(err) = open::that(&channel_url) {
+                            eprintln!("Couldn't open {channel_url}: {err}");
+                        }
                     }
-                    AllowedFieldNamesForSorting::Subscribers => {
-                        AllowedFieldNamesForSorting::Subscribers
+                }
+                Shortcut::FocusSearch => return text_input::focus(search_input_id()),
+                Shortcut::CycleTheme => {
+                    let next_theme =
+                        shortcuts::next_choice(&theme_choices(self), &self.settings.theme);
+                    if let Some(theme) = next_theme {
+                        return self.update(render_cards::Message::ThemeChanged(theme));
                     }
+                }
+                Shortcut::CycleSort => {
+                    let next_sort_option = shortcuts::next_choice(
+                        &AllowedFieldNamesForSorting::ALL,
+                        &self.sort_option,
+                    );
+                    if let Some(sort_option) = next_sort_option {
+                        return self.update(render_cards::Message::SortOptionChanged(sort_option));
+                    }
+                }
+                Shortcut::Refresh => return self.update(render_cards::Message::Refresh),
+                Shortcut::ToggleHelp => self.show_shortcuts = !self.show_shortcuts,
+                Shortcut::CloseHelp => self.show_shortcuts = false,
+            },
+            render_cards::Message::ListHeaderClicked(sort_key) => {
+                let (old_option, old_direction) = (self.sort_option, self.sort_direction);
+                (self.sort_option, self.sort_direction) =
+                    views::sort_after_header_click(old_option, old_direction, sort_key);
+                update_json_obj(self, &old_option, &old_direction);
+                save_watchlists_state(self);
+            }
+            render_cards::Message::Refresh => match &self.settings.daemon_url {
+                Some(daemon_url) => refresh_daemon(daemon_url),
+                None => return refresh_watchlist(self),
+            },
+            render_cards::Message::WatchlistLoaded(fetched) => {
+                let watchlist = fetched.watchlist.clone();
+                let shown = match fetched_creators(self, *fetched) {
+                    Some(json_obj) => show_watchlist(self, watchlist, json_obj),
+                    None => Command::none(),
                 };
-                update_json_obj(self, &old_option);
+                return Command::batch([shown, fetch_next_watchlist(self)]);
+            }
+            render_cards::Message::WatchlistRefreshed(fetched) => {
+                let shown = match fetched_creators(self, *fetched) {
+                    Some(json_obj) => show_creators(self, json_obj),
+                    None => Command::none(),
+                };
+                return Command::batch([shown, fetch_next_watchlist(self)]);
+            }
+            render_cards::Message::AvatarsDownloaded(avatars) => {
+                self.avatars.extend(avatars);
+                self.loaded_photos = render_cards::get_all_avatars(&self.json_obj, &self.avatars);
+            }
+            // The last creators stay on screen while the daemon is away
+            render_cards::Message::Daemon(update) => {
+                self.daemon_status.apply(&update, views::unix_now());
+                if let DaemonUpdate::Creators(creators, status) = update {
+                    return show_daemon_creators(self, &creators, status);
+                }
+            }
+            render_cards::Message::DaemonUrlEdited(input) => {
+                self.daemon_url_input = input;
+            }
+            render_cards::Message::DaemonUrlSubmitted => {
+                let daemon_url =
+                    Some(self.daemon_url_input.trim().to_string()).filter(|url| !url.is_empty());
+                if daemon_url != self.settings.daemon_url {
+                    self.settings.daemon_url = daemon_url;
+                    self.daemon_status = ConnectionStatus::default();
+                    save_settings(self);
+                    // Back to refreshing the watchlist from here
+                    if self.settings.daemon_url.is_none() {
+                        self.refresh_status.uses_api = sources::api_key().is_some();
+                        return refresh_watchlist(self);
+                    }
+                }
+            }
+            // The window geometry changes a lot while dragging, so it's only saved on close
+            render_cards::Message::WindowResized(width, height) => {
+                self.settings.window_size = (width, height);
+            }
+            render_cards::Message::WindowMoved(x, y) => {
+                self.settings.window_position = Some((x, y));
+            }
+            // With a tray icon, closing only hides the window
+            render_cards::Message::CloseRequested => {
+                save_settings(self);
+                if self.tray.is_some() {
+                    return iced::window::change_mode(iced::window::Mode::Hidden);
+                }
+                return iced::window::close();
             }
         }
+        Command::none()
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let window_events = iced::subscription::events_with(|event, status| match event {
+            iced_native::Event::Window(iced_native::window::Event::Resized { width, height }) => {
+                Some(render_cards::Message::WindowResized(width, height))
+            }
+            iced_native::Event::Window(iced_native::window::Event::Moved { x, y }) => {
+                Some(render_cards::Message::WindowMoved(x, y))
+            }
+            iced_native::Event::Window(iced_native::window::Event::CloseRequested) => {
+                Some(render_cards::Message::CloseRequested)
+            }
+            // Keys already used by a widget (e.g. typing in the search box) aren't shortcuts
+            iced_native::Event::Keyboard(keyboard_event)
+                if status == iced::event::Status::Ignored =>
+            {
+                key_input(keyboard_event)
+                    .and_then(shortcuts::shortcut_for)
+                    .map(render_cards::Message::Shortcut)
+            }
+            _ => None,
+        });
+        // A daemon polls for the window and tells it about every refresh
+        let polling = match &self.settings.daemon_url {
+            Some(daemon_url) => daemon_updates(daemon_url.clone()),
+            None => iced::time::every(Duration::from_secs(self.settings.polling_interval_secs))
+                .map(|_| render_cards::Message::Refresh),
+        };
+        // Followed only while the theme follows it
+        let color_scheme = if self.settings.theme == ThemeType::Auto {
+            color_scheme_changes()
+        } else {
+            Subscription::none()
+        };
+        // The frame timer only runs while something moves: a transition or a pulsing badge
+        let is_animating = self.animations.is_running()
+            || self
+                .visible_indices
+                .iter()
+                .any(|idx| self.live_status[*idx]);
+        let animation_frames = if self.settings.animations_enabled && is_animating {
+            iced::time::every(animations::FRAME_INTERVAL).map(render_cards::Message::AnimationFrame)
+        } else {
+            Subscription::none()
+        };
+        let clock =
+            iced::time::every(Duration::from_secs(1)).map(|_| render_cards::Message::ClockTicked);
+        // The D-Bus calls arrive on zbus' own thread, they're waited for on a blocking one
+        let tray_events = match &self.tray_events {
+            Some(tray_events) => iced::subscription::unfold(
+                "tray-events",
+                Arc::clone(tray_events),
+                |tray_events| async move {
+                    let receiver = Arc::clone(&tray_events);
+                    let tray_event = tokio::task::spawn_blocking(move || {
+                        receiver
+                            .lock()
+                            .ok()
+                            .and_then(|receiver| receiver.recv().ok())
+                    })
+                    .await
+                    .ok()
+                    .flatten();
+                    match tray_event {
+                        Some(tray_event) => {
+                            (Some(render_cards::Message::Tray(tray_event)), tray_events)
+                        }
+                        // The tray is gone, nothing more will come
+                        None => iced::futures::future::pending().await,
+                    }
+                },
+            ),
+            None => Subscription::none(),
+        };
+        let account_import = match &self.import_panel {
+            Some(import_panel) if import_panel.fetching_account => account_import_updates(),
+            _ => Subscription::none(),
+        };
+        Subscription::batch([
+            window_events,
+            polling,
+            color_scheme,
+            animation_frames,
+            clock,
+            tray_events,
+            account_import,
+        ])
     }
 
     fn view(&self) -> iced::Element<'_, Self::Message> {
-        let choose_theme = [
-            render_cards::ThemeType::Dark,
-            render_cards::ThemeType::Light,
-            render_cards::ThemeType::Custom,
-        ]
-        .iter()
-        .fold(
-            row![text("Choose a theme:")].spacing(10),
-            |column: iced_native::widget::row::Row<'_, render_cards::Message, Renderer>, theme| {
-                column.push(radio(
-                    format!("{:?}", theme),
-                    *theme,
-                    Some(match self.theme {
-                        Theme::Dark => render_cards::ThemeType::Dark,
-                        Theme::Light => render_cards::ThemeType::Light,
-                        Theme::Custom { .. } => render_cards::ThemeType::Custom,
-                    }),
-                    render_cards::Message::ThemeChanged,
-                ))
-            },
-        );
+        let theme_choices = theme_choices(self);
+        let selected_theme_idx = theme_choices
+            .iter()
+            .position(|theme_type| theme_type == &self.settings.theme);
+        // A daemon serves a single watchlist
+        let choose_watchlist = match self.settings.daemon_url {
+            Some(_) => row![],
+            None => row![
+                text(i18n::tr("watchlist-label")),
+                pick_list(
+                    &self.watchlists[..],
+                    Some(self.active_watchlist.clone()),
+                    render_cards::Message::WatchlistChanged,
+                ),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        };
+        let choose_theme = theme_choices
+            .into_iter()
+            .enumerate()
+            .fold(
+                row![choose_watchlist, text(i18n::tr("theme-label"))]
+                    .spacing(10)
+                    .align_items(iced::Alignment::Center),
+                |column: iced_native::widget::row::Row<'_, render_cards::Message, Renderer>,
+                 (theme_idx, theme)| {
+                    column.push(radio(
+                        theme.to_string(),
+                        theme_idx,
+                        selected_theme_idx,
+                        move |_| render_cards::Message::ThemeChanged(theme),
+                    ))
+                },
+            )
+            .push(
+                button(text(i18n::tr("edit-theme")))
+                    .on_press(render_cards::Message::ThemeEditorOpened),
+            )
+            .push(
+                button(text(i18n::tr("shortcuts-button")))
+                    .on_press(render_cards::Message::Shortcut(Shortcut::ToggleHelp)),
+            )
+            .push(
+                button(text(i18n::tr("import-button")))
+                    .on_press(render_cards::Message::ImportOpened),
+            )
+            .push(
+                button(text(i18n::tr("export-button")))
+                    .on_press(render_cards::Message::ExportOpened),
+            );
 
-        let choose_sort_by_option = [
-            render_cards::AllowedFieldNamesForSorting::Subscribers,
-            render_cards::AllowedFieldNamesForSorting::IsLiveStatus,
-        ]
-        .iter()
-        .fold(
-            row![text("Sort by:")].spacing(10),
+        let choose_sort_by_option = render_cards::AllowedFieldNamesForSorting::ALL.iter().fold(
+            row![text(i18n::tr("sort-by-label"))].spacing(10),
             |column: iced_native::widget::row::Row<'_, render_cards::Message, Renderer>,
              sort_by_option| {
                 column.push(radio(
-                    format!("{:?}", sort_by_option),
+                    sort_by_option.to_string(),
                     *sort_by_option,
                     Some(self.sort_option),
                     render_cards::Message::SortOptionChanged,
@@ -164,69 +1950,276 @@ impl Sandbox for YTMonitor {
             },
         );
 
-        let content = column![choose_theme]
-            .spacing(20)
-            .padding(20)
-            .max_width(600)
-            .width(Length::Fill);
+        let choose_sort_direction = [SortDirection::Descending, SortDirection::Ascending]
+            .iter()
+            .fold(
+                row![text(i18n::tr("order-label"))].spacing(10),
+                |column: iced_native::widget::row::Row<'_, render_cards::Message, Renderer>,
+                 direction| {
+                    column.push(radio(
+                        direction.to_string(),
+                        *direction,
+                        Some(self.sort_direction),
+                        render_cards::Message::SortDirectionChanged,
+                    ))
+                },
+            );
 
-        let sort_option_content = column![choose_sort_by_option]
-            .spacing(20)
-            .padding(20)
-            .max_width(600)
-            .width(Length::Fill);
+        let choose_layout = row![
+            text(i18n::tr("cards-per-row-label")),
+            pick_list(
+                &config::CARDS_PER_ROW_OPTIONS[..],
+                Some(self.settings.cards_per_row),
+                render_cards::Message::CardsPerRowChanged,
+            ),
+            text(i18n::tr("refresh-every-label")),
+            pick_list(
+                &config::POLLING_INTERVAL_OPTIONS[..],
+                Some(self.settings.polling_interval_secs),
+                render_cards::Message::PollingIntervalChanged,
+            ),
+            text(i18n::tr("notify-upcoming-label")),
+            pick_list(
+                &config::UpcomingNotice::ALL[..],
+                Some(self.settings.upcoming_notice),
+                render_cards::Message::UpcomingNoticeChanged,
+            ),
+            checkbox(
+                i18n::tr("animations-toggle"),
+                self.settings.animations_enabled,
+                render_cards::Message::AnimationsToggled,
+            ),
+            checkbox(
+                i18n::tr("start-hidden"),
+                self.settings.start_hidden,
+                render_cards::Message::StartHiddenToggled,
+            ),
+            text(i18n::tr("language-label")),
+            pick_list(
+                &Language::ALL[..],
+                Some(self.settings.language),
+                render_cards::Message::LanguageChanged,
+            ),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center);
 
-        let footer = render_cards::create_text(
-            "Thank you for being here, this was an app by Kushashwa Ravi Shrimali".to_string(),
-            render_cards::TextType::Footer,
+        let choose_view_mode = ViewMode::ALL.iter().fold(
+            row![text(i18n::tr("view-label"))]
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+            |column: iced_native::widget::row::Row<'_, render_cards::Message, Renderer>,
+             view_mode| {
+                column.push(radio(
+                    view_mode.to_string(),
+                    *view_mode,
+                    Some(self.settings.view_mode),
+                    render_cards::Message::ViewModeChanged,
+                ))
+            },
+        );
+
+        let choose_display_format = DisplayFormat::ALL.iter().fold(
+            row![text(i18n::tr("format-label"))]
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+            |column: iced_native::widget::row::Row<'_, render_cards::Message, Renderer>,
+             display_format| {
+                column.push(radio(
+                    display_format.to_string(),
+                    *display_format,
+                    Some(self.settings.display_format),
+                    render_cards::Message::DisplayFormatChanged,
+                ))
+            },
         );
 
+        let content = column![
+            choose_theme,
+            choose_layout,
+            choose_view_mode,
+            choose_display_format
+        ]
+        .spacing(20)
+        .padding(20)
+        .max_width(600)
+        .width(Length::Fill);
+
+        let choose_daemon = row![
+            text(i18n::tr("daemon-label")),
+            text_input(
+                &i18n::tr("daemon-placeholder"),
+                &self.daemon_url_input,
+                render_cards::Message::DaemonUrlEdited,
+            )
+            .on_submit(render_cards::Message::DaemonUrlSubmitted)
+            .padding(5)
+            .width(Length::Fixed(300.0)),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center);
+
+        let choose_static_texts = column![
+            create_static_text_input(
+                &self.settings.header_text,
+                render_cards::TextType::Header,
+                "header-text-label",
+                "header-welcome",
+            ),
+            create_static_text_input(
+                &self.settings.footer_text,
+                render_cards::TextType::Footer,
+                "footer-text-label",
+                "footer-thanks",
+            ),
+        ]
+        .spacing(10);
+
+        let sort_option_content = column![
+            choose_sort_by_option,
+            choose_sort_direction,
+            choose_static_texts,
+            choose_daemon
+        ]
+        .spacing(20)
+        .padding(20)
+        .max_width(600)
+        .width(Length::Fill);
+
+        // The whole watchlist counts, whatever the filters hide
+        let now = views::unix_now();
+        let live_count = self.live_status.iter().filter(|is_live| **is_live).count();
+        let header_summary =
+            self.refresh_status
+                .header_summary(live_count, self.live_status.len(), now);
         let title_header = render_cards::create_text(
-            "Welcome! Here is the status of your favorite YouTubers:".to_string(),
+            static_text(&self.settings.header_text, "header-welcome")
+                .into_iter()
+                .chain([header_summary])
+                .collect::<Vec<String>>()
+                .join("\n"),
             render_cards::TextType::Header,
         );
 
-        let all_cards = render_cards::create_list_of_cards(&self.json_obj);
-        let binding = render_cards::ListOfCards::default();
-        let all_photos = self.loaded_photos.to_owned();
-        let all_status = self.live_status.to_owned();
-
-        let first_row = render_cards::create_row(
-            all_cards.get(0).unwrap_or(&binding),
-            &all_photos,
-            0,
-            &self.theme,
-            &all_status,
-        );
-        let second_row = render_cards::create_row(
-            all_cards.get(1).unwrap_or(&binding),
-            &all_photos,
-            4,
-            &self.theme,
-            &all_status,
+        let footer = render_cards::create_text(
+            [self.refresh_status.footer_status(now)]
+                .into_iter()
+                .chain(
+                    self.settings
+                        .daemon_url
+                        .as_ref()
+                        .map(|daemon_url| self.daemon_status.describe(daemon_url, now)),
+                )
+                .chain(static_text(&self.settings.footer_text, "footer-thanks"))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            render_cards::TextType::Footer,
         );
-        let third_row = render_cards::create_row(
-            all_cards.get(2).unwrap_or(&binding),
-            &all_photos,
-            8,
-            &self.theme,
-            &all_status,
+
+        let filter_bar = self.json_obj.all_tags().iter().fold(
+            row![
+                text_input(
+                    &i18n::tr("search-placeholder"),
+                    &self.filter.search_query,
+                    render_cards::Message::SearchChanged,
+                )
+                .id(search_input_id())
+                .padding(5)
+                .width(Length::Fixed(300.0)),
+                checkbox(
+                    i18n::tr("live-only"),
+                    self.filter.live_only,
+                    render_cards::Message::LiveOnlyToggled
+                ),
+                pick_list(
+                    &render_cards::SubscriberRange::ALL[..],
+                    Some(self.filter.subscriber_range),
+                    render_cards::Message::SubscriberRangeChanged,
+                ),
+                checkbox(
+                    i18n::tr("group-by-tag"),
+                    self.group_by_tag,
+                    render_cards::Message::GroupByTagToggled
+                ),
+            ]
+            .spacing(20)
+            .padding(10)
+            .align_items(iced::Alignment::Center),
+            |filter_row, tag| {
+                let toggled_tag = tag.to_string();
+                filter_row.push(checkbox(
+                    format!("#{tag}"),
+                    self.filter.tags.contains(tag),
+                    move |is_selected| {
+                        render_cards::Message::TagFilterToggled(toggled_tag.clone(), is_selected)
+                    },
+                ))
+            },
         );
+        let filter_bar = if self.filter.is_active() {
+            filter_bar.push(
+                button(text(i18n::tr("clear-filters")))
+                    .on_press(render_cards::Message::ClearFilters),
+            )
+        } else {
+            filter_bar
+        };
+
+        let theme_editor: iced::Element<'_, Self::Message> = match &self.theme_editor {
+            Some(theme_editor) => create_theme_editor(theme_editor).into(),
+            None => column![].into(),
+        };
+        let import_panel: iced::Element<'_, Self::Message> = match &self.import_panel {
+            Some(import_panel) => {
+                create_import_panel(import_panel, &self.active_watchlist.name).into()
+            }
+            None => column![].into(),
+        };
+        let export_panel: iced::Element<'_, Self::Message> = match &self.export_panel {
+            Some(export_panel) => create_export_panel(export_panel, &self.settings).into(),
+            None => column![].into(),
+        };
+
+        // Only the creators passing the filter are turned into cards
+        let upcoming_section = match self.show_shortcuts {
+            true => column![],
+            false => create_upcoming_section(self),
+        };
+        let all_cards: iced::Element<'_, Self::Message> = if self.show_shortcuts {
+            container(create_shortcuts_help())
+                .width(Length::Fill)
+                .center_x()
+                .into()
+        } else if self.group_by_tag {
+            scrollable(create_tag_sections(self).padding(10)).into()
+        } else if self.settings.view_mode == ViewMode::List {
+            scrollable(create_list(self, &self.visible_indices).padding(10)).into()
+        } else {
+            let cards_per_row = self.settings.view_mode.columns(self.settings.cards_per_row);
+            let min_rows = render_cards::MAX_EXPECTED_ITEMS.div_ceil(cards_per_row);
+            create_grid(self, &self.visible_indices, Length::Fill, min_rows)
+                .height(Length::Fill)
+                .into()
+        };
 
         container(column![
             row![
-                content.width(Length::Fill).align_items(iced::Alignment::Start),
-                sort_option_content.width(Length::Fill).align_items(iced::Alignment::End)
+                content
+                    .width(Length::Fill)
+                    .align_items(iced::Alignment::Start),
+                sort_option_content
+                    .width(Length::Fill)
+                    .align_items(iced::Alignment::End)
             ],
             horizontal_rule(10),
             title_header.height(Length::Shrink),
             horizontal_rule(10),
-            column![
-                first_row.height(Length::Fill),
-                second_row.height(Length::Fill),
-                third_row.height(Length::Fill)
-            ]
-            .height(Length::Fill),
+            theme_editor,
+            import_panel,
+            export_panel,
+            filter_bar,
+            upcoming_section,
+            container(all_cards).height(Length::Fill),
             horizontal_rule(10),
             footer.height(Length::Shrink),
             horizontal_rule(10),